@@ -0,0 +1,177 @@
+//! Cenas de benchmark do rasterizador: imprime ms por frame em várias resoluções.
+//!
+//! ```bash
+//! cargo run --release --example bench            # todas as cenas
+//! cargo run --release --example bench -- text 50 # só cenas com "text", 50 frames cada
+//! RUSTUI_FONT=/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf cargo run --release --example bench
+//! ```
+
+use ab_glyph::FontArc;
+use rustui::layout::Rect;
+use rustui::parallel::available_threads;
+use rustui::renderer::{
+    DrawCommand, FontAtlas, clear, composite_layer, draw_border, draw_rect, draw_rounded_rect,
+    draw_text_smooth, paint_commands,
+};
+use rustui::shadow::{ShadowCache, draw_box_shadow};
+use rustui::*;
+use std::time::Instant;
+
+const RESOLUTIONS: [(u32, u32); 3] = [(1280, 720), (1920, 1080), (3840, 2160)];
+
+struct Scene {
+    name: &'static str,
+    draw: fn(&mut Bench, &mut [u8], u32, u32),
+}
+
+struct Bench {
+    font: FontArc,
+    atlas: FontAtlas,
+    shadows: ShadowCache,
+    layer: Vec<u8>,
+}
+
+fn scene_clear(_: &mut Bench, f: &mut [u8], _: u32, _: u32) {
+    clear(f, Color::SLATE_900);
+}
+
+/// Dashboard: grade de cards com sombra, borda e gradiente
+fn scene_cards(b: &mut Bench, f: &mut [u8], w: u32, h: u32) {
+    clear(f, Color::SLATE_900);
+    let gradient = Gradient::between(0.4, Color::SLATE_800, Color::BLUE.alpha(200));
+    let (cw, ch) = (220.0, 120.0);
+    let mut y = 16.0;
+    while y + ch < h as f32 {
+        let mut x = 16.0;
+        while x + cw < w as f32 {
+            let rect = Rect { x, y, w: cw, h: ch };
+            let shadow = BoxShadow::new(Color::BLACK.alpha(80)).offset(0.0, 4.0).blur(12.0);
+            draw_box_shadow(f, &mut b.shadows, rect, 12.0, &shadow, w, h);
+            draw_rounded_rect(f, x, y, cw, ch, 12.0, gradient, w, h);
+            draw_border(f, rect, 12.0, &Border::new(1.0, Color::WHITE.alpha(40)), w, h);
+            x += cw + 16.0;
+        }
+        y += ch + 16.0;
+    }
+}
+
+fn scene_text(b: &mut Bench, f: &mut [u8], w: u32, h: u32) {
+    clear(f, Color::SLATE_900);
+    let line = "Receita mensal 1.234.567 — usuários ativos 89.012 — conversão 3,4%";
+    let mut y = 4.0;
+    while y + 18.0 < h as f32 {
+        let mut x = 4.0;
+        while x < w as f32 {
+            draw_text_smooth(f, &mut b.atlas, &b.font, 16.0, x, y, line, Color::WHITE, w, h);
+            x += 560.0;
+        }
+        y += 20.0;
+    }
+}
+
+/// Gráficos de linha com área preenchida
+fn scene_paths(_: &mut Bench, f: &mut [u8], w: u32, h: u32) {
+    clear(f, Color::SLATE_900);
+    let rows = 4;
+    let band = h as f32 / rows as f32;
+    for r in 0..rows {
+        let base = band * (r as f32 + 1.0) - 10.0;
+        let mut line = Path::new().move_to(0.0, base);
+        let mut area = Path::new().move_to(0.0, base);
+        let mut x = 0.0;
+        while x <= w as f32 {
+            let v = base - (band * 0.35) * (1.0 + ((x * 0.013 + r as f32).sin() * (x * 0.0031).cos()));
+            line = line.line_to(x, v);
+            area = area.line_to(x, v);
+            x += 8.0;
+        }
+        area = area.line_to(w as f32, base).close();
+        fill_path(f, &area, FillRule::NonZero, Gradient::linear(std::f32::consts::FRAC_PI_2)
+            .stop(0.0, Color::BLUE.alpha(140))
+            .stop(1.0, Color::BLUE.alpha(0)), w, h);
+        stroke_path(f, &line, &StrokeStyle::new(2.0).join(LineJoin::Round), Color::WHITE, w, h);
+    }
+}
+
+/// Display list pintada em faixas paralelas (o caminho do overlay)
+fn scene_display_list(b: &mut Bench, f: &mut [u8], w: u32, h: u32) {
+    let mut commands = vec![DrawCommand::Rect { x: 0, y: 0, w: w as i32, h: h as i32, color: Color::SLATE_900 }];
+    let mut y = 8.0;
+    while y + 40.0 < h as f32 {
+        let mut x = 8.0;
+        while x + 180.0 < w as f32 {
+            commands.push(DrawCommand::RoundedRect { x, y, w: 180.0, h: 36.0, r: 8.0, color: Color::SLATE_800 });
+            commands.push(DrawCommand::Text { x: x + 12.0, y: y + 9.0, size: 16.0, text: "Item da lista".into(), color: Color::WHITE });
+            x += 190.0;
+        }
+        y += 44.0;
+    }
+    paint_commands(f, &mut b.atlas, &b.font, &commands, w, h);
+}
+
+/// Composição de uma camada do tamanho da tela com opacidade
+fn scene_layer(b: &mut Bench, f: &mut [u8], w: u32, h: u32) {
+    clear(f, Color::SLATE_900);
+    if b.layer.len() != f.len() {
+        b.layer = vec![0; f.len()];
+        draw_rect(&mut b.layer, 0, 0, w as i32, h as i32 / 2, Color::BLUE, w, h);
+        draw_rounded_rect(&mut b.layer, 40.0, 40.0, w as f32 - 80.0, h as f32 - 80.0, 24.0, Color::GREEN.alpha(160), w, h);
+    }
+    let bounds = Rect { x: 0.0, y: 0.0, w: w as f32, h: h as f32 };
+    composite_layer(f, &b.layer, bounds, 0.7, BlendMode::Normal, w, h);
+}
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let filter = args.next().unwrap_or_default();
+    let frames: u32 = args.next().and_then(|n| n.parse().ok()).unwrap_or(20);
+
+    // RUSTUI_FONT=/caminho/fonte.ttf escolhe outra fonte (útil onde não há Arial)
+    let font_data = std::env::var("RUSTUI_FONT")
+        .ok()
+        .and_then(|path| std::fs::read(path).ok())
+        .or_else(|| get_font_with_fallback(Some(config::DEFAULT_FONT_NAME)))
+        .expect("nenhuma fonte encontrada (defina RUSTUI_FONT)");
+    let mut bench = Bench {
+        font: FontArc::try_from_vec(font_data).expect("fonte inválida"),
+        atlas: FontAtlas::new(),
+        shadows: ShadowCache::new(),
+        layer: Vec::new(),
+    };
+
+    let scenes = [
+        Scene { name: "clear", draw: scene_clear },
+        Scene { name: "cards", draw: scene_cards },
+        Scene { name: "text", draw: scene_text },
+        Scene { name: "paths", draw: scene_paths },
+        Scene { name: "display_list", draw: scene_display_list },
+        Scene { name: "layer", draw: scene_layer },
+    ];
+
+    println!("threads de rasterização: {}", available_threads());
+    println!("{:<14} {:>11} {:>10} {:>10}", "cena", "resolução", "ms/frame", "pior ms");
+    for scene in scenes.iter().filter(|s| s.name.contains(filter.as_str())) {
+        for (w, h) in RESOLUTIONS {
+            let mut frame = vec![0u8; (w * h * 4) as usize];
+            // Aquece caches (glifos, máscaras de sombra)
+            (scene.draw)(&mut bench, &mut frame, w, h);
+
+            let mut total = 0.0;
+            let mut worst: f64 = 0.0;
+            for _ in 0..frames {
+                let start = Instant::now();
+                (scene.draw)(&mut bench, &mut frame, w, h);
+                let ms = start.elapsed().as_secs_f64() * 1000.0;
+                total += ms;
+                worst = worst.max(ms);
+            }
+            println!(
+                "{:<14} {:>11} {:>10.2} {:>10.2}",
+                scene.name,
+                format!("{w}x{h}"),
+                total / frames as f64,
+                worst
+            );
+        }
+    }
+}
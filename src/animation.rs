@@ -0,0 +1,1004 @@
+//! Sistema de animação: curvas de easing, springs, keyframes e combinadores
+//! Tudo é dirigido pelo relógio do frame (`Ui::now`); os valores em andamento vivem
+//! no `StateStore` e são acessados por `Ui::animate` (retarget) e `Ui::play` (timelines).
+
+use crate::config::{components, render};
+use crate::layout::Rect;
+use crate::renderer::Color;
+use std::any::Any;
+use std::collections::HashMap;
+use std::f32::consts::PI;
+use std::time::{Duration, Instant};
+
+/// Abaixo disso (em valor e velocidade) uma spring é considerada em repouso
+const REST_EPSILON: f32 = 1e-3;
+/// Passo máximo de integração da spring
+const SPRING_STEP: f32 = 1.0 / 240.0;
+/// Frames muito longos (janela minimizada, breakpoint) não disparam a física
+const MAX_FRAME_DT: f32 = 0.1;
+/// Passo (em progresso 0..1) da derivada numérica das curvas de easing
+const SLOPE_STEP: f32 = 1e-3;
+
+// ============================================================================
+// EASING
+// ============================================================================
+
+/// Curvas de easing nomeadas (ver easings.net), mais cubic-bezier estilo CSS
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Easing {
+    Linear,
+    QuadIn,
+    QuadOut,
+    QuadInOut,
+    CubicIn,
+    CubicOut,
+    CubicInOut,
+    BackIn,
+    BackOut,
+    BackInOut,
+    ElasticIn,
+    ElasticOut,
+    ElasticInOut,
+    BounceIn,
+    BounceOut,
+    BounceInOut,
+    /// Pontos de controle (x1, y1, x2, y2) como em `cubic-bezier()` do CSS
+    CubicBezier(f32, f32, f32, f32),
+}
+
+impl Easing {
+    pub const EASE: Easing = Easing::CubicBezier(0.25, 0.1, 0.25, 1.0);
+    pub const EASE_IN_OUT: Easing = Easing::CubicBezier(0.42, 0.0, 0.58, 1.0);
+
+    /// Mapeia o progresso `t` em [0, 1] (back/elastic podem sair do intervalo)
+    pub fn apply(self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        const C1: f32 = 1.70158;
+        const C2: f32 = C1 * 1.525;
+        const C3: f32 = C1 + 1.0;
+        const C4: f32 = 2.0 * PI / 3.0;
+        const C5: f32 = 2.0 * PI / 4.5;
+
+        match self {
+            Easing::Linear => t,
+            Easing::QuadIn => t * t,
+            Easing::QuadOut => 1.0 - (1.0 - t) * (1.0 - t),
+            Easing::QuadInOut => {
+                if t < 0.5 {
+                    2.0 * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(2) / 2.0
+                }
+            }
+            Easing::CubicIn => t * t * t,
+            Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+            Easing::CubicInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::BackIn => C3 * t * t * t - C1 * t * t,
+            Easing::BackOut => 1.0 + C3 * (t - 1.0).powi(3) + C1 * (t - 1.0).powi(2),
+            Easing::BackInOut => {
+                if t < 0.5 {
+                    (2.0 * t).powi(2) * ((C2 + 1.0) * 2.0 * t - C2) / 2.0
+                } else {
+                    ((2.0 * t - 2.0).powi(2) * ((C2 + 1.0) * (2.0 * t - 2.0) + C2) + 2.0) / 2.0
+                }
+            }
+            Easing::ElasticIn => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else {
+                    -(2f32.powf(10.0 * t - 10.0)) * ((10.0 * t - 10.75) * C4).sin()
+                }
+            }
+            Easing::ElasticOut => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else {
+                    2f32.powf(-10.0 * t) * ((10.0 * t - 0.75) * C4).sin() + 1.0
+                }
+            }
+            Easing::ElasticInOut => {
+                if t == 0.0 || t == 1.0 {
+                    t
+                } else if t < 0.5 {
+                    -(2f32.powf(20.0 * t - 10.0) * ((20.0 * t - 11.125) * C5).sin()) / 2.0
+                } else {
+                    2f32.powf(-20.0 * t + 10.0) * ((20.0 * t - 11.125) * C5).sin() / 2.0 + 1.0
+                }
+            }
+            Easing::BounceIn => 1.0 - bounce_out(1.0 - t),
+            Easing::BounceOut => bounce_out(t),
+            Easing::BounceInOut => {
+                if t < 0.5 {
+                    (1.0 - bounce_out(1.0 - 2.0 * t)) / 2.0
+                } else {
+                    (1.0 + bounce_out(2.0 * t - 1.0)) / 2.0
+                }
+            }
+            Easing::CubicBezier(x1, y1, x2, y2) => cubic_bezier(x1, y1, x2, y2, t),
+        }
+    }
+
+    /// Derivada da curva em `t` (diferença finita dentro de [0, 1])
+    pub fn slope(self, t: f32) -> f32 {
+        let (a, b) = ((t - SLOPE_STEP).max(0.0), (t + SLOPE_STEP).min(1.0));
+        (self.apply(b) - self.apply(a)) / (b - a)
+    }
+}
+
+fn bounce_out(t: f32) -> f32 {
+    const N1: f32 = 7.5625;
+    const D1: f32 = 2.75;
+    if t < 1.0 / D1 {
+        N1 * t * t
+    } else if t < 2.0 / D1 {
+        let t = t - 1.5 / D1;
+        N1 * t * t + 0.75
+    } else if t < 2.5 / D1 {
+        let t = t - 2.25 / D1;
+        N1 * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D1;
+        N1 * t * t + 0.984375
+    }
+}
+
+/// Resolve `x(s) = x` (Newton com fallback de bisseção) e retorna `y(s)`
+fn cubic_bezier(x1: f32, y1: f32, x2: f32, y2: f32, x: f32) -> f32 {
+    let bezier = |a: f32, b: f32, s: f32| {
+        let inv = 1.0 - s;
+        3.0 * inv * inv * s * a + 3.0 * inv * s * s * b + s * s * s
+    };
+    let slope = |a: f32, b: f32, s: f32| {
+        let inv = 1.0 - s;
+        3.0 * inv * inv * a + 6.0 * inv * s * (b - a) + 3.0 * s * s * (1.0 - b)
+    };
+
+    let mut s = x;
+    for _ in 0..8 {
+        let err = bezier(x1, x2, s) - x;
+        if err.abs() < 1e-5 {
+            return bezier(y1, y2, s);
+        }
+        let d = slope(x1, x2, s);
+        if d.abs() < 1e-6 {
+            break;
+        }
+        s -= err / d;
+    }
+
+    let (mut lo, mut hi) = (0.0f32, 1.0f32);
+    s = x;
+    for _ in 0..32 {
+        let v = bezier(x1, x2, s);
+        if (v - x).abs() < 1e-5 {
+            break;
+        }
+        if v < x {
+            lo = s;
+        } else {
+            hi = s;
+        }
+        s = (lo + hi) / 2.0;
+    }
+    bezier(y1, y2, s)
+}
+
+// ============================================================================
+// VALORES ANIMÁVEIS
+// ============================================================================
+
+/// Tipo que pode ser interpolado componente a componente (até 4 componentes)
+pub trait Animatable: Copy + PartialEq + 'static {
+    fn to_components(self) -> [f32; 4];
+    fn from_components(c: [f32; 4]) -> Self;
+
+    fn interpolate(self, other: Self, t: f32) -> Self {
+        let (a, b) = (self.to_components(), other.to_components());
+        Self::from_components(std::array::from_fn(|i| a[i] + (b[i] - a[i]) * t))
+    }
+}
+
+impl Animatable for f32 {
+    fn to_components(self) -> [f32; 4] {
+        [self, 0.0, 0.0, 0.0]
+    }
+
+    fn from_components(c: [f32; 4]) -> Self {
+        c[0]
+    }
+}
+
+/// Pontos 2D
+impl Animatable for (f32, f32) {
+    fn to_components(self) -> [f32; 4] {
+        [self.0, self.1, 0.0, 0.0]
+    }
+
+    fn from_components(c: [f32; 4]) -> Self {
+        (c[0], c[1])
+    }
+}
+
+impl Animatable for Color {
+    fn to_components(self) -> [f32; 4] {
+        [self.r as f32, self.g as f32, self.b as f32, self.a as f32]
+    }
+
+    fn from_components(c: [f32; 4]) -> Self {
+        let channel = |v: f32| v.round().clamp(0.0, 255.0) as u8;
+        Color {
+            r: channel(c[0]),
+            g: channel(c[1]),
+            b: channel(c[2]),
+            a: channel(c[3]),
+        }
+    }
+}
+
+impl Animatable for Rect {
+    fn to_components(self) -> [f32; 4] {
+        [self.x, self.y, self.w, self.h]
+    }
+
+    fn from_components(c: [f32; 4]) -> Self {
+        Rect {
+            x: c[0],
+            y: c[1],
+            w: c[2],
+            h: c[3],
+        }
+    }
+}
+
+// ============================================================================
+// SPRINGS
+// ============================================================================
+
+/// Mola amortecida; mantém a velocidade ao trocar de alvo no meio do caminho
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Spring {
+    pub stiffness: f32,
+    pub damping: f32,
+    pub mass: f32,
+}
+
+impl Spring {
+    pub const GENTLE: Spring = Spring::new(120.0, 14.0);
+    pub const DEFAULT: Spring = Spring::new(170.0, 26.0);
+    pub const SNAPPY: Spring = Spring::new(300.0, 30.0);
+    pub const BOUNCY: Spring = Spring::new(180.0, 12.0);
+
+    pub const fn new(stiffness: f32, damping: f32) -> Self {
+        Self {
+            stiffness,
+            damping,
+            mass: 1.0,
+        }
+    }
+
+    /// Integra um componente por `dt` segundos (Euler semi-implícito em sub-passos)
+    pub fn step(&self, value: &mut f32, velocity: &mut f32, target: f32, dt: f32) {
+        let steps = (dt / SPRING_STEP).ceil().max(1.0) as u32;
+        let h = dt / steps as f32;
+        for _ in 0..steps {
+            let force = -self.stiffness * (*value - target) - self.damping * *velocity;
+            *velocity += force / self.mass * h;
+            *value += *velocity * h;
+        }
+    }
+}
+
+impl Default for Spring {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+// ============================================================================
+// SPEC E TRACK (retarget via `Ui::animate`)
+// ============================================================================
+
+/// Como um valor chega ao alvo
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AnimationCurve {
+    Tween { duration: Duration, easing: Easing },
+    Spring(Spring),
+}
+
+/// Curva + atraso antes de começar
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AnimationSpec {
+    pub curve: AnimationCurve,
+    pub delay: Duration,
+}
+
+impl AnimationSpec {
+    pub fn tween(duration_ms: u64, easing: Easing) -> Self {
+        Self {
+            curve: AnimationCurve::Tween {
+                duration: Duration::from_millis(duration_ms),
+                easing,
+            },
+            delay: Duration::ZERO,
+        }
+    }
+
+    pub fn spring(spring: Spring) -> Self {
+        Self {
+            curve: AnimationCurve::Spring(spring),
+            delay: Duration::ZERO,
+        }
+    }
+
+    pub fn delay(mut self, delay_ms: u64) -> Self {
+        self.delay = Duration::from_millis(delay_ms);
+        self
+    }
+}
+
+impl Default for AnimationSpec {
+    fn default() -> Self {
+        Self::tween(render::ANIMATION_DURATION_MS as u64, Easing::CubicOut)
+    }
+}
+
+/// Estado de um valor animado em direção a um alvo
+#[derive(Clone, Debug)]
+pub struct AnimationTrack<T: Animatable> {
+    target: T,
+    spec: AnimationSpec,
+    from: [f32; 4],
+    /// Velocidade no momento do retarget; o tween parte dela em vez de parar e recomeçar
+    from_velocity: [f32; 4],
+    value: [f32; 4],
+    velocity: [f32; 4],
+    start: Instant,
+    last: Instant,
+    animating: bool,
+}
+
+impl<T: Animatable> AnimationTrack<T> {
+    pub fn new(initial: T, spec: AnimationSpec, now: Instant) -> Self {
+        let value = initial.to_components();
+        Self {
+            target: initial,
+            spec,
+            from: value,
+            from_velocity: [0.0; 4],
+            value,
+            velocity: [0.0; 4],
+            start: now,
+            last: now,
+            animating: false,
+        }
+    }
+
+    pub fn target(&self) -> T {
+        self.target
+    }
+
+    pub fn value(&self) -> T {
+        T::from_components(self.value)
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.animating
+    }
+
+    /// Velocidade atual por componente, em unidades por segundo
+    pub fn velocity(&self) -> [f32; 4] {
+        self.velocity
+    }
+
+    /// Troca o alvo; só reinicia quando ele muda de fato, partindo do valor e da
+    /// velocidade em `now` (reverter no meio do caminho não dá tranco)
+    pub fn set_target(&mut self, target: T, spec: AnimationSpec, now: Instant) {
+        if target == self.target {
+            self.spec = spec;
+            return;
+        }
+        self.update(now);
+        self.spec = spec;
+        self.target = target;
+        self.from = self.value;
+        self.from_velocity = self.velocity;
+        self.start = now;
+        self.animating = true;
+    }
+
+    /// Avança até `now` e retorna o valor atual
+    pub fn update(&mut self, now: Instant) -> T {
+        let dt = now.saturating_duration_since(self.last).as_secs_f32().min(MAX_FRAME_DT);
+        self.last = now;
+        if !self.animating {
+            return self.value();
+        }
+
+        let begin = self.start + self.spec.delay;
+        if now < begin {
+            return self.value();
+        }
+        let target = self.target.to_components();
+
+        match self.spec.curve {
+            AnimationCurve::Tween { duration, easing } => {
+                let seconds = duration.as_secs_f32();
+                let t = if duration.is_zero() {
+                    1.0
+                } else {
+                    ((now - begin).as_secs_f32() / seconds).min(1.0)
+                };
+                // Curva + termo de Hermite (t³ - 2t² + t) que troca a derivada inicial da curva
+                // pela velocidade herdada de um retarget; o termo some em t = 0 e em t = 1,
+                // e partindo do repouso a curva fica intacta
+                let (eased, slope) = (easing.apply(t), easing.slope(t));
+                let (h, h_slope) = (t * t * t - 2.0 * t * t + t, 3.0 * t * t - 4.0 * t + 1.0);
+                let start_slope = easing.slope(0.0);
+                let inverse = 1.0 / seconds.max(f32::EPSILON);
+                for (((value, velocity), (from, from_velocity)), target) in self
+                    .value
+                    .iter_mut()
+                    .zip(&mut self.velocity)
+                    .zip(self.from.iter().zip(&self.from_velocity))
+                    .zip(target)
+                {
+                    let delta = target - from;
+                    let correction = if *from_velocity == 0.0 {
+                        0.0
+                    } else {
+                        from_velocity * seconds - delta * start_slope
+                    };
+                    *value = from + delta * eased + correction * h;
+                    *velocity = (delta * slope + correction * h_slope) * inverse;
+                }
+                if t >= 1.0 {
+                    self.finish();
+                }
+            }
+            AnimationCurve::Spring(spring) => {
+                let mut at_rest = true;
+                for ((value, velocity), target) in
+                    self.value.iter_mut().zip(&mut self.velocity).zip(target)
+                {
+                    spring.step(value, velocity, target, dt);
+                    at_rest &= (*value - target).abs() < REST_EPSILON && velocity.abs() < REST_EPSILON;
+                }
+                if at_rest {
+                    self.finish();
+                }
+            }
+        }
+        self.value()
+    }
+
+    fn finish(&mut self) {
+        self.value = self.target.to_components();
+        self.velocity = [0.0; 4];
+        self.animating = false;
+    }
+}
+
+// ============================================================================
+// TRANSIÇÕES DE ENTRADA/SAÍDA
+// ============================================================================
+
+/// Borda de onde o conteúdo entra (e para onde sai) num slide
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Edge {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+/// Transição usada por `animated_visibility`; os presets se combinam com `and`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transition {
+    pub fade: bool,
+    /// Borda e distância (px) do slide
+    pub slide: Option<(Edge, f32)>,
+    /// Cresce/encolhe verticalmente, empurrando os irmãos abaixo
+    pub expand: bool,
+    pub duration_ms: f64,
+}
+
+impl Transition {
+    const NONE: Transition = Transition {
+        fade: false,
+        slide: None,
+        expand: false,
+        duration_ms: render::TRANSITION_DURATION_MS,
+    };
+
+    pub const fn fade() -> Self {
+        Self {
+            fade: true,
+            ..Self::NONE
+        }
+    }
+
+    pub const fn slide(edge: Edge) -> Self {
+        Self {
+            slide: Some((edge, components::TRANSITION_SLIDE_DISTANCE)),
+            ..Self::NONE
+        }
+    }
+
+    pub const fn expand() -> Self {
+        Self {
+            expand: true,
+            ..Self::NONE
+        }
+    }
+
+    /// Une os efeitos das duas transições (a duração é a maior)
+    pub fn and(self, other: Transition) -> Self {
+        Self {
+            fade: self.fade || other.fade,
+            slide: self.slide.or(other.slide),
+            expand: self.expand || other.expand,
+            duration_ms: self.duration_ms.max(other.duration_ms),
+        }
+    }
+
+    pub const fn duration(mut self, duration_ms: f64) -> Self {
+        self.duration_ms = duration_ms;
+        self
+    }
+}
+
+impl Default for Transition {
+    fn default() -> Self {
+        Self::fade()
+    }
+}
+
+// ============================================================================
+// TIMELINES (keyframes, delay, repeat, sequência e paralelo)
+// ============================================================================
+
+/// Valor em função do tempo desde o início da animação
+pub trait Timeline: 'static {
+    type Value;
+
+    /// Duração total; `None` para repetição infinita
+    fn duration(&self) -> Option<Duration>;
+
+    fn sample(&self, t: Duration) -> Self::Value;
+
+    fn delay(self, delay_ms: u64) -> Delay<Self>
+    where
+        Self: Sized,
+    {
+        Delay {
+            inner: self,
+            delay: Duration::from_millis(delay_ms),
+        }
+    }
+
+    /// Repete `count` vezes (`None` = para sempre)
+    fn repeat(self, count: Option<u32>) -> Repeat<Self>
+    where
+        Self: Sized,
+    {
+        Repeat {
+            inner: self,
+            count,
+            yoyo: false,
+        }
+    }
+
+    /// Repete indo e voltando
+    fn yoyo(self, count: Option<u32>) -> Repeat<Self>
+    where
+        Self: Sized,
+    {
+        Repeat {
+            inner: self,
+            count,
+            yoyo: true,
+        }
+    }
+
+    /// Toca `next` depois desta
+    fn then<B>(self, next: B) -> Sequence<Self::Value>
+    where
+        Self: Sized,
+        B: Timeline<Value = Self::Value>,
+    {
+        Sequence::new(self).push(next)
+    }
+
+    /// Toca `other` ao mesmo tempo, retornando os dois valores
+    fn with<B: Timeline>(self, other: B) -> Parallel<Self, B>
+    where
+        Self: Sized,
+    {
+        Parallel { a: self, b: other }
+    }
+}
+
+/// Interpolação simples de `from` até `to`
+#[derive(Clone, Copy, Debug)]
+pub struct Tween<T: Animatable> {
+    pub from: T,
+    pub to: T,
+    pub duration: Duration,
+    pub easing: Easing,
+}
+
+impl<T: Animatable> Tween<T> {
+    pub fn new(from: T, to: T, duration_ms: u64, easing: Easing) -> Self {
+        Self {
+            from,
+            to,
+            duration: Duration::from_millis(duration_ms),
+            easing,
+        }
+    }
+}
+
+impl<T: Animatable> Timeline for Tween<T> {
+    type Value = T;
+
+    fn duration(&self) -> Option<Duration> {
+        Some(self.duration)
+    }
+
+    fn sample(&self, t: Duration) -> T {
+        if t >= self.duration {
+            return self.to;
+        }
+        let progress = t.as_secs_f32() / self.duration.as_secs_f32();
+        self.from.interpolate(self.to, self.easing.apply(progress))
+    }
+}
+
+/// Trilha de keyframes: cada segmento chega ao seu valor com a própria curva
+#[derive(Clone, Debug)]
+pub struct Keyframes<T: Animatable> {
+    initial: T,
+    frames: Vec<(Duration, T, Easing)>,
+}
+
+impl<T: Animatable> Keyframes<T> {
+    pub fn new(initial: T) -> Self {
+        Self {
+            initial,
+            frames: Vec::new(),
+        }
+    }
+
+    /// Adiciona um segmento de `duration_ms` até `value`
+    pub fn to(mut self, value: T, duration_ms: u64, easing: Easing) -> Self {
+        self.frames
+            .push((Duration::from_millis(duration_ms), value, easing));
+        self
+    }
+}
+
+impl<T: Animatable> Timeline for Keyframes<T> {
+    type Value = T;
+
+    fn duration(&self) -> Option<Duration> {
+        Some(self.frames.iter().map(|(d, _, _)| *d).sum())
+    }
+
+    fn sample(&self, t: Duration) -> T {
+        let mut from = self.initial;
+        let mut elapsed = Duration::ZERO;
+        for &(duration, value, easing) in &self.frames {
+            if t < elapsed + duration {
+                let progress = (t - elapsed).as_secs_f32() / duration.as_secs_f32();
+                return from.interpolate(value, easing.apply(progress));
+            }
+            elapsed += duration;
+            from = value;
+        }
+        from
+    }
+}
+
+/// Segura o primeiro valor por um tempo antes de começar
+#[derive(Clone, Debug)]
+pub struct Delay<A> {
+    inner: A,
+    delay: Duration,
+}
+
+impl<A: Timeline> Timeline for Delay<A> {
+    type Value = A::Value;
+
+    fn duration(&self) -> Option<Duration> {
+        self.inner.duration().map(|d| d + self.delay)
+    }
+
+    fn sample(&self, t: Duration) -> A::Value {
+        self.inner.sample(t.saturating_sub(self.delay))
+    }
+}
+
+/// Repetição, opcionalmente em vai-e-volta
+#[derive(Clone, Debug)]
+pub struct Repeat<A> {
+    inner: A,
+    count: Option<u32>,
+    yoyo: bool,
+}
+
+impl<A: Timeline> Timeline for Repeat<A> {
+    type Value = A::Value;
+
+    fn duration(&self) -> Option<Duration> {
+        let count = self.count?;
+        self.inner.duration().map(|d| d * count)
+    }
+
+    fn sample(&self, t: Duration) -> A::Value {
+        let cycle = match self.inner.duration() {
+            Some(d) if !d.is_zero() => d,
+            _ => return self.inner.sample(t),
+        };
+
+        let mut index = (t.as_nanos() / cycle.as_nanos()) as u64;
+        let mut local = Duration::from_nanos((t.as_nanos() % cycle.as_nanos()) as u64);
+        if let Some(count) = self.count
+            && index >= count as u64
+        {
+            // Terminou: para no fim do último ciclo
+            index = count.saturating_sub(1) as u64;
+            local = cycle;
+        }
+
+        if self.yoyo && index % 2 == 1 {
+            self.inner.sample(cycle - local)
+        } else {
+            self.inner.sample(local)
+        }
+    }
+}
+
+/// Timelines tocadas uma após a outra
+pub struct Sequence<T> {
+    items: Vec<Box<dyn Timeline<Value = T>>>,
+}
+
+impl<T: 'static> Sequence<T> {
+    pub fn new(first: impl Timeline<Value = T>) -> Self {
+        Self {
+            items: vec![Box::new(first)],
+        }
+    }
+
+    pub fn push(mut self, item: impl Timeline<Value = T>) -> Self {
+        self.items.push(Box::new(item));
+        self
+    }
+}
+
+impl<T: 'static> Timeline for Sequence<T> {
+    type Value = T;
+
+    fn duration(&self) -> Option<Duration> {
+        self.items.iter().map(|item| item.duration()).sum()
+    }
+
+    fn sample(&self, t: Duration) -> T {
+        let (last, rest) = self.items.split_last().expect("Sequence tem ao menos um item");
+        let mut elapsed = Duration::ZERO;
+        for item in rest {
+            // Um item infinito segura a sequência nele
+            match item.duration() {
+                Some(d) if t >= elapsed + d => elapsed += d,
+                _ => return item.sample(t - elapsed),
+            }
+        }
+        last.sample(t - elapsed)
+    }
+}
+
+/// Duas timelines ao mesmo tempo
+#[derive(Clone, Debug)]
+pub struct Parallel<A, B> {
+    a: A,
+    b: B,
+}
+
+impl<A: Timeline, B: Timeline> Timeline for Parallel<A, B> {
+    type Value = (A::Value, B::Value);
+
+    fn duration(&self) -> Option<Duration> {
+        Some(self.a.duration()?.max(self.b.duration()?))
+    }
+
+    fn sample(&self, t: Duration) -> Self::Value {
+        (self.a.sample(t), self.b.sample(t))
+    }
+}
+
+// ============================================================================
+// STORE
+// ============================================================================
+
+/// Animações em andamento por ID (separadas dos estados de widget)
+#[derive(Default)]
+pub struct AnimationStore {
+    tracks: HashMap<u64, Box<dyn Any>>,
+    timelines: HashMap<u64, Instant>,
+}
+
+impl AnimationStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Track do ID, criada no valor `target` na primeira vez (ou se o tipo mudou)
+    pub fn track<T: Animatable>(
+        &mut self,
+        id: u64,
+        target: T,
+        spec: AnimationSpec,
+        now: Instant,
+    ) -> &mut AnimationTrack<T> {
+        let entry = self
+            .tracks
+            .entry(id)
+            .or_insert_with(|| Box::new(AnimationTrack::new(target, spec, now)));
+        if !entry.is::<AnimationTrack<T>>() {
+            *entry = Box::new(AnimationTrack::new(target, spec, now));
+        }
+        entry
+            .downcast_mut::<AnimationTrack<T>>()
+            .expect("tipo da animação verificado acima")
+    }
+
+    /// Instante em que a timeline do ID começou a tocar
+    pub fn timeline_start(&mut self, id: u64, now: Instant) -> Instant {
+        *self.timelines.entry(id).or_insert(now)
+    }
+
+    pub fn restart(&mut self, id: u64) {
+        self.tracks.remove(&id);
+        self.timelines.remove(&id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_EASINGS: [Easing; 18] = [
+        Easing::Linear,
+        Easing::QuadIn,
+        Easing::QuadOut,
+        Easing::QuadInOut,
+        Easing::CubicIn,
+        Easing::CubicOut,
+        Easing::CubicInOut,
+        Easing::BackIn,
+        Easing::BackOut,
+        Easing::BackInOut,
+        Easing::ElasticIn,
+        Easing::ElasticOut,
+        Easing::ElasticInOut,
+        Easing::BounceIn,
+        Easing::BounceOut,
+        Easing::BounceInOut,
+        Easing::EASE,
+        Easing::EASE_IN_OUT,
+    ];
+
+    #[test]
+    fn easings_start_at_zero_and_end_at_one() {
+        for easing in ALL_EASINGS {
+            assert!(easing.apply(0.0).abs() < 1e-4, "{easing:?} em 0");
+            assert!((easing.apply(1.0) - 1.0).abs() < 1e-4, "{easing:?} em 1");
+            // Fora do intervalo o progresso é limitado
+            assert!(easing.apply(-1.0).abs() < 1e-4, "{easing:?} abaixo de 0");
+            assert!((easing.apply(2.0) - 1.0).abs() < 1e-4, "{easing:?} acima de 1");
+        }
+    }
+
+    #[test]
+    fn cubic_bezier_matches_linear_on_the_diagonal() {
+        let linear = Easing::CubicBezier(0.0, 0.0, 1.0, 1.0);
+        for i in 0..=10 {
+            let t = i as f32 / 10.0;
+            assert!((linear.apply(t) - t).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn springs_settle_on_the_target() {
+        for spring in [Spring::GENTLE, Spring::DEFAULT, Spring::SNAPPY, Spring::BOUNCY] {
+            let (mut value, mut velocity) = (0.0, 0.0);
+            for _ in 0..300 {
+                spring.step(&mut value, &mut velocity, 100.0, 1.0 / 60.0);
+            }
+            assert!((value - 100.0).abs() < 0.01, "{spring:?} parou em {value}");
+            assert!(velocity.abs() < 0.01);
+        }
+    }
+
+    #[test]
+    fn tracks_reach_their_target() {
+        let start = Instant::now();
+        let frame = Duration::from_millis(16);
+
+        let spec = AnimationSpec::tween(100, Easing::CubicOut);
+        let mut track = AnimationTrack::new(0.0f32, spec, start);
+        track.set_target(10.0, spec, start);
+        assert!(track.is_animating());
+        let mut now = start;
+        for _ in 0..10 {
+            now += frame;
+            track.update(now);
+        }
+        assert_eq!(track.value(), 10.0);
+        assert!(!track.is_animating());
+
+        let spec = AnimationSpec::spring(Spring::SNAPPY);
+        let mut track = AnimationTrack::new(0.0f32, spec, start);
+        track.set_target(-5.0, spec, start);
+        let mut now = start;
+        for _ in 0..300 {
+            now += frame;
+            track.update(now);
+        }
+        assert_eq!(track.value(), -5.0);
+        assert!(!track.is_animating());
+    }
+
+    #[test]
+    fn reversing_a_tween_keeps_value_and_velocity() {
+        let start = Instant::now();
+        let spec = AnimationSpec::tween(200, Easing::CubicOut);
+        let mut track = AnimationTrack::new(0.0f32, spec, start);
+        track.set_target(1.0, spec, start);
+
+        let mid = start + Duration::from_millis(100);
+        let value = track.update(mid);
+        let velocity = track.velocity()[0];
+        assert!(velocity > 0.0);
+
+        // Reverte no mesmo instante: nem o valor nem a velocidade saltam
+        track.set_target(0.0, spec, mid);
+        assert_eq!(track.update(mid), value);
+        assert!((track.velocity()[0] - velocity).abs() < velocity * 0.01);
+
+        // Logo depois ainda segue na direção antiga, na mesma velocidade
+        let dt = 0.001;
+        let next = track.update(mid + Duration::from_secs_f32(dt));
+        assert!(next > value);
+        assert!(((next - value) / dt - velocity).abs() < velocity * 0.05);
+
+        // E termina no novo alvo
+        assert_eq!(track.update(mid + Duration::from_millis(200)), 0.0);
+        assert_eq!(track.velocity(), [0.0; 4]);
+    }
+
+    #[test]
+    fn delayed_tracks_hold_the_start_value() {
+        let start = Instant::now();
+        let spec = AnimationSpec::tween(100, Easing::Linear).delay(50);
+        let mut track = AnimationTrack::new(0.0f32, spec, start);
+        track.set_target(1.0, spec, start);
+        assert_eq!(track.update(start + Duration::from_millis(40)), 0.0);
+        let mid = track.update(start + Duration::from_millis(100));
+        assert!((mid - 0.5).abs() < 1e-3);
+    }
+
+    #[test]
+    fn tweens_end_at_their_final_value() {
+        let tween = Tween::new(0.0f32, 4.0, 200, Easing::BounceOut);
+        assert_eq!(tween.sample(Duration::ZERO), 0.0);
+        assert_eq!(tween.sample(Duration::from_millis(200)), 4.0);
+        assert_eq!(tween.sample(Duration::from_secs(5)), 4.0);
+    }
+}
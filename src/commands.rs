@@ -0,0 +1,614 @@
+//! Registro global de comandos e atalhos de teclado
+//! Comandos nomeados com chords ("Ctrl+Shift+P") ou sequências ("g g"),
+//! com escopo por região de foco, detecção de conflitos e listagem para paletas/ajuda
+
+use crate::core::InputState;
+use std::collections::HashSet;
+use std::fmt;
+use std::time::{Duration, Instant};
+use winit::event::VirtualKeyCode;
+
+/// Tempo máximo entre as teclas de uma sequência ("g g")
+pub const SEQUENCE_TIMEOUT: Duration = Duration::from_millis(1000);
+
+// ============================================================================
+// KEY CHORDS E BINDINGS
+// ============================================================================
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct KeyMods {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+}
+
+impl KeyMods {
+    pub const NONE: KeyMods = KeyMods {
+        ctrl: false,
+        shift: false,
+        alt: false,
+    };
+
+    pub fn from_input(input: &InputState) -> Self {
+        Self {
+            ctrl: input.ctrl(),
+            shift: input.shift(),
+            alt: input.alt(),
+        }
+    }
+}
+
+/// Uma tecla com modificadores, ex: Ctrl+Shift+P
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+    pub key: VirtualKeyCode,
+    pub mods: KeyMods,
+}
+
+impl KeyChord {
+    pub fn new(key: VirtualKeyCode, mods: KeyMods) -> Self {
+        Self { key, mods }
+    }
+
+    /// Chord que produziria texto em um input (sem Ctrl/Alt, tecla imprimível)
+    pub fn is_text(&self) -> bool {
+        !self.mods.ctrl && !self.mods.alt && key_produces_text(self.key)
+    }
+
+    fn parse(token: &str) -> Result<Self, CommandError> {
+        let mut mods = KeyMods::NONE;
+        let mut key = None;
+        for part in token.split('+') {
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" | "cmd" => mods.ctrl = true,
+                "shift" => mods.shift = true,
+                "alt" | "option" => mods.alt = true,
+                name if key.is_none() => {
+                    key = Some(
+                        key_from_name(name)
+                            .ok_or_else(|| CommandError::InvalidBinding(token.to_string()))?,
+                    );
+                }
+                _ => return Err(CommandError::InvalidBinding(token.to_string())),
+            }
+        }
+        key.map(|key| Self { key, mods })
+            .ok_or_else(|| CommandError::InvalidBinding(token.to_string()))
+    }
+}
+
+impl fmt::Display for KeyChord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.mods.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.mods.shift {
+            write!(f, "Shift+")?;
+        }
+        if self.mods.alt {
+            write!(f, "Alt+")?;
+        }
+        write!(f, "{}", key_name(self.key))
+    }
+}
+
+/// Sequência de chords que dispara um comando ("Ctrl+K Ctrl+S", "g g")
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyBinding(pub Vec<KeyChord>);
+
+impl KeyBinding {
+    /// Chords separados por espaço, modificadores por `+`: "Ctrl+Shift+P", "g g", "F2"
+    pub fn parse(s: &str) -> Result<Self, CommandError> {
+        let chords = s
+            .split_whitespace()
+            .map(KeyChord::parse)
+            .collect::<Result<Vec<_>, _>>()?;
+        if chords.is_empty() {
+            return Err(CommandError::InvalidBinding(s.to_string()));
+        }
+        Ok(Self(chords))
+    }
+
+    pub fn chords(&self) -> &[KeyChord] {
+        &self.0
+    }
+
+    /// `self` é prefixo (ou igual) de `other`
+    pub fn is_prefix_of(&self, other: &KeyBinding) -> bool {
+        other.0.starts_with(&self.0)
+    }
+}
+
+impl fmt::Display for KeyBinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, chord) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}", chord)?;
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// COMANDOS
+// ============================================================================
+
+/// Onde um binding está ativo
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CommandScope {
+    /// Sempre ativo
+    Global,
+    /// Ativo apenas quando o foco está dentro da região (ver `Ui::focus_region`)
+    Region(u64),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum CommandError {
+    InvalidBinding(String),
+    /// O binding colide (igual ou prefixo) com outro comando no mesmo escopo, ou é
+    /// prefixo estrito de um atalho global (ou vice-versa) e o tornaria inalcançável
+    Conflict {
+        binding: KeyBinding,
+        scope: CommandScope,
+        existing: String,
+    },
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::InvalidBinding(s) => write!(f, "atalho inválido: {:?}", s),
+            CommandError::Conflict {
+                binding,
+                scope,
+                existing,
+            } => write!(
+                f,
+                "atalho {} ({:?}) conflita com o comando {:?}",
+                binding, scope, existing
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {}
+
+#[derive(Clone, Debug)]
+pub struct Command {
+    pub name: String,
+    pub description: String,
+    pub bindings: Vec<(KeyBinding, CommandScope)>,
+}
+
+#[derive(Default)]
+pub struct CommandRegistry {
+    commands: Vec<Command>,
+    /// Chords já digitados de uma sequência em andamento
+    pending: Vec<KeyChord>,
+    last_chord_at: Option<Instant>,
+    triggered: HashSet<String>,
+    reported_conflicts: HashSet<String>,
+}
+
+impl CommandRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registra (ou reafirma) um comando com um atalho.
+    /// Registrar o mesmo comando/atalho/escopo de novo é um no-op, então pode ser chamado a cada frame.
+    pub fn register(
+        &mut self,
+        name: &str,
+        description: &str,
+        binding: &str,
+        scope: CommandScope,
+    ) -> Result<(), CommandError> {
+        let binding = KeyBinding::parse(binding)?;
+
+        for command in &self.commands {
+            if command.name == name {
+                continue;
+            }
+            for (other, other_scope) in &command.bindings {
+                let prefix = binding.is_prefix_of(other) || other.is_prefix_of(&binding);
+                // Um global vale dentro de qualquer região: se um for prefixo estrito do outro,
+                // o mais curto dispara antes de a sequência mais longa terminar. Atalho igual
+                // em escopos diferentes é sobrescrita intencional (a região vence)
+                let overlapping = *other_scope == scope
+                    || ((*other_scope == CommandScope::Global || scope == CommandScope::Global)
+                        && binding != *other);
+                if prefix && overlapping {
+                    return Err(CommandError::Conflict {
+                        binding,
+                        scope,
+                        existing: command.name.clone(),
+                    });
+                }
+            }
+        }
+
+        match self.commands.iter_mut().find(|c| c.name == name) {
+            Some(command) => {
+                command.description = description.to_string();
+                if !command.bindings.contains(&(binding.clone(), scope)) {
+                    command.bindings.push((binding, scope));
+                }
+            }
+            None => self.commands.push(Command {
+                name: name.to_string(),
+                description: description.to_string(),
+                bindings: vec![(binding, scope)],
+            }),
+        }
+        Ok(())
+    }
+
+    /// Igual a `register`, mas só loga conflitos (uma vez por comando)
+    pub fn register_or_warn(&mut self, name: &str, description: &str, binding: &str, scope: CommandScope) {
+        if let Err(err) = self.register(name, description, binding, scope)
+            && self.reported_conflicts.insert(format!("{}|{}", name, binding))
+        {
+            log::warn!("Comando {:?} não registrado: {}", name, err);
+        }
+    }
+
+    pub fn unregister(&mut self, name: &str) {
+        self.commands.retain(|c| c.name != name);
+    }
+
+    /// Todos os comandos, na ordem de registro (para paleta de comandos / overlay de ajuda)
+    pub fn commands(&self) -> &[Command] {
+        &self.commands
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Command> {
+        self.commands.iter().find(|c| c.name == name)
+    }
+
+    /// Atalhos formatados de um comando, ex: ["Ctrl+Shift+P"]
+    pub fn bindings_for(&self, name: &str) -> Vec<String> {
+        self.get(name)
+            .map(|c| c.bindings.iter().map(|(b, _)| b.to_string()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Dispara um comando manualmente (ex: escolhido na paleta)
+    pub fn trigger(&mut self, name: &str) {
+        self.triggered.insert(name.to_string());
+    }
+
+    /// O comando disparou neste frame
+    pub fn triggered(&self, name: &str) -> bool {
+        self.triggered.contains(name)
+    }
+
+    /// Sequência em andamento, para exibir "g ..." na UI
+    pub fn pending(&self) -> &[KeyChord] {
+        &self.pending
+    }
+
+    /// Processa as teclas do frame. `active_regions` é a cadeia de regiões do widget focado
+    /// (da mais externa para a mais interna); `text_focused` suprime atalhos que digitariam texto.
+    pub fn process(&mut self, input: &InputState, active_regions: &[u64], text_focused: bool, now: Instant) {
+        self.triggered.clear();
+
+        if self
+            .last_chord_at
+            .is_some_and(|t| now.duration_since(t) > SEQUENCE_TIMEOUT)
+        {
+            self.pending.clear();
+        }
+
+        let mods = KeyMods::from_input(input);
+        for (idx, &just) in input.keys_just_pressed.iter().enumerate() {
+            if !just {
+                continue;
+            }
+            // Modificadores sozinhos não estão em NAMED_KEYS e são ignorados aqui
+            let Some(key) = key_from_index(idx) else {
+                continue;
+            };
+            let chord = KeyChord::new(key, mods);
+            if text_focused && self.pending.is_empty() && chord.is_text() {
+                continue;
+            }
+            self.last_chord_at = Some(now);
+            self.pending.push(chord);
+
+            if !self.advance(active_regions) {
+                // Sequência quebrada: tenta recomeçar a partir deste chord
+                self.pending = vec![chord];
+                if !self.advance(active_regions) {
+                    self.pending.clear();
+                }
+            }
+        }
+    }
+
+    /// Tenta casar `pending`; retorna false se nenhum binding começa com ele
+    fn advance(&mut self, active_regions: &[u64]) -> bool {
+        let pending = KeyBinding(self.pending.clone());
+        let mut best: Option<(usize, &str)> = None;
+        let mut is_prefix = false;
+
+        for command in &self.commands {
+            for (binding, scope) in &command.bindings {
+                // Especificidade: região mais interna > região externa > global
+                let rank = match scope {
+                    CommandScope::Global => 0,
+                    CommandScope::Region(id) => match active_regions.iter().position(|r| r == id) {
+                        Some(depth) => depth + 1,
+                        None => continue,
+                    },
+                };
+                if *binding == pending {
+                    if best.is_none_or(|(r, _)| rank > r) {
+                        best = Some((rank, &command.name));
+                    }
+                } else if pending.is_prefix_of(binding) {
+                    is_prefix = true;
+                }
+            }
+        }
+
+        if let Some((_, name)) = best {
+            self.triggered.insert(name.to_string());
+            self.pending.clear();
+            return true;
+        }
+        is_prefix
+    }
+}
+
+// ============================================================================
+// NOMES DE TECLAS
+// ============================================================================
+
+const NAMED_KEYS: &[(&str, VirtualKeyCode)] = &[
+    ("a", VirtualKeyCode::A),
+    ("b", VirtualKeyCode::B),
+    ("c", VirtualKeyCode::C),
+    ("d", VirtualKeyCode::D),
+    ("e", VirtualKeyCode::E),
+    ("f", VirtualKeyCode::F),
+    ("g", VirtualKeyCode::G),
+    ("h", VirtualKeyCode::H),
+    ("i", VirtualKeyCode::I),
+    ("j", VirtualKeyCode::J),
+    ("k", VirtualKeyCode::K),
+    ("l", VirtualKeyCode::L),
+    ("m", VirtualKeyCode::M),
+    ("n", VirtualKeyCode::N),
+    ("o", VirtualKeyCode::O),
+    ("p", VirtualKeyCode::P),
+    ("q", VirtualKeyCode::Q),
+    ("r", VirtualKeyCode::R),
+    ("s", VirtualKeyCode::S),
+    ("t", VirtualKeyCode::T),
+    ("u", VirtualKeyCode::U),
+    ("v", VirtualKeyCode::V),
+    ("w", VirtualKeyCode::W),
+    ("x", VirtualKeyCode::X),
+    ("y", VirtualKeyCode::Y),
+    ("z", VirtualKeyCode::Z),
+    ("0", VirtualKeyCode::Key0),
+    ("1", VirtualKeyCode::Key1),
+    ("2", VirtualKeyCode::Key2),
+    ("3", VirtualKeyCode::Key3),
+    ("4", VirtualKeyCode::Key4),
+    ("5", VirtualKeyCode::Key5),
+    ("6", VirtualKeyCode::Key6),
+    ("7", VirtualKeyCode::Key7),
+    ("8", VirtualKeyCode::Key8),
+    ("9", VirtualKeyCode::Key9),
+    ("f1", VirtualKeyCode::F1),
+    ("f2", VirtualKeyCode::F2),
+    ("f3", VirtualKeyCode::F3),
+    ("f4", VirtualKeyCode::F4),
+    ("f5", VirtualKeyCode::F5),
+    ("f6", VirtualKeyCode::F6),
+    ("f7", VirtualKeyCode::F7),
+    ("f8", VirtualKeyCode::F8),
+    ("f9", VirtualKeyCode::F9),
+    ("f10", VirtualKeyCode::F10),
+    ("f11", VirtualKeyCode::F11),
+    ("f12", VirtualKeyCode::F12),
+    ("enter", VirtualKeyCode::Return),
+    ("return", VirtualKeyCode::Return),
+    ("esc", VirtualKeyCode::Escape),
+    ("escape", VirtualKeyCode::Escape),
+    ("tab", VirtualKeyCode::Tab),
+    ("space", VirtualKeyCode::Space),
+    ("backspace", VirtualKeyCode::Back),
+    ("delete", VirtualKeyCode::Delete),
+    ("insert", VirtualKeyCode::Insert),
+    ("home", VirtualKeyCode::Home),
+    ("end", VirtualKeyCode::End),
+    ("pageup", VirtualKeyCode::PageUp),
+    ("pagedown", VirtualKeyCode::PageDown),
+    ("up", VirtualKeyCode::Up),
+    ("down", VirtualKeyCode::Down),
+    ("left", VirtualKeyCode::Left),
+    ("right", VirtualKeyCode::Right),
+    (",", VirtualKeyCode::Comma),
+    (".", VirtualKeyCode::Period),
+    ("/", VirtualKeyCode::Slash),
+    ("-", VirtualKeyCode::Minus),
+    ("=", VirtualKeyCode::Equals),
+    (";", VirtualKeyCode::Semicolon),
+    ("[", VirtualKeyCode::LBracket),
+    ("]", VirtualKeyCode::RBracket),
+];
+
+fn key_from_name(name: &str) -> Option<VirtualKeyCode> {
+    NAMED_KEYS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, key)| *key)
+}
+
+fn key_name(key: VirtualKeyCode) -> String {
+    match NAMED_KEYS.iter().find(|(_, k)| *k == key) {
+        Some((name, _)) if name.len() == 1 => name.to_ascii_uppercase(),
+        Some((name, _)) => {
+            let mut chars = name.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        }
+        None => format!("{:?}", key),
+    }
+}
+
+/// Converte o índice de `InputState::keys_pressed` de volta para a tecla
+fn key_from_index(idx: usize) -> Option<VirtualKeyCode> {
+    NAMED_KEYS
+        .iter()
+        .map(|(_, key)| *key)
+        .chain([VirtualKeyCode::NumpadEnter])
+        .find(|key| *key as usize == idx)
+}
+
+fn key_produces_text(key: VirtualKeyCode) -> bool {
+    let idx = key as usize;
+    (VirtualKeyCode::Key1 as usize..=VirtualKeyCode::Z as usize).contains(&idx)
+        || matches!(
+            key,
+            VirtualKeyCode::Space
+                | VirtualKeyCode::Back
+                | VirtualKeyCode::Comma
+                | VirtualKeyCode::Period
+                | VirtualKeyCode::Slash
+                | VirtualKeyCode::Minus
+                | VirtualKeyCode::Equals
+                | VirtualKeyCode::Semicolon
+                | VirtualKeyCode::LBracket
+                | VirtualKeyCode::RBracket
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ctrl() -> KeyMods {
+        KeyMods {
+            ctrl: true,
+            ..KeyMods::NONE
+        }
+    }
+
+    #[test]
+    fn parses_chords_with_modifiers() {
+        let binding = KeyBinding::parse("Ctrl+Shift+P").unwrap();
+        let mods = KeyMods {
+            shift: true,
+            ..ctrl()
+        };
+        assert_eq!(binding.chords(), &[KeyChord::new(VirtualKeyCode::P, mods)]);
+        assert_eq!(binding.to_string(), "Ctrl+Shift+P");
+
+        // Modificadores em qualquer ordem e caixa
+        assert_eq!(KeyBinding::parse("shift+control+p").unwrap(), binding);
+        assert_eq!(
+            KeyBinding::parse("F2").unwrap().chords(),
+            &[KeyChord::new(VirtualKeyCode::F2, KeyMods::NONE)]
+        );
+    }
+
+    #[test]
+    fn parses_sequences() {
+        let binding = KeyBinding::parse("Ctrl+K  Ctrl+S").unwrap();
+        assert_eq!(
+            binding.chords(),
+            &[
+                KeyChord::new(VirtualKeyCode::K, ctrl()),
+                KeyChord::new(VirtualKeyCode::S, ctrl()),
+            ]
+        );
+        assert_eq!(binding.to_string(), "Ctrl+K Ctrl+S");
+        assert_eq!(KeyBinding::parse("g g").unwrap().chords().len(), 2);
+    }
+
+    #[test]
+    fn rejects_invalid_bindings() {
+        for s in ["", "   ", "Ctrl+", "Ctrl+Shift", "Ctrl+P+Q", "Hyper+P", "NoSuchKey"] {
+            assert!(
+                matches!(KeyBinding::parse(s), Err(CommandError::InvalidBinding(_))),
+                "{:?} deveria ser inválido",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn prefix_detection() {
+        let g = KeyBinding::parse("g").unwrap();
+        let gg = KeyBinding::parse("g g").unwrap();
+        assert!(g.is_prefix_of(&gg));
+        assert!(gg.is_prefix_of(&gg));
+        assert!(!gg.is_prefix_of(&g));
+        assert!(!KeyBinding::parse("Ctrl+G").unwrap().is_prefix_of(&gg));
+    }
+
+    #[test]
+    fn detects_conflicts_in_the_same_scope() {
+        let mut registry = CommandRegistry::new();
+        registry.register("goto.top", "Topo", "g g", CommandScope::Global).unwrap();
+
+        // Mesmo atalho ou prefixo/extensão dele conflita
+        for binding in ["g g", "g", "g g x"] {
+            let err = registry.register("other", "", binding, CommandScope::Global).unwrap_err();
+            assert!(
+                matches!(&err, CommandError::Conflict { existing, .. } if existing == "goto.top"),
+                "{:?}: {:?}",
+                binding,
+                err
+            );
+        }
+
+        // Outro escopo ou atalho independente não conflita
+        registry.register("other", "", "g g", CommandScope::Region(7)).unwrap();
+        registry.register("save", "Salvar", "Ctrl+S", CommandScope::Global).unwrap();
+        assert_eq!(registry.commands().len(), 3);
+    }
+
+    #[test]
+    fn detects_prefix_conflicts_between_global_and_regions() {
+        let mut registry = CommandRegistry::new();
+        registry.register("goto", "Ir para", "g", CommandScope::Global).unwrap();
+
+        // Um `g` global dispararia antes do segundo `g` da região
+        let err = registry
+            .register("goto.top", "Topo", "g g", CommandScope::Region(7))
+            .unwrap_err();
+        assert!(matches!(&err, CommandError::Conflict { existing, .. } if existing == "goto"));
+
+        // E na direção contrária: um `g` da região engoliria o `g g` global
+        let mut registry = CommandRegistry::new();
+        registry.register("goto.top", "Topo", "g g", CommandScope::Global).unwrap();
+        assert!(registry.register("goto", "Ir para", "g", CommandScope::Region(3)).is_err());
+
+        // O mesmo atalho numa região sobrescreve o global e não é conflito
+        registry.register("region.top", "Topo", "g g", CommandScope::Region(3)).unwrap();
+    }
+
+    #[test]
+    fn re_registering_is_idempotent() {
+        let mut registry = CommandRegistry::new();
+        for _ in 0..3 {
+            registry.register("save", "Salvar", "Ctrl+S", CommandScope::Global).unwrap();
+        }
+        // O próprio comando não conflita consigo; um segundo atalho é acrescentado
+        registry.register("save", "Salvar", "Ctrl+K Ctrl+S", CommandScope::Global).unwrap();
+        assert_eq!(registry.commands().len(), 1);
+        assert_eq!(registry.bindings_for("save"), vec!["Ctrl+S", "Ctrl+K Ctrl+S"]);
+
+        registry.unregister("save");
+        assert!(registry.get("save").is_none());
+    }
+}
@@ -3,6 +3,7 @@
 
 use crate::renderer::Color;
 use std::path::PathBuf;
+use std::time::Duration;
 
 // ============================================================================
 // 1. FONT PATH MULTI-PLATAFORMA
@@ -93,6 +94,7 @@ impl Theme {
                 text_muted: Color { r: 71, g: 85, b: 105, a: 255 },
                 border: Color { r: 51, g: 65, b: 85, a: 255 },
                 shadow: Color { r: 0, g: 0, b: 0, a: 80 },
+                focus_ring: Color { r: 129, g: 140, b: 248, a: 255 },
             },
             spacing: ThemeSpacing::default(),
             radius: ThemeRadius::default(),
@@ -115,6 +117,7 @@ impl Theme {
                 text_muted: Color { r: 148, g: 163, b: 184, a: 255 },
                 border: Color { r: 226, g: 232, b: 240, a: 255 },
                 shadow: Color { r: 0, g: 0, b: 0, a: 25 },
+                focus_ring: Color { r: 37, g: 99, b: 235, a: 255 },
             },
             spacing: ThemeSpacing::default(),
             radius: ThemeRadius::default(),
@@ -140,6 +143,7 @@ pub struct ThemeColors {
     pub text_muted: Color,
     pub border: Color,
     pub shadow: Color,
+    pub focus_ring: Color,
 }
 
 #[derive(Clone, Debug)]
@@ -215,25 +219,42 @@ pub mod components {
     pub const BUTTON_HEIGHT_SMALL: f32 = 32.0;
     pub const BUTTON_PADDING_X: f32 = 16.0;
     pub const BUTTON_BORDER_RADIUS: f32 = 8.0;
+    pub const BUTTON_ICON_SIZE: f32 = 18.0;
+    /// Espaço entre o ícone inicial e o texto (button, sidebar_item)
+    pub const ICON_GAP: f32 = 8.0;
+    pub const INPUT_BORDER_WIDTH: f32 = 1.0;
+    pub const INPUT_BORDER_WIDTH_FOCUSED: f32 = 2.0;
 
     pub const CARD_PADDING: f32 = 16.0;
     pub const CARD_BORDER_RADIUS: f32 = 12.0;
-    pub const CARD_SHADOW_BLUR: f32 = 4.0;
-    pub const CARD_SHADOW_OFFSET_Y: f32 = 2.0;
+    pub const CARD_SHADOW_BLUR: f32 = 12.0;
+    pub const CARD_SHADOW_OFFSET_Y: f32 = 4.0;
 
     pub const SIDEBAR_WIDTH: f32 = 260.0;
     pub const SIDEBAR_PADDING: f32 = 25.0;
     pub const SIDEBAR_ITEM_HEIGHT: f32 = 45.0;
     pub const SIDEBAR_ITEM_BORDER_RADIUS: f32 = 8.0;
+    pub const SIDEBAR_ICON_SIZE: f32 = 18.0;
 
     pub const STAT_CARD_WIDTH: f32 = 260.0;
     pub const STAT_CARD_HEIGHT: f32 = 120.0;
+    pub const STAT_CARD_ICON_SIZE: f32 = 32.0;
 
     pub const DIVIDER_HEIGHT: f32 = 1.0;
     pub const DIVIDER_MARGIN_Y: f32 = 15.0;
 
     pub const SCROLLBAR_WIDTH: f32 = 8.0;
     pub const SCROLLBAR_MIN_HEIGHT: f32 = 32.0;
+
+    pub const FOCUS_RING_WIDTH: f32 = 2.0;
+    pub const FOCUS_RING_OFFSET: f32 = 2.0;
+
+    pub const DRAG_GHOST_ALPHA: u8 = 200;
+
+    /// Folga em volta do layout de uma camada que ainda é composta (sombras, anéis de foco)
+    pub const LAYER_PAINT_MARGIN: f32 = 32.0;
+
+    pub const TRANSITION_SLIDE_DISTANCE: f32 = 24.0;
 }
 
 pub mod text_alpha {
@@ -247,6 +268,38 @@ pub mod render {
     pub const TEXT_ALPHA_THRESHOLD: f32 = 0.01;
     pub const SDF_QUALITY: f32 = 1.0;
     pub const ANIMATION_DURATION_MS: f64 = 150.0;
+    pub const PRESS_ANIMATION_DURATION_MS: f64 = 80.0;
+    pub const TRANSITION_DURATION_MS: f64 = 200.0;
+    /// Meio período do caret piscando (visível/oculto)
+    pub const CARET_BLINK_MS: u64 = 530;
+    /// Mistura em luz linear por padrão (ver `renderer::set_blend_space`)
+    pub const LINEAR_BLENDING: bool = false;
+    /// Abaixo desta área (px) a rasterização não é dividida entre threads
+    pub const PARALLEL_MIN_PIXELS: usize = 512 * 512;
+    pub const MAX_RASTER_THREADS: usize = 16;
+}
+
+pub mod input {
+    pub const MULTI_CLICK_INTERVAL_MS: u64 = 400;
+    pub const MULTI_CLICK_DISTANCE: f32 = 6.0;
+    /// Distância mínima (px) para uma pressão virar arrasto
+    pub const DRAG_THRESHOLD: f32 = 4.0;
+}
+
+/// Janela de tempo e distância máxima para contar cliques duplos/triplos
+#[derive(Clone, Copy, Debug)]
+pub struct ClickConfig {
+    pub multi_click_interval: Duration,
+    pub multi_click_distance: f32,
+}
+
+impl Default for ClickConfig {
+    fn default() -> Self {
+        Self {
+            multi_click_interval: Duration::from_millis(input::MULTI_CLICK_INTERVAL_MS),
+            multi_click_distance: input::MULTI_CLICK_DISTANCE,
+        }
+    }
 }
 
 // ============================================================================
@@ -1,31 +1,95 @@
-use crate::config::Theme;
-use crate::renderer::FontAtlas;
+use crate::commands::{CommandRegistry, CommandScope};
+use crate::config::{ClickConfig, Theme};
+use crate::layout::Rect;
+use crate::animation::AnimationStore;
+use crate::drag::DragState;
+use crate::layer::LayerStore;
+use crate::recording::InputRecording;
+use crate::renderer::{paint_commands, DrawCommand, FontAtlas};
+use crate::image::ImageCache;
+use crate::shadow::ShadowCache;
+use crate::svg::SvgAtlas;
 use ab_glyph::FontArc;
 use pixels::{Pixels, SurfaceTexture};
 use std::any::Any;
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::rc::Rc;
 use std::time::Instant;
 use winit::{
-    event::{ElementState, Event, MouseButton, VirtualKeyCode, WindowEvent},
+    dpi::PhysicalPosition,
+    event::{ElementState, Event, Ime, MouseButton, StartCause, VirtualKeyCode, WindowEvent},
     event_loop::{ControlFlow, EventLoop},
-    window::WindowBuilder,
+    window::{CursorIcon, WindowBuilder},
 };
 
 // ============================================================================
 // INPUT STATE EXPANDIDO
 // ============================================================================
 
-#[derive(Clone, Copy)]
+/// Texto em composição pelo IME (ex: kana antes de virar kanji, acento morto)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ImePreedit {
+    pub text: String,
+    /// Range do cursor em bytes dentro de `text` (None = cursor oculto)
+    pub cursor: Option<(usize, usize)>,
+}
+
+/// Botões do mouse rastreados pelo runtime
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PointerButton {
+    Primary,
+    Secondary,
+    Middle,
+}
+
+impl PointerButton {
+    pub const ALL: [PointerButton; 3] = [
+        PointerButton::Primary,
+        PointerButton::Secondary,
+        PointerButton::Middle,
+    ];
+
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn from_winit(button: MouseButton) -> Option<Self> {
+        match button {
+            MouseButton::Left => Some(PointerButton::Primary),
+            MouseButton::Right => Some(PointerButton::Secondary),
+            MouseButton::Middle => Some(PointerButton::Middle),
+            MouseButton::Other(_) => None,
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct InputState {
     pub mouse_pos: (f32, f32),
+    /// Botão primário pressionado (atalho para `mouse_down[Primary]`)
     pub mouse_clicked: bool,
+    /// Botão primário foi pressionado neste frame (atalho para `mouse_pressed[Primary]`)
     pub mouse_just_clicked: bool,
+    /// Estado atual de cada botão, indexado por `PointerButton::index`
+    pub mouse_down: [bool; 3],
+    /// Borda de descida neste frame
+    pub mouse_pressed: [bool; 3],
+    /// Borda de subida neste frame
+    pub mouse_released: [bool; 3],
+    /// Cliques consecutivos da última pressão (1 = simples, 2 = duplo, 3 = triplo...)
+    pub click_count: [u32; 3],
+    /// Posição do mouse na última pressão de cada botão
+    pub press_pos: [(f32, f32); 3],
     pub keys_pressed: [bool; 256],
     pub keys_just_pressed: [bool; 256],
     pub char_input: Option<char>,
     pub scroll_delta: f32,
+    /// Composição IME em andamento (persiste entre frames até ser limpa)
+    pub ime_preedit: Option<ImePreedit>,
+    /// Texto confirmado pelo IME neste frame
+    pub ime_commit: Option<String>,
 }
 
 impl Default for InputState {
@@ -34,10 +98,17 @@ impl Default for InputState {
             mouse_pos: (0.0, 0.0),
             mouse_clicked: false,
             mouse_just_clicked: false,
+            mouse_down: [false; 3],
+            mouse_pressed: [false; 3],
+            mouse_released: [false; 3],
+            click_count: [0; 3],
+            press_pos: [(0.0, 0.0); 3],
             keys_pressed: [false; 256],
             keys_just_pressed: [false; 256],
             char_input: None,
             scroll_delta: 0.0,
+            ime_preedit: None,
+            ime_commit: None,
         }
     }
 }
@@ -62,6 +133,88 @@ impl InputState {
     pub fn alt(&self) -> bool {
         self.key_down(VirtualKeyCode::LAlt) || self.key_down(VirtualKeyCode::RAlt)
     }
+
+    pub fn button_down(&self, button: PointerButton) -> bool {
+        self.mouse_down[button.index()]
+    }
+
+    pub fn button_pressed(&self, button: PointerButton) -> bool {
+        self.mouse_pressed[button.index()]
+    }
+
+    pub fn button_released(&self, button: PointerButton) -> bool {
+        self.mouse_released[button.index()]
+    }
+
+    pub fn click_count(&self, button: PointerButton) -> u32 {
+        self.click_count[button.index()]
+    }
+
+    /// Aplica uma borda de pressão/soltura de botão (usado pelo runtime)
+    pub fn set_button(&mut self, button: PointerButton, down: bool, click_count: u32) {
+        let i = button.index();
+        if down {
+            if !self.mouse_down[i] {
+                self.mouse_pressed[i] = true;
+                self.click_count[i] = click_count;
+                self.press_pos[i] = self.mouse_pos;
+            }
+        } else if self.mouse_down[i] {
+            self.mouse_released[i] = true;
+        }
+        self.mouse_down[i] = down;
+
+        if button == PointerButton::Primary {
+            self.mouse_clicked = down;
+            self.mouse_just_clicked = self.mouse_pressed[i];
+        }
+    }
+
+    /// Limpa os estados de borda ("just") após o frame
+    pub fn end_frame(&mut self) {
+        self.mouse_just_clicked = false;
+        self.mouse_pressed = [false; 3];
+        self.mouse_released = [false; 3];
+        self.keys_just_pressed.fill(false);
+        self.char_input = None;
+        self.ime_commit = None;
+        self.scroll_delta = 0.0;
+    }
+
+    /// True se há texto sendo composto pelo IME
+    pub fn is_composing(&self) -> bool {
+        self.ime_preedit.as_ref().is_some_and(|p| !p.text.is_empty())
+    }
+}
+
+// ============================================================================
+// DETECÇÃO DE CLIQUE DUPLO/TRIPLO
+// ============================================================================
+
+/// Conta cliques consecutivos por botão respeitando `ClickConfig`
+#[derive(Default)]
+pub struct ClickTracker {
+    last_press: [Option<(Instant, (f32, f32))>; 3],
+    count: [u32; 3],
+}
+
+impl ClickTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registra uma pressão e retorna a contagem de cliques consecutivos
+    pub fn press(&mut self, button: PointerButton, pos: (f32, f32), now: Instant, config: &ClickConfig) -> u32 {
+        let i = button.index();
+        let continues = self.last_press[i].is_some_and(|(at, last_pos)| {
+            let (dx, dy) = (pos.0 - last_pos.0, pos.1 - last_pos.1);
+            now.duration_since(at) <= config.multi_click_interval
+                && (dx * dx + dy * dy).sqrt() <= config.multi_click_distance
+        });
+        self.count[i] = if continues { self.count[i] + 1 } else { 1 };
+        self.last_press[i] = Some((now, pos));
+        self.count[i]
+    }
 }
 
 // ============================================================================
@@ -108,6 +261,143 @@ impl WidgetIdStack {
     }
 }
 
+// ============================================================================
+// FOCUS MANAGER (navegação por teclado)
+// ============================================================================
+
+/// Widget registrado na ordem de Tab do frame
+#[derive(Clone)]
+struct FocusEntry {
+    id: u64,
+    /// Regiões de foco que contêm o widget (da mais externa para a mais interna)
+    regions: Vec<u64>,
+    /// Widget consome texto (teclas simples não devem disparar atalhos)
+    text_entry: bool,
+}
+
+/// Controla o único widget focado e a ordem de Tab construída a cada frame
+#[derive(Default)]
+pub struct FocusManager {
+    focused: Option<u64>,
+    /// Foco veio do teclado (Tab): só nesse caso o anel de foco é desenhado
+    focus_visible: bool,
+    /// Ordem de Tab sendo construída no frame atual
+    tab_order: Vec<FocusEntry>,
+    /// Ordem de Tab do frame anterior, usada para navegar
+    last_tab_order: Vec<FocusEntry>,
+    /// Regiões de foco abertas durante o frame
+    region_stack: Vec<u64>,
+}
+
+impl FocusManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn focused(&self) -> Option<u64> {
+        self.focused
+    }
+
+    pub fn is_focused(&self, id: u64) -> bool {
+        self.focused == Some(id)
+    }
+
+    pub fn focus_visible(&self) -> bool {
+        self.focus_visible
+    }
+
+    /// Foca um widget via mouse/código (sem anel de foco)
+    pub fn request_focus(&mut self, id: u64) {
+        self.focused = Some(id);
+        self.focus_visible = false;
+    }
+
+    pub fn clear_focus(&mut self) {
+        self.focused = None;
+        self.focus_visible = false;
+    }
+
+    /// Registra um widget interativo na ordem de Tab do frame
+    pub fn register(&mut self, id: u64) {
+        self.push_entry(id, false);
+    }
+
+    /// Registra um widget que recebe texto (ex: `text_input`)
+    pub fn register_text_entry(&mut self, id: u64) {
+        self.push_entry(id, true);
+    }
+
+    fn push_entry(&mut self, id: u64, text_entry: bool) {
+        self.tab_order.push(FocusEntry {
+            id,
+            regions: self.region_stack.clone(),
+            text_entry,
+        });
+    }
+
+    pub fn push_region(&mut self, id: u64) {
+        self.region_stack.push(id);
+    }
+
+    pub fn pop_region(&mut self) {
+        self.region_stack.pop();
+    }
+
+    fn focused_entry(&self) -> Option<&FocusEntry> {
+        let id = self.focused?;
+        self.last_tab_order.iter().find(|e| e.id == id)
+    }
+
+    /// Cadeia de regiões do widget focado (da mais externa para a mais interna)
+    pub fn active_regions(&self) -> &[u64] {
+        self.focused_entry().map(|e| e.regions.as_slice()).unwrap_or(&[])
+    }
+
+    /// O widget focado consome texto
+    pub fn text_entry_focused(&self) -> bool {
+        self.focused_entry().is_some_and(|e| e.text_entry)
+    }
+
+    /// Move o foco para o próximo (ou anterior) widget da ordem de Tab
+    pub fn move_focus(&mut self, backwards: bool) {
+        let order = &self.last_tab_order;
+        if order.is_empty() {
+            return;
+        }
+        let current = self
+            .focused
+            .and_then(|id| order.iter().position(|other| other.id == id));
+        let next = match (current, backwards) {
+            (Some(i), false) => (i + 1) % order.len(),
+            (Some(i), true) => (i + order.len() - 1) % order.len(),
+            (None, false) => 0,
+            (None, true) => order.len() - 1,
+        };
+        self.focused = Some(order[next].id);
+        self.focus_visible = true;
+    }
+
+    /// Início do frame: fecha a ordem de Tab anterior e processa Tab/Shift+Tab e cliques
+    pub fn begin_frame(&mut self, input: &InputState) {
+        self.last_tab_order = std::mem::take(&mut self.tab_order);
+        self.region_stack.clear();
+
+        // Widget focado sumiu da tela
+        if self.focused.is_some() && self.focused_entry().is_none() {
+            self.clear_focus();
+        }
+
+        // Clique limpa o foco; o widget clicado o reivindica durante o frame
+        if input.mouse_just_clicked {
+            self.clear_focus();
+        }
+
+        if input.key_just_pressed(VirtualKeyCode::Tab) {
+            self.move_focus(input.shift());
+        }
+    }
+}
+
 // ============================================================================
 // STATE STORE COM SUPORTE A IDS ESTÁVEIS
 // ============================================================================
@@ -117,16 +407,69 @@ pub struct StateStore {
     pub widget_stack: WidgetIdStack,
     pub theme: Theme,
     pub current_index: u64,
+    /// Retângulo do caret do input focado, usado para posicionar a janela de candidatos do IME
+    pub ime_caret: Option<Rect>,
+    pub focus: FocusManager,
+    pub commands: CommandRegistry,
+    /// Tempo/distância para clique duplo e triplo
+    pub click_config: ClickConfig,
+    /// Widget que recebeu a pressão de cada botão; o clique só vale se soltar sobre ele
+    pub pointer_capture: [Option<u64>; 3],
+    pub drag: DragState,
+    /// Comandos desenhados pelo runtime por cima de tudo ao final do frame
+    pub overlay: Vec<DrawCommand>,
+    /// Cursor pedido pelos widgets em hover neste frame (o último pedido vence)
+    pub cursor_icon: Option<CursorIcon>,
+    /// Cursor de um arrasto ou widget pressionado; vence os pedidos de hover
+    pub captured_cursor: Option<CursorIcon>,
+    /// Relógio do frame: capturado uma vez pelo runtime e usado por todas as animações
+    pub frame_time: Instant,
+    /// Próximo frame pedido pelos widgets (animações, caret piscando); `None` = idle
+    pub repaint_at: Option<Instant>,
+    pub animations: AnimationStore,
+    /// Buffers e cache das camadas offscreen
+    pub layers: LayerStore,
+    /// Máscaras de sombra já borradas, por tamanho e parâmetros
+    pub shadows: ShadowCache,
+    /// Imagens decodificadas, por caminho ou bytes
+    pub images: ImageCache,
+    /// Documentos SVG e seus bitmaps por tamanho
+    pub svgs: SvgAtlas,
 }
 
 impl StateStore {
     pub fn new() -> Self {
-        Self {
+        let mut store = Self {
             states: HashMap::new(),
             widget_stack: WidgetIdStack::new(),
             theme: Theme::default(),
             current_index: 0,
-        }
+            ime_caret: None,
+            focus: FocusManager::new(),
+            commands: CommandRegistry::new(),
+            click_config: ClickConfig::default(),
+            pointer_capture: [None; 3],
+            drag: DragState::new(),
+            overlay: Vec::new(),
+            cursor_icon: None,
+            captured_cursor: None,
+            frame_time: Instant::now(),
+            repaint_at: None,
+            animations: AnimationStore::new(),
+            layers: LayerStore::new(),
+            shadows: ShadowCache::new(),
+            images: ImageCache::new(),
+            svgs: SvgAtlas::new(),
+        };
+
+        // Comandos embutidos do runtime
+        store.commands.register_or_warn(
+            "debug.toggle_overlay",
+            "Alterna o overlay de debug",
+            "F3",
+            CommandScope::Global,
+        );
+        store
     }
 
     pub fn get_widget_id(&mut self, local_id: u64) -> u64 {
@@ -144,6 +487,40 @@ impl StateStore {
     pub fn reset_frame(&mut self) {
         self.widget_stack.reset();
         self.current_index = 0;
+        self.ime_caret = None;
+        self.overlay.clear();
+        self.cursor_icon = None;
+        self.captured_cursor = None;
+        self.repaint_at = None;
+    }
+
+    /// Pede um frame até `at` (o pedido mais cedo vence)
+    pub fn request_repaint_at(&mut self, at: Instant) {
+        self.repaint_at = Some(self.repaint_at.map_or(at, |t| t.min(at)));
+    }
+
+    /// Prepara o store para um novo frame: IDs, foco, atalhos e captura do ponteiro
+    pub fn begin_frame(&mut self, input: &InputState, now: Instant) {
+        self.reset_frame();
+        self.frame_time = now;
+        self.focus.begin_frame(input);
+        self.commands.process(
+            input,
+            self.focus.active_regions(),
+            self.focus.text_entry_focused(),
+            now,
+        );
+
+        self.drag.begin_frame(input);
+        self.layers.begin_frame();
+
+        // A captura dura enquanto o botão está pressionado e no frame em que é solto
+        for button in PointerButton::ALL {
+            let i = button.index();
+            if !input.mouse_down[i] && !input.mouse_released[i] && !input.mouse_pressed[i] {
+                self.pointer_capture[i] = None;
+            }
+        }
     }
 }
 
@@ -178,11 +555,122 @@ pub struct DebugInfo {
     pub show_overlay: bool,
 }
 
+// ============================================================================
+// FRAME RUNNER (compartilhado entre janela e headless)
+// ============================================================================
+
+/// Tudo que persiste entre frames fora do app: fonte, atlas, store e rastreio de cliques
+pub struct FrameRunner {
+    pub width: u32,
+    pub height: u32,
+    pub font: FontArc,
+    pub atlas: FontAtlas,
+    pub state: Rc<RefCell<StateStore>>,
+    pub click_tracker: ClickTracker,
+}
+
+impl FrameRunner {
+    pub fn new(width: u32, height: u32, font: FontArc) -> Self {
+        Self {
+            width,
+            height,
+            font,
+            atlas: FontAtlas::new(),
+            state: Rc::new(RefCell::new(StateStore::new())),
+            click_tracker: ClickTracker::new(),
+        }
+    }
+
+    /// Aplica pressão/soltura de um botão ao input, contando cliques consecutivos
+    pub fn mouse_button(&mut self, input: &mut InputState, button: PointerButton, down: bool, now: Instant) {
+        let click_count = if down && !input.button_down(button) {
+            let config = self.state.borrow().click_config;
+            self.click_tracker.press(button, input.mouse_pos, now, &config)
+        } else {
+            input.click_count(button)
+        };
+        input.set_button(button, down, click_count);
+    }
+
+    /// Executa um frame completo: prepara o store, `update`, `draw` e overlay
+    pub fn frame(&mut self, app: &mut impl App, frame: &mut [u8], input: &InputState, now: Instant) {
+        self.state.borrow_mut().begin_frame(input, now);
+
+        app.update(input);
+        app.draw(
+            frame,
+            self.width,
+            self.height,
+            &self.font,
+            &mut self.atlas,
+            self.state.clone(),
+            input,
+        );
+
+        // Overlay (preview de arrasto etc.) por cima do conteúdo do app
+        let overlay = std::mem::take(&mut self.state.borrow_mut().overlay);
+        paint_commands(frame, &mut self.atlas, &self.font, &overlay, self.width, self.height);
+    }
+
+    /// Cursor pedido pelos widgets no último frame
+    pub fn cursor_icon(&self) -> CursorIcon {
+        let store = self.state.borrow();
+        store.captured_cursor.or(store.cursor_icon).unwrap_or_default()
+    }
+
+    /// Instante do próximo frame pedido pelos widgets no último frame
+    pub fn next_repaint(&self) -> Option<Instant> {
+        self.state.borrow().repaint_at
+    }
+}
+
 // ============================================================================
 // MAIN LOOP
 // ============================================================================
 
-pub fn run(mut app: impl App + 'static, width: u32, height: u32, font: FontArc) {
+/// Opções do runtime de janela
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// Grava o input da sessão neste arquivo ao fechar a janela
+    pub record_to: Option<PathBuf>,
+    /// Reproduz uma gravação no lugar do input real
+    pub replay_from: Option<PathBuf>,
+}
+
+impl RunOptions {
+    /// Lê `RUSTUI_RECORD` e `RUSTUI_REPLAY` do ambiente (útil para bug reports)
+    pub fn from_env() -> Self {
+        Self {
+            record_to: std::env::var_os("RUSTUI_RECORD").map(PathBuf::from),
+            replay_from: std::env::var_os("RUSTUI_REPLAY").map(PathBuf::from),
+        }
+    }
+}
+
+/// Eventos de input real, ignorados durante um replay
+fn is_input_event(event: &WindowEvent) -> bool {
+    matches!(
+        event,
+        WindowEvent::CursorMoved { .. }
+            | WindowEvent::MouseInput { .. }
+            | WindowEvent::KeyboardInput { .. }
+            | WindowEvent::ReceivedCharacter(_)
+            | WindowEvent::Ime(_)
+            | WindowEvent::MouseWheel { .. }
+    )
+}
+
+pub fn run(app: impl App + 'static, width: u32, height: u32, font: FontArc) {
+    run_with_options(app, width, height, font, RunOptions::from_env());
+}
+
+pub fn run_with_options(
+    mut app: impl App + 'static,
+    width: u32,
+    height: u32,
+    font: FontArc,
+    options: RunOptions,
+) {
     let event_loop = EventLoop::new();
     let window = WindowBuilder::new()
         .with_title("RustUI - Reactive Pro ⚡")
@@ -197,25 +685,88 @@ pub fn run(mut app: impl App + 'static, width: u32, height: u32, font: FontArc)
     };
 
     let mut input = InputState::default();
-    let mut atlas = FontAtlas::new();
-    let state_store = Rc::new(RefCell::new(StateStore::new()));
+    let mut runner = FrameRunner::new(width, height, font);
 
     // Debug timing
     let mut _last_frame = Instant::now();
     let mut frame_count = 0;
     let mut fps_timer = Instant::now();
     let mut debug_info = DebugInfo::default();
+    let mut ime_allowed = false;
+    let mut current_cursor = CursorIcon::Default;
+
+    // Gravação/replay: tempos relativos ao início da sessão
+    let session_start = Instant::now();
+    let mut recorder = options
+        .record_to
+        .as_ref()
+        .map(|_| InputRecording::new(width, height));
+    let mut replay = options.replay_from.as_ref().and_then(|path| {
+        match InputRecording::load(path) {
+            Ok(recording) => {
+                log::info!("Reproduzindo {} frames de {:?}", recording.len(), path);
+                Some((recording, 0usize))
+            }
+            Err(err) => {
+                log::error!("Falha ao carregar gravação {:?}: {}", path, err);
+                None
+            }
+        }
+    });
 
-    event_loop.run(move |event, _, control_flow| {
-        *control_flow = ControlFlow::Wait;
-
+    // O replay roda no tamanho em que foi gravado (posições do mouse dependem dele)
+    if let Some((recording, _)) = &replay
+        && (recording.width, recording.height) != (runner.width, runner.height)
+        && recording.width > 0
+        && recording.height > 0
+    {
+        window.set_inner_size(winit::dpi::LogicalSize::new(recording.width, recording.height));
+        match pixels.resize_buffer(recording.width, recording.height) {
+            Ok(()) => {
+                runner.width = recording.width;
+                runner.height = recording.height;
+            }
+            Err(err) => log::error!("Falha ao aplicar o tamanho gravado: {:?}", err),
+        }
+    }
 
+    event_loop.run(move |event, _, control_flow| {
+        // Idle por padrão; acorda no próximo frame pedido pelos widgets ou,
+        // no replay, no instante gravado do próximo frame
+        let deadline = match &replay {
+            Some((recording, next)) => recording.frames.get(*next).map(|f| session_start + f.time),
+            None => runner.next_repaint(),
+        };
+        let repaint_due = deadline.is_some_and(|t| t <= Instant::now());
+        *control_flow = match deadline {
+            Some(_) if repaint_due => ControlFlow::Poll,
+            Some(t) => ControlFlow::WaitUntil(t),
+            None => ControlFlow::Wait,
+        };
+
+        if replay.is_some()
+            && let Event::WindowEvent { event: window_event, .. } = &event
+            && is_input_event(window_event)
+        {
+            return;
+        }
 
         match event {
+            Event::NewEvents(StartCause::ResumeTimeReached { .. }) => window.request_redraw(),
+            Event::MainEventsCleared if repaint_due => window.request_redraw(),
+
             Event::WindowEvent {
                 event: WindowEvent::CloseRequested,
                 ..
-            } => *control_flow = ControlFlow::Exit,
+            } => {
+                if let (Some(recording), Some(path)) = (&recorder, &options.record_to) {
+                    match recording.save(path) {
+                        Ok(()) => log::info!("Gravação salva em {:?} ({} frames)", path, recording.len()),
+                        Err(err) => log::error!("Falha ao salvar gravação {:?}: {}", path, err),
+                    }
+                }
+                *control_flow = ControlFlow::Exit;
+            }
 
             Event::WindowEvent {
                 event: WindowEvent::Resized(size),
@@ -237,16 +788,14 @@ pub fn run(mut app: impl App + 'static, width: u32, height: u32, font: FontArc)
                 event:
                     WindowEvent::MouseInput {
                         state,
-                        button: MouseButton::Left,
+                        button,
                         ..
                     },
                 ..
             } => {
-                if state == ElementState::Pressed {
-                    input.mouse_clicked = true;
-                    input.mouse_just_clicked = true;
-                } else {
-                    input.mouse_clicked = false;
+                if let Some(button) = PointerButton::from_winit(button) {
+                    let down = state == ElementState::Pressed;
+                    runner.mouse_button(&mut input, button, down, Instant::now());
                 }
                 window.request_redraw();
             }
@@ -263,11 +812,6 @@ pub fn run(mut app: impl App + 'static, width: u32, height: u32, font: FontArc)
                                 input.keys_just_pressed[idx] = true;
                             }
                             input.keys_pressed[idx] = true;
-
-                            // Toggle debug overlay com F3
-                            if virtual_keycode == VirtualKeyCode::F3 {
-                                debug_info.show_overlay = !debug_info.show_overlay;
-                            }
                         } else {
                             input.keys_pressed[idx] = false;
                         }
@@ -284,6 +828,27 @@ pub fn run(mut app: impl App + 'static, width: u32, height: u32, font: FontArc)
                 window.request_redraw();
             }
 
+            Event::WindowEvent {
+                event: WindowEvent::Ime(ime),
+                ..
+            } => {
+                match ime {
+                    Ime::Preedit(text, cursor) => {
+                        input.ime_preedit = if text.is_empty() {
+                            None
+                        } else {
+                            Some(ImePreedit { text, cursor })
+                        };
+                    }
+                    Ime::Commit(text) => {
+                        input.ime_commit.get_or_insert_with(String::new).push_str(&text);
+                    }
+                    Ime::Enabled => {}
+                    Ime::Disabled => input.ime_preedit = None,
+                }
+                window.request_redraw();
+            }
+
             Event::WindowEvent {
                 event: WindowEvent::MouseWheel { delta, .. },
                 ..
@@ -301,26 +866,62 @@ pub fn run(mut app: impl App + 'static, width: u32, height: u32, font: FontArc)
 
             Event::RedrawRequested(_) => {
                 let frame_start = Instant::now();
+                let mut now = frame_start;
+
+                // Replay: o input e o relógio do frame vêm da gravação. Um frame gravado só
+                // é consumido no seu instante; redraws antes disso (resize, expose) repetem o
+                // instante do último frame reproduzido
+                if let Some((recording, next)) = &mut replay {
+                    match recording.frames.get(*next) {
+                        Some(frame) if session_start + frame.time <= frame_start => {
+                            input = frame.input.clone();
+                            now = session_start + frame.time;
+                            *next += 1;
+                        }
+                        _ => {
+                            now = match next.checked_sub(1) {
+                                Some(last) => session_start + recording.frames[last].time,
+                                None => session_start,
+                            };
+                        }
+                    }
+                    if *next >= recording.frames.len() {
+                        log::info!("Replay concluído, voltando ao input real");
+                        replay = None;
+                    }
+                }
 
-                // Reset widget stack para novos IDs
-                state_store.borrow_mut().reset_frame();
+                if let Some(recording) = &mut recorder {
+                    recording.push(now - session_start, &input);
+                }
 
-                app.update(&input);
-                app.draw(
-                    pixels.frame_mut(),
-                    width,
-                    height,
-                    &font,
-                    &mut atlas,
-                    state_store.clone(),
-                    &input,
-                );
+                runner.frame(&mut app, pixels.frame_mut(), &input, now);
+                if runner.state.borrow().commands.triggered("debug.toggle_overlay") {
+                    debug_info.show_overlay = !debug_info.show_overlay;
+                }
+
+                // Cursor só é trocado na janela quando muda
+                let cursor = runner.cursor_icon();
+                if cursor != current_cursor {
+                    window.set_cursor_icon(cursor);
+                    current_cursor = cursor;
+                }
+
+                // IME: só habilita com um input focado e acompanha o caret para a janela de candidatos
+                let ime_caret = runner.state.borrow().ime_caret;
+                if ime_caret.is_some() != ime_allowed {
+                    ime_allowed = ime_caret.is_some();
+                    window.set_ime_allowed(ime_allowed);
+                    if !ime_allowed {
+                        input.ime_preedit = None;
+                    }
+                }
+                if let Some(caret) = ime_caret {
+                    window.set_ime_position(PhysicalPosition::new(caret.x, caret.y + caret.h));
+                }
 
                 // After drawing, reset the "just" states for the *next* frame's input collection.
-                input.mouse_just_clicked = false;
-                input.keys_just_pressed.fill(false);
-                input.char_input = None;
-                input.scroll_delta = 0.0;
+                input.end_frame();
 
                 if let Err(err) = pixels.render() {
                     log::error!("Erro ao renderizar frame: {:?}", err);
@@ -344,3 +945,45 @@ pub fn run(mut app: impl App + 'static, width: u32, height: u32, font: FontArc)
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn counts_consecutive_clicks_per_button() {
+        let config = ClickConfig::default();
+        let mut tracker = ClickTracker::new();
+        let start = Instant::now();
+        let ms = Duration::from_millis;
+
+        assert_eq!(tracker.press(PointerButton::Primary, (10.0, 10.0), start, &config), 1);
+        assert_eq!(tracker.press(PointerButton::Primary, (12.0, 11.0), start + ms(150), &config), 2);
+        assert_eq!(tracker.press(PointerButton::Primary, (12.0, 11.0), start + ms(300), &config), 3);
+        // Outro botão tem sua própria contagem
+        assert_eq!(tracker.press(PointerButton::Secondary, (12.0, 11.0), start + ms(310), &config), 1);
+
+        // Longe demais ou devagar demais recomeça
+        assert_eq!(tracker.press(PointerButton::Primary, (40.0, 11.0), start + ms(400), &config), 1);
+        let late = start + ms(400) + config.multi_click_interval + ms(1);
+        assert_eq!(tracker.press(PointerButton::Primary, (40.0, 11.0), late, &config), 1);
+    }
+
+    #[test]
+    fn button_edges_last_one_frame() {
+        let mut input = InputState::default();
+        input.set_button(PointerButton::Middle, true, 2);
+        assert!(input.button_pressed(PointerButton::Middle) && input.button_down(PointerButton::Middle));
+        assert_eq!(input.click_count(PointerButton::Middle), 2);
+        // O botão primário não é afetado
+        assert!(!input.mouse_clicked);
+
+        input.end_frame();
+        assert!(!input.button_pressed(PointerButton::Middle));
+        input.set_button(PointerButton::Middle, false, 2);
+        assert!(input.button_released(PointerButton::Middle) && !input.button_down(PointerButton::Middle));
+        input.end_frame();
+        assert!(!input.button_released(PointerButton::Middle));
+    }
+}
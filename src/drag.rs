@@ -0,0 +1,170 @@
+//! Gestos de arrasto e drag-and-drop entre widgets
+//! O estado vive no `StateStore` e sobrevive entre frames; a API fica em `Ui::drag`,
+//! `Ui::drag_source` e `Ui::drop_target`.
+
+use crate::config::input;
+use crate::core::{InputState, PointerButton};
+use std::any::Any;
+use std::rc::Rc;
+
+/// Resultado de um gesto de arrasto para o widget neste frame
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DragResponse {
+    /// O arrasto passou do threshold neste frame
+    pub started: bool,
+    /// Arrasto ativo com o botão ainda pressionado
+    pub dragging: bool,
+    /// Movimento do mouse desde o frame anterior
+    pub delta: (f32, f32),
+    /// Deslocamento total desde a pressão
+    pub offset: (f32, f32),
+    /// O botão foi solto encerrando um arrasto ativo
+    pub released: bool,
+}
+
+/// Resultado de um alvo de drop para um payload do tipo `T`
+pub struct DropResponse<T> {
+    /// Um payload compatível está sobre o alvo
+    pub hovered: bool,
+    /// Payload solto sobre o alvo neste frame
+    pub dropped: Option<T>,
+}
+
+impl<T> Default for DropResponse<T> {
+    fn default() -> Self {
+        Self {
+            hovered: false,
+            dropped: None,
+        }
+    }
+}
+
+/// Estado global do arrasto (no máximo um por vez)
+#[derive(Default)]
+pub struct DragState {
+    /// Widget que recebeu a pressão e pode virar origem do arrasto
+    pub source: Option<u64>,
+    pub start_pos: (f32, f32),
+    pub last_pos: (f32, f32),
+    /// Passou do threshold: é um arrasto de fato (e não um clique)
+    pub active: bool,
+    /// Distância do ponto de pressão até o canto do widget de origem (para o preview)
+    pub grab_offset: (f32, f32),
+    pub payload: Option<Rc<dyn Any>>,
+}
+
+impl DragState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn begin(&mut self, id: u64, pos: (f32, f32), grab_offset: (f32, f32)) {
+        *self = Self {
+            source: Some(id),
+            start_pos: pos,
+            last_pos: pos,
+            grab_offset,
+            ..Self::default()
+        };
+    }
+
+    pub fn is_dragging(&self) -> bool {
+        self.active
+    }
+
+    /// Há um payload do tipo `T` sendo arrastado
+    pub fn has_payload<T: 'static>(&self) -> bool {
+        self.active && self.payload.as_ref().is_some_and(|p| p.is::<T>())
+    }
+
+    pub fn payload<T: 'static + Clone>(&self) -> Option<T> {
+        if !self.active {
+            return None;
+        }
+        self.payload.as_ref()?.downcast_ref::<T>().cloned()
+    }
+
+    /// Atualiza o gesto para o widget de origem e retorna a resposta do frame
+    pub fn update(&mut self, id: u64, input_state: &InputState) -> DragResponse {
+        if self.source != Some(id) {
+            return DragResponse::default();
+        }
+        let pos = input_state.mouse_pos;
+        let mut response = DragResponse::default();
+
+        let (dx, dy) = (pos.0 - self.start_pos.0, pos.1 - self.start_pos.1);
+        if !self.active
+            && input_state.button_down(PointerButton::Primary)
+            && (dx * dx + dy * dy).sqrt() >= input::DRAG_THRESHOLD
+        {
+            self.active = true;
+            response.started = true;
+        }
+
+        if self.active {
+            response.dragging = input_state.button_down(PointerButton::Primary);
+            response.delta = (pos.0 - self.last_pos.0, pos.1 - self.last_pos.1);
+            response.offset = (dx, dy);
+            response.released = input_state.button_released(PointerButton::Primary);
+        }
+        self.last_pos = pos;
+        response
+    }
+
+    /// Início do frame: encerra o arrasto depois do frame em que o botão foi solto
+    pub fn begin_frame(&mut self, input_state: &InputState) {
+        let button = PointerButton::Primary;
+        if self.source.is_some()
+            && !input_state.button_down(button)
+            && !input_state.button_released(button)
+            && !input_state.button_pressed(button)
+        {
+            *self = Self::default();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn input_at(pos: (f32, f32), down: bool) -> InputState {
+        let mut input = InputState {
+            mouse_pos: pos,
+            ..Default::default()
+        };
+        input.set_button(PointerButton::Primary, down, 1);
+        input
+    }
+
+    #[test]
+    fn drags_start_past_the_threshold_and_end_after_release() {
+        let mut drag = DragState::new();
+        drag.begin(1, (0.0, 0.0), (5.0, 5.0));
+        drag.payload = Some(Rc::new(3u8));
+
+        // Outros widgets não veem o gesto
+        assert!(!drag.update(2, &input_at((10.0, 0.0), true)).started);
+
+        let small = input::DRAG_THRESHOLD / 2.0;
+        assert!(!drag.update(1, &input_at((small, 0.0), true)).dragging);
+        assert!(!drag.has_payload::<u8>());
+
+        let response = drag.update(1, &input_at((10.0, 0.0), true));
+        assert!(response.started && response.dragging);
+        assert_eq!((response.delta, response.offset), ((10.0 - small, 0.0), (10.0, 0.0)));
+        assert!(drag.has_payload::<u8>() && !drag.has_payload::<u32>());
+        assert_eq!(drag.payload::<u8>(), Some(3));
+
+        // Soltar vale por um frame; no seguinte o estado é limpo
+        let mut released = input_at((10.0, 0.0), true);
+        released.end_frame();
+        released.set_button(PointerButton::Primary, false, 1);
+        assert!(drag.update(1, &released).released);
+        drag.begin_frame(&released);
+        assert!(drag.is_dragging());
+        released.end_frame();
+        drag.begin_frame(&released);
+        assert!(!drag.is_dragging() && drag.source.is_none());
+    }
+}
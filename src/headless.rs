@@ -0,0 +1,605 @@
+//! Backend headless: roda o app sem janela, num buffer RGBA em memória
+//! Útil para testes e para inspecionar o resultado de cada frame (pixels, cursor, foco...)
+
+use crate::core::{App, FrameRunner, InputState, PointerButton, StateStore};
+use crate::recording::InputRecording;
+use crate::renderer::Color;
+use ab_glyph::FontArc;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use winit::event::VirtualKeyCode;
+use winit::window::CursorIcon;
+
+pub struct Headless {
+    pub runner: FrameRunner,
+    pub frame: Vec<u8>,
+    /// Input do próximo frame; as bordas ("just") são limpas após cada `step`
+    pub input: InputState,
+    /// Relógio virtual: só avança com `advance` (ou no replay), deixando o tempo determinístico
+    pub clock: Instant,
+}
+
+impl Headless {
+    pub fn new(width: u32, height: u32, font: FontArc) -> Self {
+        Self {
+            runner: FrameRunner::new(width, height, font),
+            frame: vec![0; (width * height * 4) as usize],
+            input: InputState::default(),
+            clock: Instant::now(),
+        }
+    }
+
+    /// Roda um frame do app com o input acumulado, no instante do relógio virtual
+    pub fn step(&mut self, app: &mut impl App) {
+        self.runner.frame(app, &mut self.frame, &self.input, self.clock);
+        self.input.end_frame();
+    }
+
+    /// Avança o relógio virtual
+    pub fn advance(&mut self, dt: Duration) {
+        self.clock += dt;
+    }
+
+    /// Avança o relógio e roda um frame
+    pub fn step_by(&mut self, app: &mut impl App, dt: Duration) {
+        self.advance(dt);
+        self.step(app);
+    }
+
+    /// Troca o tamanho do frame (o conteúdo é descartado)
+    pub fn resize(&mut self, width: u32, height: u32) {
+        self.runner.width = width;
+        self.runner.height = height;
+        self.frame = vec![0; (width * height * 4) as usize];
+    }
+
+    /// Reproduz todos os frames de uma gravação, com os tempos e o tamanho gravados
+    pub fn replay(&mut self, app: &mut impl App, recording: &InputRecording) {
+        if (recording.width, recording.height) != (self.runner.width, self.runner.height)
+            && recording.width > 0
+            && recording.height > 0
+        {
+            self.resize(recording.width, recording.height);
+        }
+        let origin = self.clock;
+        for frame in &recording.frames {
+            self.input = frame.input.clone();
+            self.clock = origin + frame.time;
+            self.step(app);
+        }
+    }
+
+    pub fn state(&self) -> Rc<RefCell<StateStore>> {
+        self.runner.state.clone()
+    }
+
+    /// Cursor que a janela mostraria após o último frame
+    pub fn cursor_icon(&self) -> CursorIcon {
+        self.runner.cursor_icon()
+    }
+
+    /// Pixel como guardado no frame (RGBA pré-multiplicado; onde o frame é opaco é o RGBA comum)
+    pub fn pixel(&self, x: u32, y: u32) -> Color {
+        let idx = ((y * self.runner.width + x) * 4) as usize;
+        Color {
+            r: self.frame[idx],
+            g: self.frame[idx + 1],
+            b: self.frame[idx + 2],
+            a: self.frame[idx + 3],
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // SIMULAÇÃO DE INPUT
+    // ------------------------------------------------------------------------
+
+    pub fn move_mouse(&mut self, x: f32, y: f32) {
+        self.input.mouse_pos = (x, y);
+    }
+
+    pub fn press(&mut self, button: PointerButton) {
+        self.runner
+            .mouse_button(&mut self.input, button, true, self.clock);
+    }
+
+    pub fn release(&mut self, button: PointerButton) {
+        self.runner
+            .mouse_button(&mut self.input, button, false, self.clock);
+    }
+
+    /// Pressiona e solta no mesmo frame
+    pub fn click(&mut self, button: PointerButton) {
+        self.press(button);
+        self.release(button);
+    }
+
+    pub fn key_down(&mut self, key: VirtualKeyCode) {
+        let idx = key as usize;
+        if idx < 256 {
+            if !self.input.keys_pressed[idx] {
+                self.input.keys_just_pressed[idx] = true;
+            }
+            self.input.keys_pressed[idx] = true;
+        }
+    }
+
+    pub fn key_up(&mut self, key: VirtualKeyCode) {
+        let idx = key as usize;
+        if idx < 256 {
+            self.input.keys_pressed[idx] = false;
+        }
+    }
+
+    pub fn type_char(&mut self, ch: char) {
+        self.input.char_input = Some(ch);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modifier::Modifier;
+    use crate::renderer::{FontAtlas, clear};
+    use crate::ui_context::Ui;
+    use crate::transform::Transform;
+    use crate::animation::Transition;
+    use crate::config::{components, render, spacing, text_alpha};
+    use crate::icon::{Icon, draw_icon};
+    use crate::layout::Rect;
+    use crate::widgets::{
+        animated_visibility, button, column, sidebar_item_with, text_input, transform, with_opacity,
+    };
+    use std::cell::Cell;
+
+    /// Fonte para os testes: `RUSTUI_FONT`, a padrão do sistema ou DejaVu. Sem fonte o
+    /// teste falha em vez de passar sem testar nada
+    fn test_font() -> FontArc {
+        let data = std::env::var("RUSTUI_FONT")
+            .ok()
+            .and_then(|path| std::fs::read(path).ok())
+            .or_else(|| crate::config::get_font_with_fallback(Some(crate::config::DEFAULT_FONT_NAME)))
+            .or_else(|| std::fs::read("/usr/share/fonts/truetype/dejavu/DejaVuSans.ttf").ok())
+            .expect("nenhuma fonte para os testes headless; aponte RUSTUI_FONT para um .ttf");
+        FontArc::try_from_vec(data).expect("a fonte dos testes headless não é válida")
+    }
+
+    #[derive(Default)]
+    struct Probe {
+        updates: u32,
+        frame_times: RefCell<Vec<Instant>>,
+        clicks: Cell<u32>,
+    }
+
+    impl App for Probe {
+        fn update(&mut self, _input: &InputState) {
+            self.updates += 1;
+        }
+
+        fn draw(
+            &self,
+            frame: &mut [u8],
+            width: u32,
+            height: u32,
+            font: &FontArc,
+            atlas: &mut FontAtlas,
+            state: Rc<RefCell<StateStore>>,
+            input: &InputState,
+        ) {
+            clear(frame, Color::WHITE);
+            let mut ui = Ui::new(frame, width, height, font, atlas, state, input);
+            self.frame_times.borrow_mut().push(ui.now());
+            if button(&mut ui, Modifier::new().size(100.0, 40.0), "Ok").0 {
+                self.clicks.set(self.clicks.get() + 1);
+            }
+        }
+    }
+
+    #[test]
+    fn steps_frames_on_the_virtual_clock() {
+        let mut headless = Headless::new(200, 100, test_font());
+        let mut app = Probe::default();
+        let start = headless.clock;
+
+        headless.step(&mut app);
+        headless.step_by(&mut app, Duration::from_millis(16));
+        headless.advance(Duration::from_millis(100));
+        headless.step(&mut app);
+
+        assert_eq!(app.updates, 3);
+        let times = app.frame_times.borrow();
+        assert_eq!(
+            times.as_slice(),
+            &[start, start + Duration::from_millis(16), start + Duration::from_millis(116)]
+        );
+        // Fora do botão o frame é o fundo limpo
+        assert_eq!(headless.pixel(150, 80), Color::WHITE);
+    }
+
+    #[test]
+    fn clicks_reach_widgets_across_frames() {
+        let mut headless = Headless::new(200, 100, test_font());
+        let mut app = Probe::default();
+
+        headless.move_mouse(50.0, 20.0);
+        headless.step(&mut app);
+        assert_eq!(headless.cursor_icon(), CursorIcon::Hand);
+
+        // O clique só vale ao soltar sobre o mesmo botão
+        headless.press(PointerButton::Primary);
+        headless.step(&mut app);
+        assert_eq!(app.clicks.get(), 0);
+        headless.release(PointerButton::Primary);
+        headless.step(&mut app);
+        assert_eq!(app.clicks.get(), 1);
+
+        // Pressionar no botão e soltar fora cancela
+        headless.press(PointerButton::Primary);
+        headless.step(&mut app);
+        headless.move_mouse(180.0, 90.0);
+        headless.release(PointerButton::Primary);
+        headless.step(&mut app);
+        assert_eq!(app.clicks.get(), 1);
+        assert_eq!(headless.cursor_icon(), CursorIcon::Default);
+    }
+
+    /// Botão sozinho numa camada (não avança o cursor: o layout da camada fica vazio)
+    struct Faded;
+
+    impl App for Faded {
+        fn update(&mut self, _input: &InputState) {}
+
+        fn draw(
+            &self,
+            frame: &mut [u8],
+            width: u32,
+            height: u32,
+            font: &FontArc,
+            atlas: &mut FontAtlas,
+            state: Rc<RefCell<StateStore>>,
+            input: &InputState,
+        ) {
+            clear(frame, Color::WHITE);
+            let mut ui = Ui::new(frame, width, height, font, atlas, state, input);
+            with_opacity(&mut ui, 1.0, |ui| {
+                button(ui, Modifier::new().size(150.0, 80.0), "Ok");
+            });
+        }
+    }
+
+    #[test]
+    fn layers_composite_the_area_widgets_drew() {
+        let mut headless = Headless::new(300, 200, test_font());
+        headless.step(&mut Faded);
+        // Longe da folga em volta do cursor, mas dentro do botão
+        assert_ne!(headless.pixel(140, 70), Color::WHITE);
+        assert_eq!(headless.pixel(250, 150), Color::WHITE);
+    }
+
+    /// Botão sob uma transformação escolhida pelo teste
+    struct Transformed {
+        t: Transform,
+        clicks: Cell<u32>,
+    }
+
+    impl App for Transformed {
+        fn update(&mut self, _input: &InputState) {}
+
+        fn draw(
+            &self,
+            frame: &mut [u8],
+            width: u32,
+            height: u32,
+            font: &FontArc,
+            atlas: &mut FontAtlas,
+            state: Rc<RefCell<StateStore>>,
+            input: &InputState,
+        ) {
+            clear(frame, Color::WHITE);
+            let mut ui = Ui::new(frame, width, height, font, atlas, state, input);
+            transform(&mut ui, self.t, |ui| {
+                if button(ui, Modifier::new().size(100.0, 40.0), "Ok").0 {
+                    self.clicks.set(self.clicks.get() + 1);
+                }
+            });
+        }
+    }
+
+    #[test]
+    fn transforms_move_paint_and_hit_testing() {
+        let mut headless = Headless::new(300, 200, test_font());
+        let mut app = Transformed {
+            t: Transform::translate(150.0, 100.0),
+            clicks: Cell::new(0),
+        };
+
+        headless.step(&mut app);
+        assert_eq!(headless.pixel(50, 20), Color::WHITE);
+        assert_ne!(headless.pixel(200, 120), Color::WHITE);
+
+        headless.move_mouse(200.0, 120.0);
+        headless.click(PointerButton::Primary);
+        headless.step(&mut app);
+        assert_eq!(app.clicks.get(), 1);
+
+        // Escala zero: nada aparece e o ponteiro não acerta o conteúdo
+        app.t = Transform::scale(0.0);
+        headless.move_mouse(50.0, 20.0);
+        headless.step(&mut app);
+        headless.click(PointerButton::Primary);
+        headless.step(&mut app);
+        assert_eq!(app.clicks.get(), 1);
+        assert_eq!(headless.pixel(50, 20), Color::WHITE);
+        assert_eq!(headless.cursor_icon(), CursorIcon::Default);
+    }
+
+    /// Botão dentro de um `animated_visibility` que expande
+    struct Reveal {
+        visible: Cell<bool>,
+    }
+
+    impl App for Reveal {
+        fn update(&mut self, _input: &InputState) {}
+
+        fn draw(
+            &self,
+            frame: &mut [u8],
+            width: u32,
+            height: u32,
+            font: &FontArc,
+            atlas: &mut FontAtlas,
+            state: Rc<RefCell<StateStore>>,
+            input: &InputState,
+        ) {
+            clear(frame, Color::WHITE);
+            let mut ui = Ui::new(frame, width, height, font, atlas, state, input);
+            animated_visibility(&mut ui, self.visible.get(), Transition::expand(), |ui| {
+                column(ui, Modifier::new().size(100.0, 40.0), |ui| {
+                    button(ui, Modifier::new().size(100.0, 40.0), "Ok");
+                });
+            });
+        }
+    }
+
+    #[test]
+    fn expanding_content_is_clipped_to_the_revealed_band() {
+        let mut headless = Headless::new(200, 100, test_font());
+        let mut app = Reveal { visible: Cell::new(false) };
+
+        headless.step(&mut app);
+        assert_eq!(headless.pixel(50, 5), Color::WHITE);
+
+        app.visible.set(true);
+        headless.step(&mut app);
+        headless.step_by(&mut app, Duration::from_millis(50));
+        // O topo do botão já aparece; a parte de baixo ainda está recortada
+        assert_ne!(headless.pixel(50, 5), Color::WHITE);
+        assert_eq!(headless.pixel(50, 38), Color::WHITE);
+
+        headless.step_by(&mut app, Duration::from_millis(500));
+        assert_ne!(headless.pixel(50, 38), Color::WHITE);
+    }
+
+    /// Item de sidebar com um ícone ou, como referência, só o ícone desenhado à mão
+    struct SidebarIcon {
+        icon: Icon,
+        reference: bool,
+    }
+
+    impl SidebarIcon {
+        fn icon_rect() -> Rect {
+            let size = components::SIDEBAR_ICON_SIZE;
+            Rect {
+                x: spacing::MD,
+                y: (components::SIDEBAR_ITEM_HEIGHT - size) / 2.0,
+                w: size,
+                h: size,
+            }
+        }
+    }
+
+    impl App for SidebarIcon {
+        fn update(&mut self, _input: &InputState) {}
+
+        fn draw(
+            &self,
+            frame: &mut [u8],
+            width: u32,
+            height: u32,
+            font: &FontArc,
+            atlas: &mut FontAtlas,
+            state: Rc<RefCell<StateStore>>,
+            input: &InputState,
+        ) {
+            clear(frame, Color::WHITE);
+            let mut ui = Ui::new(frame, width, height, font, atlas, state, input);
+            if self.reference {
+                let color = ui.theme().colors.text_secondary.alpha(text_alpha::SECONDARY);
+                draw_icon(ui.frame, self.icon, Self::icon_rect(), color, width, height);
+            } else {
+                sidebar_item_with(&mut ui, Modifier::new().icon(self.icon), "Busca", false);
+            }
+        }
+    }
+
+    #[test]
+    fn sidebar_items_draw_the_chosen_icon() {
+        let icon_pixels = |icon: Icon, reference: bool| {
+            let mut headless = Headless::new(200, 60, test_font());
+            headless.move_mouse(190.0, 58.0);
+            headless.step(&mut SidebarIcon { icon, reference });
+            let r = SidebarIcon::icon_rect();
+            let (x0, y0) = (r.x as u32, r.y as u32);
+            let (x1, y1) = ((r.x + r.w).ceil() as u32, (r.y + r.h).ceil() as u32);
+            (y0..y1)
+                .flat_map(|y| (x0..x1).map(move |x| (x, y)))
+                .map(|(x, y)| headless.pixel(x, y))
+                .collect::<Vec<_>>()
+        };
+
+        // Pixel a pixel igual ao ícone escolhido, e diferente de outro ícone
+        let chosen = icon_pixels(Icon::Search, false);
+        assert_eq!(chosen, icon_pixels(Icon::Search, true));
+        assert_ne!(chosen, icon_pixels(Icon::Plus, true));
+        // O ícone aparece de fato (não é a comparação de duas áreas vazias)
+        assert!(chosen.iter().any(|&c| c != Color::WHITE));
+    }
+
+    /// Origem de arrasto carregando um `u32` e dois alvos: um compatível e um de `String`
+    #[derive(Default)]
+    struct Board {
+        hovered: Cell<(bool, bool)>,
+        dropped: Cell<Option<u32>>,
+    }
+
+    impl Board {
+        const SOURCE: Rect = Rect { x: 10.0, y: 10.0, w: 80.0, h: 30.0 };
+        const TARGET: Rect = Rect { x: 150.0, y: 10.0, w: 80.0, h: 30.0 };
+        const TEXT_TARGET: Rect = Rect { x: 150.0, y: 100.0, w: 80.0, h: 30.0 };
+    }
+
+    impl App for Board {
+        fn update(&mut self, _input: &InputState) {}
+
+        fn draw(
+            &self,
+            frame: &mut [u8],
+            width: u32,
+            height: u32,
+            font: &FontArc,
+            atlas: &mut FontAtlas,
+            state: Rc<RefCell<StateStore>>,
+            input: &InputState,
+        ) {
+            clear(frame, Color::WHITE);
+            let mut ui = Ui::new(frame, width, height, font, atlas, state, input);
+            ui.drag_source(1, Self::SOURCE, "Cartão", 7u32);
+            let target = ui.drop_target::<u32>(Self::TARGET);
+            let text_target = ui.drop_target::<String>(Self::TEXT_TARGET);
+            self.hovered.set((target.hovered, text_target.hovered));
+            if target.dropped.is_some() {
+                self.dropped.set(target.dropped);
+            }
+        }
+    }
+
+    #[test]
+    fn dragged_payloads_reach_compatible_drop_targets() {
+        let mut headless = Headless::new(300, 150, test_font());
+        let mut app = Board::default();
+
+        headless.move_mouse(20.0, 20.0);
+        headless.press(PointerButton::Primary);
+        headless.step(&mut app);
+        // Abaixo do threshold ainda não é arrasto
+        headless.move_mouse(22.0, 20.0);
+        headless.step(&mut app);
+        assert_ne!(headless.cursor_icon(), CursorIcon::Grabbing);
+
+        headless.move_mouse(160.0, 20.0);
+        headless.step(&mut app);
+        assert_eq!(app.hovered.get(), (true, false));
+        assert_eq!(headless.cursor_icon(), CursorIcon::Grabbing);
+
+        // O preview segue o cursor (o ponto agarrado fica sob o mouse) e o alvo apaga
+        headless.move_mouse(160.0, 70.0);
+        headless.step(&mut app);
+        assert_ne!(headless.pixel(220, 85), Color::WHITE);
+        assert_eq!(headless.pixel(220, 35), Color::WHITE);
+
+        // Sobre o alvo de `String` nada acontece
+        headless.move_mouse(160.0, 110.0);
+        headless.step(&mut app);
+        assert_eq!(app.hovered.get(), (false, false));
+
+        headless.move_mouse(160.0, 20.0);
+        headless.step(&mut app);
+        headless.release(PointerButton::Primary);
+        headless.step(&mut app);
+        assert_eq!(app.dropped.get(), Some(7));
+
+        // Depois do drop o arrasto acaba
+        headless.step(&mut app);
+        assert_eq!(app.hovered.get(), (false, false));
+        assert_eq!(headless.cursor_icon(), CursorIcon::Default);
+    }
+
+    /// Botão e alça de arrasto em cima, campo de texto logo abaixo
+    struct Form;
+
+    impl App for Form {
+        fn update(&mut self, _input: &InputState) {}
+
+        fn draw(
+            &self,
+            frame: &mut [u8],
+            width: u32,
+            height: u32,
+            font: &FontArc,
+            atlas: &mut FontAtlas,
+            state: Rc<RefCell<StateStore>>,
+            input: &InputState,
+        ) {
+            clear(frame, Color::WHITE);
+            let mut ui = Ui::new(frame, width, height, font, atlas, state, input);
+            button(&mut ui, Modifier::new().size(100.0, 40.0), "Ok");
+            ui.drag(1, Rect { x: 120.0, y: 0.0, w: 60.0, h: 40.0 });
+            ui.cursor.y += 60.0;
+            text_input(&mut ui, Modifier::new().size(150.0, 40.0), "Nome");
+        }
+    }
+
+    #[test]
+    fn widgets_pick_the_cursor_and_a_drag_keeps_it() {
+        let mut headless = Headless::new(200, 120, test_font());
+
+        headless.move_mouse(50.0, 80.0);
+        headless.step(&mut Form);
+        assert_eq!(headless.cursor_icon(), CursorIcon::Text);
+
+        headless.move_mouse(50.0, 20.0);
+        headless.step(&mut Form);
+        assert_eq!(headless.cursor_icon(), CursorIcon::Hand);
+
+        // Arrastando, passar sobre o campo (desenhado depois) não troca o cursor
+        headless.move_mouse(150.0, 20.0);
+        headless.press(PointerButton::Primary);
+        headless.step(&mut Form);
+        headless.move_mouse(50.0, 80.0);
+        headless.step(&mut Form);
+        assert_eq!(headless.cursor_icon(), CursorIcon::Grabbing);
+
+        headless.release(PointerButton::Primary);
+        headless.step(&mut Form);
+        headless.step(&mut Form);
+        assert_eq!(headless.cursor_icon(), CursorIcon::Text);
+
+        headless.move_mouse(180.0, 115.0);
+        headless.step(&mut Form);
+        assert_eq!(headless.cursor_icon(), CursorIcon::Default);
+    }
+
+    #[test]
+    fn animations_keep_frames_coming_until_they_settle() {
+        let mut headless = Headless::new(200, 100, test_font());
+        let mut app = Probe::default();
+
+        headless.move_mouse(150.0, 80.0);
+        headless.step(&mut app);
+        assert_eq!(headless.runner.next_repaint(), None);
+
+        // O hover anima: cada frame pede o próximo, imediatamente
+        headless.move_mouse(50.0, 20.0);
+        headless.step(&mut app);
+        assert_eq!(headless.runner.next_repaint(), Some(headless.clock));
+
+        let mut frames = 0;
+        while headless.runner.next_repaint().is_some() {
+            headless.step_by(&mut app, Duration::from_millis(16));
+            frames += 1;
+            assert!(frames < 100, "a animação não termina");
+        }
+        let expected = render::ANIMATION_DURATION_MS as u32 / 16;
+        assert!(frames >= expected, "{frames} frames");
+    }
+}
@@ -0,0 +1,467 @@
+//! Ícones vetoriais embutidos, desenhados numa grade 24x24 no estilo traço (2 unidades,
+//! pontas e junções redondas) e rasterizados com anti-aliasing em qualquer tamanho e cor.
+
+use crate::layout::Rect;
+use crate::paint::Paint;
+use crate::path::{FillRule, LineCap, LineJoin, Path, StrokeStyle, fill_path, stroke_path};
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+/// Largura do traço na grade 24x24
+const STROKE_WIDTH: f32 = 2.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Icon {
+    Plus,
+    Minus,
+    Check,
+    Close,
+    ChevronLeft,
+    ChevronRight,
+    ChevronUp,
+    ChevronDown,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    ArrowDown,
+    Search,
+    Settings,
+    User,
+    Users,
+    Trash,
+    Edit,
+    Info,
+    Warning,
+    Error,
+    Home,
+    Menu,
+    More,
+    Chart,
+    TrendingUp,
+    Bell,
+    Mail,
+    Calendar,
+    Download,
+    Upload,
+    Refresh,
+    Star,
+    Heart,
+    Lock,
+    Eye,
+    Filter,
+    Copy,
+    Folder,
+    File,
+    Logout,
+    Play,
+    Pause,
+}
+
+impl Icon {
+    /// Todos os ícones, na ordem da declaração (útil para galerias)
+    pub const ALL: [Icon; 43] = [
+        Icon::Plus,
+        Icon::Minus,
+        Icon::Check,
+        Icon::Close,
+        Icon::ChevronLeft,
+        Icon::ChevronRight,
+        Icon::ChevronUp,
+        Icon::ChevronDown,
+        Icon::ArrowLeft,
+        Icon::ArrowRight,
+        Icon::ArrowUp,
+        Icon::ArrowDown,
+        Icon::Search,
+        Icon::Settings,
+        Icon::User,
+        Icon::Users,
+        Icon::Trash,
+        Icon::Edit,
+        Icon::Info,
+        Icon::Warning,
+        Icon::Error,
+        Icon::Home,
+        Icon::Menu,
+        Icon::More,
+        Icon::Chart,
+        Icon::TrendingUp,
+        Icon::Bell,
+        Icon::Mail,
+        Icon::Calendar,
+        Icon::Download,
+        Icon::Upload,
+        Icon::Refresh,
+        Icon::Star,
+        Icon::Heart,
+        Icon::Lock,
+        Icon::Eye,
+        Icon::Filter,
+        Icon::Copy,
+        Icon::Folder,
+        Icon::File,
+        Icon::Logout,
+        Icon::Play,
+        Icon::Pause,
+    ];
+
+    /// Geometria do ícone no quadrado `(x, y, size)`: (partes com traço, partes preenchidas)
+    pub fn paths(self, x: f32, y: f32, size: f32) -> (Path, Path) {
+        let s = Pen::new(x, y, size);
+        let f = Pen::new(x, y, size);
+        let (s, f) = match self {
+            Icon::Plus => (s.m(12.0, 5.0).l(12.0, 19.0).m(5.0, 12.0).l(19.0, 12.0), f),
+            Icon::Minus => (s.m(5.0, 12.0).l(19.0, 12.0), f),
+            Icon::Check => (s.m(20.0, 6.0).l(9.0, 17.0).l(4.0, 12.0), f),
+            Icon::Close => (s.m(18.0, 6.0).l(6.0, 18.0).m(6.0, 6.0).l(18.0, 18.0), f),
+            Icon::ChevronLeft => (s.m(15.0, 18.0).l(9.0, 12.0).l(15.0, 6.0), f),
+            Icon::ChevronRight => (s.m(9.0, 18.0).l(15.0, 12.0).l(9.0, 6.0), f),
+            Icon::ChevronUp => (s.m(18.0, 15.0).l(12.0, 9.0).l(6.0, 15.0), f),
+            Icon::ChevronDown => (s.m(6.0, 9.0).l(12.0, 15.0).l(18.0, 9.0), f),
+            Icon::ArrowLeft => (s.m(19.0, 12.0).l(5.0, 12.0).m(12.0, 19.0).l(5.0, 12.0).l(12.0, 5.0), f),
+            Icon::ArrowRight => (s.m(5.0, 12.0).l(19.0, 12.0).m(12.0, 5.0).l(19.0, 12.0).l(12.0, 19.0), f),
+            Icon::ArrowUp => (s.m(12.0, 19.0).l(12.0, 5.0).m(5.0, 12.0).l(12.0, 5.0).l(19.0, 12.0), f),
+            Icon::ArrowDown => (s.m(12.0, 5.0).l(12.0, 19.0).m(19.0, 12.0).l(12.0, 19.0).l(5.0, 12.0), f),
+            Icon::Search => (s.circle(11.0, 11.0, 8.0).m(21.0, 21.0).l(16.65, 16.65), f),
+            Icon::Settings => (s.gear().circle(12.0, 12.0, 3.0), f),
+            Icon::User => (
+                s.circle(12.0, 7.0, 4.0)
+                    .m(4.0, 21.0)
+                    .l(4.0, 19.0)
+                    .arc(8.0, 19.0, 4.0, PI, FRAC_PI_2)
+                    .l(16.0, 15.0)
+                    .arc(16.0, 19.0, 4.0, -FRAC_PI_2, FRAC_PI_2)
+                    .l(20.0, 21.0),
+                f,
+            ),
+            Icon::Users => (
+                s.circle(9.0, 7.0, 4.0)
+                    .m(2.0, 21.0)
+                    .l(2.0, 19.0)
+                    .arc(6.0, 19.0, 4.0, PI, FRAC_PI_2)
+                    .l(12.0, 15.0)
+                    .arc(12.0, 19.0, 4.0, -FRAC_PI_2, FRAC_PI_2)
+                    .l(16.0, 21.0)
+                    .m(16.0, 3.13)
+                    .arc(15.0, 7.0, 4.0, -1.32, 2.64)
+                    .m(22.0, 21.0)
+                    .l(22.0, 19.0)
+                    .q(22.0, 16.0, 19.0, 15.13),
+                f,
+            ),
+            Icon::Trash => (
+                s.m(3.0, 6.0)
+                    .l(21.0, 6.0)
+                    .m(19.0, 6.0)
+                    .l(19.0, 20.0)
+                    .q(19.0, 22.0, 17.0, 22.0)
+                    .l(7.0, 22.0)
+                    .q(5.0, 22.0, 5.0, 20.0)
+                    .l(5.0, 6.0)
+                    .m(8.0, 6.0)
+                    .l(8.0, 4.0)
+                    .q(8.0, 2.0, 10.0, 2.0)
+                    .l(14.0, 2.0)
+                    .q(16.0, 2.0, 16.0, 4.0)
+                    .l(16.0, 6.0)
+                    .m(10.0, 11.0)
+                    .l(10.0, 17.0)
+                    .m(14.0, 11.0)
+                    .l(14.0, 17.0),
+                f,
+            ),
+            Icon::Edit => (
+                s.m(17.0, 3.0)
+                    .l(21.0, 7.0)
+                    .l(8.0, 20.0)
+                    .l(3.0, 21.0)
+                    .l(4.0, 16.0)
+                    .z()
+                    .m(15.0, 5.0)
+                    .l(19.0, 9.0),
+                f,
+            ),
+            Icon::Info => (s.circle(12.0, 12.0, 10.0).m(12.0, 16.0).l(12.0, 12.0), f.circle(12.0, 8.0, 1.25)),
+            Icon::Warning => (
+                s.m(12.0, 3.0).l(22.0, 20.0).l(2.0, 20.0).z().m(12.0, 9.0).l(12.0, 13.0),
+                f.circle(12.0, 16.5, 1.25),
+            ),
+            Icon::Error => (
+                s.circle(12.0, 12.0, 10.0).m(15.0, 9.0).l(9.0, 15.0).m(9.0, 9.0).l(15.0, 15.0),
+                f,
+            ),
+            Icon::Home => (
+                s.m(3.0, 9.0)
+                    .l(12.0, 2.0)
+                    .l(21.0, 9.0)
+                    .l(21.0, 20.0)
+                    .q(21.0, 22.0, 19.0, 22.0)
+                    .l(5.0, 22.0)
+                    .q(3.0, 22.0, 3.0, 20.0)
+                    .z()
+                    .m(9.0, 22.0)
+                    .l(9.0, 12.0)
+                    .l(15.0, 12.0)
+                    .l(15.0, 22.0),
+                f,
+            ),
+            Icon::Menu => (s.m(3.0, 6.0).l(21.0, 6.0).m(3.0, 12.0).l(21.0, 12.0).m(3.0, 18.0).l(21.0, 18.0), f),
+            Icon::More => (s, f.circle(5.0, 12.0, 1.75).circle(12.0, 12.0, 1.75).circle(19.0, 12.0, 1.75)),
+            Icon::Chart => (s.m(12.0, 20.0).l(12.0, 10.0).m(18.0, 20.0).l(18.0, 4.0).m(6.0, 20.0).l(6.0, 16.0), f),
+            Icon::TrendingUp => (
+                s.m(22.0, 7.0).l(13.5, 15.5).l(8.5, 10.5).l(2.0, 17.0).m(16.0, 7.0).l(22.0, 7.0).l(22.0, 13.0),
+                f,
+            ),
+            Icon::Bell => (
+                s.m(3.0, 17.0)
+                    .c(3.0, 17.0, 6.0, 15.0, 6.0, 8.0)
+                    .arc(12.0, 8.0, 6.0, PI, PI)
+                    .c(18.0, 15.0, 21.0, 17.0, 21.0, 17.0)
+                    .z()
+                    .m(10.3, 21.0)
+                    .q(12.0, 22.6, 13.7, 21.0),
+                f,
+            ),
+            Icon::Mail => (s.rounded_rect(2.0, 4.0, 20.0, 16.0, 2.0).m(22.0, 6.0).l(12.0, 13.0).l(2.0, 6.0), f),
+            Icon::Calendar => (
+                s.rounded_rect(3.0, 4.0, 18.0, 18.0, 2.0)
+                    .m(16.0, 2.0)
+                    .l(16.0, 6.0)
+                    .m(8.0, 2.0)
+                    .l(8.0, 6.0)
+                    .m(3.0, 10.0)
+                    .l(21.0, 10.0),
+                f,
+            ),
+            Icon::Download => (s.tray().m(7.0, 10.0).l(12.0, 15.0).l(17.0, 10.0).m(12.0, 15.0).l(12.0, 3.0), f),
+            Icon::Upload => (s.tray().m(17.0, 8.0).l(12.0, 3.0).l(7.0, 8.0).m(12.0, 3.0).l(12.0, 15.0), f),
+            Icon::Refresh => (
+                s.m(21.0, 12.0)
+                    .arc(12.0, 12.0, 9.0, 0.0, PI * 1.75)
+                    .m(21.0, 3.0)
+                    .l(21.0, 8.0)
+                    .l(16.0, 8.0),
+                f,
+            ),
+            Icon::Star => (s.star(), f),
+            Icon::Heart => (
+                s.m(12.0, 20.0)
+                    .c(12.0, 20.0, 3.0, 14.0, 3.0, 8.5)
+                    .c(3.0, 5.5, 5.5, 3.5, 8.0, 3.5)
+                    .c(10.0, 3.5, 11.2, 4.7, 12.0, 6.0)
+                    .c(12.8, 4.7, 14.0, 3.5, 16.0, 3.5)
+                    .c(18.5, 3.5, 21.0, 5.5, 21.0, 8.5)
+                    .c(21.0, 14.0, 12.0, 20.0, 12.0, 20.0)
+                    .z(),
+                f,
+            ),
+            Icon::Lock => (
+                s.rounded_rect(3.0, 11.0, 18.0, 11.0, 2.0)
+                    .m(7.0, 11.0)
+                    .l(7.0, 7.0)
+                    .arc(12.0, 7.0, 5.0, PI, PI)
+                    .l(17.0, 11.0),
+                f,
+            ),
+            Icon::Eye => (
+                s.m(2.0, 12.0)
+                    .c(4.0, 7.0, 8.0, 5.0, 12.0, 5.0)
+                    .c(16.0, 5.0, 20.0, 7.0, 22.0, 12.0)
+                    .c(20.0, 17.0, 16.0, 19.0, 12.0, 19.0)
+                    .c(8.0, 19.0, 4.0, 17.0, 2.0, 12.0)
+                    .z()
+                    .circle(12.0, 12.0, 3.0),
+                f,
+            ),
+            Icon::Filter => (
+                s.m(22.0, 3.0).l(2.0, 3.0).l(10.0, 12.46).l(10.0, 19.0).l(14.0, 21.0).l(14.0, 12.46).z(),
+                f,
+            ),
+            Icon::Copy => (
+                s.rounded_rect(9.0, 9.0, 13.0, 13.0, 2.0)
+                    .m(5.0, 15.0)
+                    .l(4.0, 15.0)
+                    .q(2.0, 15.0, 2.0, 13.0)
+                    .l(2.0, 4.0)
+                    .q(2.0, 2.0, 4.0, 2.0)
+                    .l(13.0, 2.0)
+                    .q(15.0, 2.0, 15.0, 4.0)
+                    .l(15.0, 5.0),
+                f,
+            ),
+            Icon::Folder => (
+                s.m(22.0, 19.0)
+                    .q(22.0, 21.0, 20.0, 21.0)
+                    .l(4.0, 21.0)
+                    .q(2.0, 21.0, 2.0, 19.0)
+                    .l(2.0, 5.0)
+                    .q(2.0, 3.0, 4.0, 3.0)
+                    .l(9.0, 3.0)
+                    .l(11.0, 6.0)
+                    .l(20.0, 6.0)
+                    .q(22.0, 6.0, 22.0, 8.0)
+                    .z(),
+                f,
+            ),
+            Icon::File => (
+                s.m(14.0, 2.0)
+                    .l(6.0, 2.0)
+                    .q(4.0, 2.0, 4.0, 4.0)
+                    .l(4.0, 20.0)
+                    .q(4.0, 22.0, 6.0, 22.0)
+                    .l(18.0, 22.0)
+                    .q(20.0, 22.0, 20.0, 20.0)
+                    .l(20.0, 8.0)
+                    .z()
+                    .m(14.0, 2.0)
+                    .l(14.0, 8.0)
+                    .l(20.0, 8.0),
+                f,
+            ),
+            Icon::Logout => (
+                s.m(9.0, 21.0)
+                    .l(5.0, 21.0)
+                    .q(3.0, 21.0, 3.0, 19.0)
+                    .l(3.0, 5.0)
+                    .q(3.0, 3.0, 5.0, 3.0)
+                    .l(9.0, 3.0)
+                    .m(16.0, 17.0)
+                    .l(21.0, 12.0)
+                    .l(16.0, 7.0)
+                    .m(21.0, 12.0)
+                    .l(9.0, 12.0),
+                f,
+            ),
+            Icon::Play => (s.m(6.0, 3.0).l(20.0, 12.0).l(6.0, 21.0).z(), f),
+            Icon::Pause => (s.rounded_rect(6.0, 4.0, 4.0, 16.0, 0.5).rounded_rect(14.0, 4.0, 4.0, 16.0, 0.5), f),
+        };
+        (s.path, f.path)
+    }
+}
+
+/// Desenha `icon` no maior quadrado centrado em `rect`, com a tinta dada
+pub fn draw_icon(f: &mut [u8], icon: Icon, rect: Rect, paint: impl Into<Paint>, sw: u32, sh: u32) {
+    let size = rect.w.min(rect.h);
+    if size <= 0.0 {
+        return;
+    }
+    let (x, y) = (rect.x + (rect.w - size) / 2.0, rect.y + (rect.h - size) / 2.0);
+    let paint = paint.into();
+    let (stroked, filled) = icon.paths(x, y, size);
+    let style = StrokeStyle::new(STROKE_WIDTH * size / 24.0)
+        .join(LineJoin::Round)
+        .cap(LineCap::Round);
+    stroke_path(f, &stroked, &style, paint, sw, sh);
+    fill_path(f, &filled, FillRule::NonZero, paint, sw, sh);
+}
+
+/// Monta um `Path` com coordenadas da grade 24x24, já convertidas para pixels: as curvas são
+/// achatadas na escala final, então ícones grandes continuam lisos
+struct Pen {
+    path: Path,
+    x: f32,
+    y: f32,
+    scale: f32,
+}
+
+impl Pen {
+    fn new(x: f32, y: f32, size: f32) -> Self {
+        Self {
+            path: Path::new(),
+            x,
+            y,
+            scale: size / 24.0,
+        }
+    }
+
+    fn px(&self, x: f32, y: f32) -> (f32, f32) {
+        (self.x + x * self.scale, self.y + y * self.scale)
+    }
+
+    fn m(mut self, x: f32, y: f32) -> Self {
+        let (x, y) = self.px(x, y);
+        self.path = self.path.move_to(x, y);
+        self
+    }
+
+    fn l(mut self, x: f32, y: f32) -> Self {
+        let (x, y) = self.px(x, y);
+        self.path = self.path.line_to(x, y);
+        self
+    }
+
+    fn q(mut self, cx: f32, cy: f32, x: f32, y: f32) -> Self {
+        let (cx, cy) = self.px(cx, cy);
+        let (x, y) = self.px(x, y);
+        self.path = self.path.quad_to(cx, cy, x, y);
+        self
+    }
+
+    fn c(mut self, c1x: f32, c1y: f32, c2x: f32, c2y: f32, x: f32, y: f32) -> Self {
+        let (c1x, c1y) = self.px(c1x, c1y);
+        let (c2x, c2y) = self.px(c2x, c2y);
+        let (x, y) = self.px(x, y);
+        self.path = self.path.cubic_to(c1x, c1y, c2x, c2y, x, y);
+        self
+    }
+
+    fn arc(mut self, cx: f32, cy: f32, r: f32, start: f32, sweep: f32) -> Self {
+        let (cx, cy) = self.px(cx, cy);
+        self.path = self.path.arc(cx, cy, r * self.scale, start, sweep);
+        self
+    }
+
+    fn z(mut self) -> Self {
+        self.path = self.path.close();
+        self
+    }
+
+    fn circle(mut self, cx: f32, cy: f32, r: f32) -> Self {
+        let (cx, cy) = self.px(cx, cy);
+        self.path = self.path.extend(Path::circle(cx, cy, r * self.scale));
+        self
+    }
+
+    fn rounded_rect(mut self, x: f32, y: f32, w: f32, h: f32, r: f32) -> Self {
+        let (x, y) = self.px(x, y);
+        let s = self.scale;
+        self.path = self.path.extend(Path::rounded_rect(x, y, w * s, h * s, r * s));
+        self
+    }
+
+    /// Bandeja aberta de download/upload
+    fn tray(self) -> Self {
+        self.m(21.0, 15.0)
+            .l(21.0, 19.0)
+            .q(21.0, 21.0, 19.0, 21.0)
+            .l(5.0, 21.0)
+            .q(3.0, 21.0, 3.0, 19.0)
+            .l(3.0, 15.0)
+    }
+
+    /// Engrenagem de 8 dentes centrada em (12, 12)
+    fn gear(mut self) -> Self {
+        const TEETH: usize = 8;
+        let (outer, inner) = (10.0, 7.5);
+        let step = TAU / TEETH as f32;
+        let profile = (0..TEETH).flat_map(|i| {
+            let a = i as f32 * step;
+            [(a - 0.42, inner), (a - 0.2, outer), (a + 0.2, outer), (a + 0.42, inner)]
+        });
+        for (n, (angle, r)) in profile.enumerate() {
+            let (x, y) = (12.0 + r * angle.cos(), 12.0 + r * angle.sin());
+            self = if n == 0 { self.m(x, y) } else { self.l(x, y) };
+        }
+        self.z()
+    }
+
+    /// Estrela de 5 pontas
+    fn star(mut self) -> Self {
+        for i in 0..10 {
+            let r = if i % 2 == 0 { 10.0 } else { 4.2 };
+            let angle = -FRAC_PI_2 + i as f32 * PI / 5.0;
+            let (x, y) = (12.0 + r * angle.cos(), 12.5 + r * angle.sin());
+            self = if i == 0 { self.m(x, y) } else { self.l(x, y) };
+        }
+        self.z()
+    }
+}
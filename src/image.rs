@@ -0,0 +1,648 @@
+//! Imagens bitmap: decodificação (PNG, JPEG, BMP), cache por caminho ou bytes e desenho
+//! com modos de ajuste, amostragem bilinear/nearest, recorte arredondado e tint.
+//! Os pixels ficam em RGBA pré-multiplicado, o mesmo formato do frame.
+
+use crate::layout::Rect;
+use crate::parallel::for_each_band_in;
+use crate::renderer::{BlendMode, Color, CornerRadii, Premul, blend_premul, rounded_rect_distance};
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::Path;
+
+/// Bitmap RGBA pré-multiplicado com mipmaps para reduções grandes
+pub struct Image {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+    /// Versões reduzidas à metade, da maior para a menor
+    mips: Vec<Image>,
+}
+
+impl Image {
+    /// Cria a partir de RGBA com alpha não pré-multiplicado (`width * height * 4` bytes);
+    /// imagens sem pixels ou com buffer do tamanho errado são rejeitadas
+    pub fn from_rgba(width: u32, height: u32, mut rgba: Vec<u8>) -> io::Result<Self> {
+        check_size(width, height, rgba.len())?;
+        for p in rgba.chunks_exact_mut(4) {
+            let a = p[3] as u32;
+            if a < 255 {
+                for c in &mut p[..3] {
+                    *c = ((*c as u32 * a + 127) / 255) as u8;
+                }
+            }
+        }
+        Self::from_premultiplied(width, height, rgba)
+    }
+
+    /// Cria a partir de RGBA já pré-multiplicado (ex: um buffer rasterizado pelo renderer)
+    pub fn from_premultiplied(width: u32, height: u32, pixels: Vec<u8>) -> io::Result<Self> {
+        check_size(width, height, pixels.len())?;
+        let mut image = Self {
+            width,
+            height,
+            pixels,
+            mips: Vec::new(),
+        };
+        image.build_mips();
+        Ok(image)
+    }
+
+    /// Decodifica PNG, JPEG ou BMP (o formato é detectado pelo conteúdo)
+    pub fn decode(bytes: &[u8]) -> io::Result<Self> {
+        let decoded = ::image::load_from_memory(bytes)
+            .map_err(|e| invalid(&e.to_string()))?
+            .to_rgba8();
+        let (width, height) = decoded.dimensions();
+        Self::from_rgba(width, height, decoded.into_raw())
+    }
+
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::decode(&std::fs::read(path)?)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Pixels RGBA pré-multiplicados, linha a linha
+    pub fn pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    fn build_mips(&mut self) {
+        let mut mips: Vec<Image> = Vec::new();
+        let (mut w, mut h) = (self.width, self.height);
+        while w > 1 || h > 1 {
+            let src = mips.last().map_or(&self.pixels, |m| &m.pixels);
+            let (nw, nh) = ((w / 2).max(1), (h / 2).max(1));
+            let mut pixels = vec![0u8; (nw * nh * 4) as usize];
+            for y in 0..nh {
+                for x in 0..nw {
+                    // Média 2x2 (pré-multiplicada, então as bordas transparentes não escurecem)
+                    let (x0, y0) = ((x * 2).min(w - 1), (y * 2).min(h - 1));
+                    let (x1, y1) = ((x * 2 + 1).min(w - 1), (y * 2 + 1).min(h - 1));
+                    let texel = |tx: u32, ty: u32| &src[((ty * w + tx) * 4) as usize..][..4];
+                    let out = &mut pixels[((y * nw + x) * 4) as usize..][..4];
+                    for (c, channel) in out.iter_mut().enumerate() {
+                        let sum = texel(x0, y0)[c] as u32
+                            + texel(x1, y0)[c] as u32
+                            + texel(x0, y1)[c] as u32
+                            + texel(x1, y1)[c] as u32;
+                        *channel = ((sum + 2) / 4) as u8;
+                    }
+                }
+            }
+            mips.push(Image {
+                width: nw,
+                height: nh,
+                pixels,
+                mips: Vec::new(),
+            });
+            (w, h) = (nw, nh);
+        }
+        self.mips = mips;
+    }
+
+    /// Nível de mipmap para `texels_per_pixel` texels por pixel de tela (0 = original)
+    fn level(&self, texels_per_pixel: f32) -> (usize, &Image) {
+        let n = (texels_per_pixel.max(1.0).log2().floor() as usize).min(self.mips.len());
+        match n {
+            0 => (0, self),
+            n => (n, &self.mips[n - 1]),
+        }
+    }
+
+    #[inline]
+    fn texel(&self, x: i32, y: i32) -> Premul {
+        let x = x.clamp(0, self.width as i32 - 1) as u32;
+        let y = y.clamp(0, self.height as i32 - 1) as u32;
+        Premul::load(&self.pixels[((y * self.width + x) * 4) as usize..][..4])
+    }
+
+    /// Amostra na posição `(u, v)` em texels (centros em `i + 0.5`)
+    #[inline]
+    fn sample(&self, u: f32, v: f32, sampling: Sampling) -> Premul {
+        match sampling {
+            Sampling::Nearest => self.texel(u.floor() as i32, v.floor() as i32),
+            Sampling::Bilinear => {
+                let (u, v) = (u - 0.5, v - 0.5);
+                let (x, y) = (u.floor(), v.floor());
+                let (fx, fy) = (u - x, v - y);
+                let (x, y) = (x as i32, y as i32);
+                let mix = |a: Premul, b: Premul, t: f32| Premul {
+                    r: a.r + (b.r - a.r) * t,
+                    g: a.g + (b.g - a.g) * t,
+                    b: a.b + (b.b - a.b) * t,
+                    a: a.a + (b.a - a.a) * t,
+                };
+                let top = mix(self.texel(x, y), self.texel(x + 1, y), fx);
+                let bottom = mix(self.texel(x, y + 1), self.texel(x + 1, y + 1), fx);
+                mix(top, bottom, fy)
+            }
+        }
+    }
+}
+
+// ============================================================================
+// OPÇÕES DE DESENHO
+// ============================================================================
+
+/// Como a imagem ocupa o retângulo de destino
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum ImageFit {
+    /// Inteira dentro do retângulo, mantendo a proporção (sobra espaço)
+    #[default]
+    Contain,
+    /// Cobre o retângulo todo, mantendo a proporção (corta o excesso)
+    Cover,
+    /// Estica para o tamanho exato
+    Fill,
+    /// Tamanho original, centralizada
+    None,
+}
+
+impl ImageFit {
+    /// Onde a imagem de `width x height` é posicionada dentro de `rect`
+    pub fn place(self, width: f32, height: f32, rect: Rect) -> Rect {
+        let (w, h) = match self {
+            ImageFit::Fill => return rect,
+            ImageFit::None => (width, height),
+            ImageFit::Contain | ImageFit::Cover => {
+                let (sx, sy) = (rect.w / width, rect.h / height);
+                let scale = if self == ImageFit::Contain { sx.min(sy) } else { sx.max(sy) };
+                (width * scale, height * scale)
+            }
+        };
+        Rect {
+            x: rect.x + (rect.w - w) / 2.0,
+            y: rect.y + (rect.h - h) / 2.0,
+            w,
+            h,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Sampling {
+    /// Suave, com mipmaps nas reduções
+    #[default]
+    Bilinear,
+    /// Pixels duros (pixel art, ícones em escala inteira)
+    Nearest,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ImageStyle {
+    pub fit: ImageFit,
+    pub sampling: Sampling,
+    /// Pinta a imagem com esta cor usando só o alpha dela (ícones monocromáticos)
+    pub tint: Option<Color>,
+}
+
+impl ImageStyle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn fit(mut self, fit: ImageFit) -> Self {
+        self.fit = fit;
+        self
+    }
+
+    pub fn nearest(mut self) -> Self {
+        self.sampling = Sampling::Nearest;
+        self
+    }
+
+    pub fn tint(mut self, color: Color) -> Self {
+        self.tint = Some(color);
+        self
+    }
+}
+
+/// Largura e altura não nulas e buffer com exatamente `width * height * 4` bytes
+fn check_size(width: u32, height: u32, len: usize) -> io::Result<()> {
+    if width == 0 || height == 0 {
+        return Err(invalid("imagem sem pixels"));
+    }
+    if Some(len) != (width as usize).checked_mul(height as usize).and_then(|n| n.checked_mul(4)) {
+        return Err(invalid("tamanho do buffer RGBA inválido"));
+    }
+    Ok(())
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+// ============================================================================
+// CACHE
+// ============================================================================
+
+/// Identifica uma imagem no `ImageCache` (hash do caminho ou dos bytes)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ImageHandle(u64);
+
+/// De onde vem uma imagem: arquivo, bytes em memória ou uma já carregada
+#[derive(Clone, Copy, Debug)]
+pub enum ImageSource<'a> {
+    Path(&'a Path),
+    Bytes(&'a [u8]),
+    Handle(ImageHandle),
+}
+
+impl ImageSource<'_> {
+    /// Chave no cache; bytes são hasheados inteiros, então imagens grandes em memória
+    /// devem ser carregadas uma vez (`load`) e usadas pelo handle
+    pub fn handle(&self) -> ImageHandle {
+        let mut hasher = DefaultHasher::new();
+        match self {
+            ImageSource::Path(path) => (0u8, path).hash(&mut hasher),
+            ImageSource::Bytes(bytes) => (1u8, bytes).hash(&mut hasher),
+            ImageSource::Handle(handle) => return *handle,
+        }
+        ImageHandle(hasher.finish())
+    }
+}
+
+impl<'a> From<&'a str> for ImageSource<'a> {
+    fn from(path: &'a str) -> Self {
+        ImageSource::Path(Path::new(path))
+    }
+}
+
+impl<'a> From<&'a Path> for ImageSource<'a> {
+    fn from(path: &'a Path) -> Self {
+        ImageSource::Path(path)
+    }
+}
+
+impl<'a> From<&'a [u8]> for ImageSource<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        ImageSource::Bytes(bytes)
+    }
+}
+
+/// `include_bytes!("logo.png")`
+impl<'a, const N: usize> From<&'a [u8; N]> for ImageSource<'a> {
+    fn from(bytes: &'a [u8; N]) -> Self {
+        ImageSource::Bytes(bytes)
+    }
+}
+
+impl From<ImageHandle> for ImageSource<'_> {
+    fn from(handle: ImageHandle) -> Self {
+        ImageSource::Handle(handle)
+    }
+}
+
+/// Imagens decodificadas; falhas também ficam registradas para não reler o arquivo a cada frame
+#[derive(Default)]
+pub struct ImageCache {
+    images: HashMap<ImageHandle, Option<Image>>,
+    next_id: u64,
+}
+
+impl ImageCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Carrega (uma vez) e retorna o handle; `None` se a imagem não pôde ser lida
+    pub fn load(&mut self, source: ImageSource) -> Option<ImageHandle> {
+        let handle = source.handle();
+        let entry = self.images.entry(handle).or_insert_with(|| {
+            let result = match source {
+                ImageSource::Path(path) => Image::open(path),
+                ImageSource::Bytes(bytes) => Image::decode(bytes),
+                ImageSource::Handle(_) => return None,
+            };
+            result
+                .inspect_err(|e| log::warn!("Falha ao carregar imagem {:?}: {}", source, e))
+                .ok()
+        });
+        entry.as_ref().map(|_| handle)
+    }
+
+    /// Adiciona uma imagem gerada em memória
+    pub fn insert(&mut self, image: Image) -> ImageHandle {
+        self.next_id += 1;
+        let mut hasher = DefaultHasher::new();
+        (2u8, self.next_id).hash(&mut hasher);
+        let handle = ImageHandle(hasher.finish());
+        self.images.insert(handle, Some(image));
+        handle
+    }
+
+    pub fn get(&self, handle: ImageHandle) -> Option<&Image> {
+        self.images.get(&handle).and_then(Option::as_ref)
+    }
+
+    /// Remove do cache (a próxima `load` do mesmo caminho relê o arquivo)
+    pub fn remove(&mut self, handle: ImageHandle) {
+        self.images.remove(&handle);
+    }
+
+    pub fn len(&self) -> usize {
+        self.images.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.images.is_empty()
+    }
+}
+
+// ============================================================================
+// DESENHO
+// ============================================================================
+
+/// Desenha `image` em `rect` conforme o estilo, recortada pelos cantos `radius`
+pub fn draw_image(
+    f: &mut [u8],
+    image: &Image,
+    rect: Rect,
+    radius: impl Into<CornerRadii>,
+    style: &ImageStyle,
+    sw: u32,
+    sh: u32,
+) {
+    if image.width == 0 || image.height == 0 {
+        return;
+    }
+    let placed = style.fit.place(image.width as f32, image.height as f32, rect);
+    let source = Rect {
+        x: 0.0,
+        y: 0.0,
+        w: image.width as f32,
+        h: image.height as f32,
+    };
+    let radii = radius.into().clamped(rect.w, rect.h);
+    let region = ImageRegion::new(source, placed).clip(rect, radii);
+    draw_image_region(f, image, &region, style, sw, sh);
+}
+
+/// Região `source` (em pixels da imagem) esticada sobre `dest`, visível só dentro de `clip`
+/// com cantos `radii` (já limitados ao tamanho de `clip`)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ImageRegion {
+    pub source: Rect,
+    pub dest: Rect,
+    pub clip: Rect,
+    pub radii: CornerRadii,
+}
+
+impl ImageRegion {
+    /// Sem recorte além do próprio destino
+    pub fn new(source: Rect, dest: Rect) -> Self {
+        Self {
+            source,
+            dest,
+            clip: dest,
+            radii: CornerRadii::ZERO,
+        }
+    }
+
+    pub fn clip(mut self, clip: Rect, radii: CornerRadii) -> Self {
+        self.clip = clip;
+        self.radii = radii;
+        self
+    }
+}
+
+/// Desenha a `region` de `image` conforme o estilo (o ajuste `fit` já está em `dest`)
+pub fn draw_image_region(f: &mut [u8], image: &Image, region: &ImageRegion, style: &ImageStyle, sw: u32, sh: u32) {
+    let ImageRegion {
+        source,
+        dest,
+        clip,
+        radii,
+    } = *region;
+    if dest.w <= 0.0 || dest.h <= 0.0 || source.w <= 0.0 || source.h <= 0.0 {
+        return;
+    }
+    // Área visível: destino ∩ recorte
+    let vx0 = dest.x.max(clip.x);
+    let vy0 = dest.y.max(clip.y);
+    let vx1 = (dest.x + dest.w).min(clip.x + clip.w);
+    let vy1 = (dest.y + dest.h).min(clip.y + clip.h);
+    let x_start = (vx0.floor() as i32).max(0);
+    let x_end = (vx1.ceil() as i32).min(sw as i32);
+    let y_start = (vy0.floor() as i32).max(0);
+    let y_end = (vy1.ceil() as i32).min(sh as i32);
+    if x_start >= x_end || y_start >= y_end {
+        return;
+    }
+
+    // Texels por pixel de tela decide o mipmap (nearest usa sempre o original)
+    let (sx, sy) = (source.w / dest.w, source.h / dest.h);
+    let (level, bitmap) = match style.sampling {
+        Sampling::Bilinear => image.level(sx.min(sy)),
+        Sampling::Nearest => (0, image),
+    };
+    let k = (1u32 << level) as f32;
+    let (sx, sy, ox, oy) = (sx / k, sy / k, source.x / k, source.y / k);
+    // Limites da região no nível escolhido: a amostragem não vaza para fora dela
+    let edge = match style.sampling {
+        Sampling::Bilinear => 0.5,
+        Sampling::Nearest => 0.001,
+    };
+    let u_range = (ox + edge, (ox + source.w / k - edge).max(ox + edge));
+    let v_range = (oy + edge, (oy + source.h / k - edge).max(oy + edge));
+
+    // Só os cantos precisam do SDF do recorte
+    let left = clip.x + radii.top_left.max(radii.bottom_left);
+    let right = clip.x + clip.w - radii.top_right.max(radii.bottom_right);
+    let top = clip.y + radii.top_left.max(radii.top_right);
+    let bottom = clip.y + clip.h - radii.bottom_left.max(radii.bottom_right);
+
+    let stride = sw as usize * 4;
+    let area = ((x_end - x_start) * (y_end - y_start)) as usize;
+    let rows = y_start as u32..y_end as u32;
+    for_each_band_in(f, stride, rows, area, |band, first, count| {
+        for py in first as i32..(first + count) as i32 {
+            let (y0, y1) = (py as f32, py as f32 + 1.0);
+            let cover_y = (y1.min(vy1) - y0.max(vy0)).clamp(0.0, 1.0);
+            let v = ((y0 + 0.5 - dest.y) * sy + oy).clamp(v_range.0, v_range.1);
+            let straight_row = y0 >= top && y1 <= bottom;
+            let row = (py as u32 - first) as usize * stride;
+
+            for px in x_start..x_end {
+                let (x0, x1) = (px as f32, px as f32 + 1.0);
+                let mut coverage = (x1.min(vx1) - x0.max(vx0)).clamp(0.0, 1.0) * cover_y;
+                if !(straight_row || (x0 >= left && x1 <= right)) {
+                    coverage *= (0.5 - rounded_rect_distance(x0 + 0.5, y0 + 0.5, clip, &radii)).clamp(0.0, 1.0);
+                }
+                if coverage <= 0.0 {
+                    continue;
+                }
+                let u = ((x0 + 0.5 - dest.x) * sx + ox).clamp(u_range.0, u_range.1);
+                let mut texel = bitmap.sample(u, v, style.sampling);
+                if let Some(tint) = style.tint {
+                    texel = Premul::from_color(tint, texel.a);
+                }
+                let idx = row + px as usize * 4;
+                blend_premul(&mut band[idx..idx + 4], texel.scale(coverage), BlendMode::Normal);
+            }
+        }
+    });
+}
+
+// ============================================================================
+// NINE-SLICE
+// ============================================================================
+
+/// Imagem fatiada em nove partes para painéis e botões com skin: os cantos ficam intactos,
+/// as bordas esticam num só eixo e o centro nos dois
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NineSlice {
+    pub image: ImageHandle,
+    /// Margens dos cantos (top, right, bottom, left) em pixels da imagem
+    pub insets: [f32; 4],
+    /// Escala das margens na tela (ex.: 0.5 para uma skin desenhada em 2x)
+    pub scale: f32,
+    pub tint: Option<Color>,
+}
+
+impl NineSlice {
+    /// Mesma margem nos quatro lados
+    pub fn new(image: ImageHandle, inset: f32) -> Self {
+        Self {
+            image,
+            insets: [inset; 4],
+            scale: 1.0,
+            tint: None,
+        }
+    }
+
+    pub fn insets(mut self, top: f32, right: f32, bottom: f32, left: f32) -> Self {
+        self.insets = [top, right, bottom, left];
+        self
+    }
+
+    pub fn scale(mut self, scale: f32) -> Self {
+        self.scale = scale.max(0.0);
+        self
+    }
+
+    pub fn tint(mut self, color: Color) -> Self {
+        self.tint = Some(color);
+        self
+    }
+}
+
+/// Desenha `image` fatiada conforme `slice` cobrindo `rect`. Se o retângulo for menor que
+/// a soma das margens, os cantos encolhem proporcionalmente
+pub fn draw_nine_slice(f: &mut [u8], image: &Image, slice: &NineSlice, rect: Rect, sw: u32, sh: u32) {
+    if rect.w <= 0.0 || rect.h <= 0.0 || image.width == 0 || image.height == 0 {
+        return;
+    }
+    let (iw, ih) = (image.width as f32, image.height as f32);
+    let [top, right, bottom, left] = slice.insets.map(|v| v.max(0.0));
+    // Margens maiores que a imagem são reduzidas na mesma proporção
+    let fit = |a: f32, b: f32, len: f32| if a + b > len { len / (a + b) } else { 1.0 };
+    let (kx, ky) = (fit(left, right, iw), fit(top, bottom, ih));
+    let (left, right, top, bottom) = (left * kx, right * kx, top * ky, bottom * ky);
+
+    let fit_dest = |a: f32, b: f32, len: f32| {
+        let (a, b) = (a * slice.scale, b * slice.scale);
+        let k = fit(a, b, len);
+        (a * k, b * k)
+    };
+    let (dl, dr) = fit_dest(left, right, rect.w);
+    let (dt, db) = fit_dest(top, bottom, rect.h);
+
+    // Divisões internas alinhadas ao pixel: peças vizinhas não deixam costura semitransparente
+    let split = |start: f32, end: f32, a: f32, b: f32| {
+        let first = (start + a).round().clamp(start, end);
+        let second = (end - b).round().clamp(first, end);
+        [start, first, second, end]
+    };
+    let dest_x = split(rect.x, rect.x + rect.w, dl, dr);
+    let dest_y = split(rect.y, rect.y + rect.h, dt, db);
+    let src_x = [0.0, left, iw - right, iw];
+    let src_y = [0.0, top, ih - bottom, ih];
+
+    let style = ImageStyle {
+        fit: ImageFit::Fill,
+        sampling: Sampling::Bilinear,
+        tint: slice.tint,
+    };
+    for row in 0..3 {
+        for col in 0..3 {
+            let source = Rect {
+                x: src_x[col],
+                y: src_y[row],
+                w: src_x[col + 1] - src_x[col],
+                h: src_y[row + 1] - src_y[row],
+            };
+            let dest = Rect {
+                x: dest_x[col],
+                y: dest_y[row],
+                w: dest_x[col + 1] - dest_x[col],
+                h: dest_y[row + 1] - dest_y[row],
+            };
+            draw_image_region(f, image, &ImageRegion::new(source, dest), &style, sw, sh);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_images_without_pixels() {
+        for (w, h) in [(0, 4), (4, 0), (0, 0)] {
+            assert!(Image::from_rgba(w, h, Vec::new()).is_err(), "{w}x{h}");
+            assert!(Image::from_premultiplied(w, h, Vec::new()).is_err(), "{w}x{h}");
+        }
+        assert!(Image::from_rgba(2, 2, vec![0; 12]).is_err());
+
+        let image = Image::from_rgba(3, 1, vec![255; 12]).expect("imagem 3x1");
+        assert_eq!((image.width(), image.height()), (3, 1));
+    }
+
+    #[test]
+    fn nine_slices_keep_corners_and_stretch_the_rest() {
+        // Imagem 6x6 com margens de 2px: cada uma das nove partes tem uma cor
+        let (edge, center) = ([90, 90, 90], [255, 255, 255]);
+        let (red, green, blue, yellow) = ([255, 0, 0], [0, 255, 0], [0, 0, 255], [255, 255, 0]);
+        let colors: [[u8; 3]; 9] = [red, edge, green, edge, center, edge, blue, edge, yellow];
+        let mut rgba = Vec::new();
+        for y in 0..6 {
+            for x in 0..6 {
+                let [r, g, b] = colors[(y / 2) * 3 + x / 2];
+                rgba.extend_from_slice(&[r, g, b, 255]);
+            }
+        }
+        let image = Image::from_rgba(6, 6, rgba).expect("imagem 6x6");
+        let slice = NineSlice::new(ImageHandle(0), 2.0);
+
+        let (sw, sh) = (50u32, 40u32);
+        let mut frame = vec![0u8; (sw * sh * 4) as usize];
+        let rect = Rect { x: 5.0, y: 5.0, w: 40.0, h: 30.0 };
+        draw_nine_slice(&mut frame, &image, &slice, rect, sw, sh);
+        let at = |x: u32, y: u32| {
+            let i = ((y * sw + x) * 4) as usize;
+            [frame[i], frame[i + 1], frame[i + 2]]
+        };
+
+        // Cantos com o tamanho original, pixel a pixel
+        for (x0, y0, color) in [(5, 5, colors[0]), (43, 5, colors[2]), (5, 33, colors[6]), (43, 33, colors[8])] {
+            for (dx, dy) in [(0, 0), (1, 0), (0, 1), (1, 1)] {
+                assert_eq!(at(x0 + dx, y0 + dy), color, "canto em ({x0}, {y0})");
+            }
+        }
+        // Bordas esticam num eixo só, o centro nos dois; nada fora do retângulo
+        assert_eq!(at(25, 5), colors[1]);
+        assert_eq!(at(5, 20), colors[3]);
+        assert_eq!(at(25, 20), colors[4]);
+        assert_eq!(frame[((4 * sw + 4) * 4 + 3) as usize], 0);
+    }
+}
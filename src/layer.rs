@@ -0,0 +1,189 @@
+//! Camadas offscreen: o conteúdo é desenhado num buffer transparente (pré-multiplicado) do tamanho do frame
+//! e composto uma única vez (opacidade de grupo, modos de mistura, subárvores em cache).
+//! Só a área que o conteúdo registrou como pintada (`Ui::mark_drawn`), mais o layout, é composta.
+//! A API fica em `with_opacity`, `with_layer` e `cached_layer` (widgets).
+
+use crate::config::components;
+use crate::layout::Rect;
+use std::collections::{HashMap, HashSet};
+
+/// Subárvore já renderizada, reaproveitada enquanto versão, posição e tamanho baterem
+pub struct CachedLayer {
+    pub version: u64,
+    pub origin: (f32, f32),
+    pub size: (u32, u32),
+    pub pixels: Vec<u8>,
+    /// Área consumida no layout pelo conteúdo
+    pub rect: Rect,
+    /// Área do frame composta (ver `paint_bounds`)
+    pub bounds: Rect,
+    /// Quantos IDs de widget o conteúdo consumiu (mantém os IDs seguintes estáveis)
+    pub widget_ids: u64,
+}
+
+#[derive(Default)]
+pub struct LayerStore {
+    /// Buffers livres, reaproveitados entre frames para evitar alocar a cada camada
+    pool: Vec<Vec<u8>>,
+    cache: HashMap<u64, CachedLayer>,
+    /// IDs do cache usados no frame atual; os demais são descartados em `begin_frame`
+    used: HashSet<u64>,
+    /// Área pintada pelos widgets desde o último `begin_measure`
+    drawn: Option<Rect>,
+}
+
+impl LayerStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Buffer RGBA transparente com `len` bytes
+    pub fn take(&mut self, len: usize) -> Vec<u8> {
+        let mut buffer = self.pool.pop().unwrap_or_default();
+        buffer.clear();
+        buffer.resize(len, 0);
+        buffer
+    }
+
+    pub fn give_back(&mut self, buffer: Vec<u8>) {
+        self.pool.push(buffer);
+    }
+
+    /// Entrada do cache ainda válida para esta versão, posição e tamanho de frame
+    pub fn cached(&mut self, id: u64, version: u64, origin: (f32, f32), size: (u32, u32)) -> Option<&CachedLayer> {
+        self.used.insert(id);
+        self.cache
+            .get(&id)
+            .filter(|c| c.version == version && c.origin == origin && c.size == size)
+    }
+
+    pub fn insert(&mut self, id: u64, layer: CachedLayer) {
+        self.used.insert(id);
+        if let Some(old) = self.cache.insert(id, layer) {
+            self.give_back(old.pixels);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Descarta as camadas em cache que não apareceram no último frame
+    pub fn begin_frame(&mut self) {
+        let used = std::mem::take(&mut self.used);
+        self.cache.retain(|id, _| used.contains(id));
+        self.drawn = None;
+    }
+
+    pub fn mark_drawn(&mut self, rect: Rect) {
+        self.drawn = Some(self.drawn.map_or(rect, |drawn| drawn.union(&rect)));
+    }
+
+    /// Começa a medir o que uma subárvore pinta; guarde o retorno para `end_measure`
+    pub fn begin_measure(&mut self) -> Option<Rect> {
+        self.drawn.take()
+    }
+
+    /// Área pintada desde `begin_measure`; a medição de fora volta a ser `outer`
+    /// (quem compõe a subárvore registra no pai a área onde ela foi parar)
+    pub fn end_measure(&mut self, outer: Option<Rect>) -> Option<Rect> {
+        std::mem::replace(&mut self.drawn, outer)
+    }
+}
+
+/// Área do frame que o conteúdo pode ter pintado: o layout mais o que foi registrado em
+/// `drawn`, com uma folga para sombras e anéis de foco, em pixels inteiros e limitada ao frame
+pub fn paint_bounds(layout: Rect, drawn: Option<Rect>, sw: u32, sh: u32) -> Rect {
+    let rect = match drawn {
+        Some(drawn) if layout.w > 0.0 && layout.h > 0.0 => layout.union(&drawn),
+        Some(drawn) => drawn,
+        None => layout,
+    };
+    let margin = components::LAYER_PAINT_MARGIN;
+    let x0 = (rect.x - margin).floor().clamp(0.0, sw as f32);
+    let y0 = (rect.y - margin).floor().clamp(0.0, sh as f32);
+    let x1 = (rect.x + rect.w + margin).ceil().clamp(x0, sw as f32);
+    let y1 = (rect.y + rect.h + margin).ceil().clamp(y0, sh as f32);
+    Rect {
+        x: x0,
+        y: y0,
+        w: x1 - x0,
+        h: y1 - y0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layer(version: u64) -> CachedLayer {
+        CachedLayer {
+            version,
+            origin: (0.0, 0.0),
+            size: (4, 4),
+            pixels: vec![0; 64],
+            rect: Rect::default(),
+            bounds: Rect::default(),
+            widget_ids: 0,
+        }
+    }
+
+    #[test]
+    fn evicts_layers_not_used_in_the_last_frame() {
+        let mut store = LayerStore::new();
+        store.insert(1, layer(0));
+        store.insert(2, layer(0));
+        store.begin_frame();
+        assert_eq!(store.len(), 2);
+
+        // Só a 1 aparece neste frame
+        assert!(store.cached(1, 0, (0.0, 0.0), (4, 4)).is_some());
+        store.begin_frame();
+        assert_eq!(store.len(), 1);
+        assert!(store.cached(2, 0, (0.0, 0.0), (4, 4)).is_none());
+
+        store.begin_frame();
+        store.begin_frame();
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn paint_bounds_stay_inside_the_frame() {
+        let rect = Rect { x: 10.0, y: 100.0, w: 50.5, h: 20.0 };
+        let bounds = paint_bounds(rect, None, 200, 130);
+        assert_eq!(bounds, Rect { x: 0.0, y: 68.0, w: 93.0, h: 62.0 });
+
+        let outside = paint_bounds(Rect { x: 500.0, y: 500.0, w: 10.0, h: 10.0 }, None, 200, 130);
+        assert_eq!((outside.w, outside.h), (0.0, 0.0));
+    }
+
+    #[test]
+    fn paint_bounds_include_widgets_outside_the_layout() {
+        // Layout vazio (widgets que não avançam o cursor): vale só o que foi pintado
+        let drawn = Rect { x: 100.0, y: 100.0, w: 20.0, h: 10.0 };
+        let bounds = paint_bounds(Rect { x: 0.0, y: 0.0, w: 0.0, h: 0.0 }, Some(drawn), 400, 400);
+        assert_eq!(bounds, Rect { x: 68.0, y: 68.0, w: 84.0, h: 74.0 });
+
+        let layout = Rect { x: 100.0, y: 50.0, w: 10.0, h: 10.0 };
+        let bounds = paint_bounds(layout, Some(drawn), 400, 400);
+        assert_eq!(bounds, Rect { x: 68.0, y: 18.0, w: 84.0, h: 124.0 });
+    }
+
+    #[test]
+    fn nested_measures_report_only_their_own_area() {
+        let mut store = LayerStore::new();
+        store.mark_drawn(Rect { x: 0.0, y: 0.0, w: 10.0, h: 10.0 });
+        let outer = store.begin_measure();
+        store.mark_drawn(Rect { x: 50.0, y: 50.0, w: 10.0, h: 10.0 });
+        store.mark_drawn(Rect { x: 70.0, y: 40.0, w: 5.0, h: 5.0 });
+        let inner = store.end_measure(outer);
+        assert_eq!(inner, Some(Rect { x: 50.0, y: 40.0, w: 25.0, h: 20.0 }));
+
+        store.mark_drawn(Rect { x: 20.0, y: 0.0, w: 10.0, h: 10.0 });
+        assert_eq!(store.end_measure(None), Some(Rect { x: 0.0, y: 0.0, w: 30.0, h: 10.0 }));
+    }
+}
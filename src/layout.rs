@@ -1,4 +1,4 @@
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Rect {
     pub x: f32,
     pub y: f32,
@@ -10,6 +10,30 @@ impl Rect {
     pub fn contains(&self, p: (f32, f32)) -> bool {
         p.0 >= self.x && p.0 <= self.x + self.w && p.1 >= self.y && p.1 <= self.y + self.h
     }
+
+    /// Área comum aos dois retângulos (vazia se não se tocam)
+    pub fn intersect(&self, other: &Rect) -> Rect {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        Rect {
+            x,
+            y,
+            w: ((self.x + self.w).min(other.x + other.w) - x).max(0.0),
+            h: ((self.y + self.h).min(other.y + other.h) - y).max(0.0),
+        }
+    }
+
+    /// Menor retângulo que contém os dois
+    pub fn union(&self, other: &Rect) -> Rect {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Rect {
+            x,
+            y,
+            w: (self.x + self.w).max(other.x + other.w) - x,
+            h: (self.y + self.h).max(other.y + other.h) - y,
+        }
+    }
 }
 
 pub enum LayoutType {
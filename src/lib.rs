@@ -1,18 +1,48 @@
+pub mod animation;
+pub mod commands;
 pub mod config;
 pub mod core;
+pub mod drag;
+pub mod headless;
+pub mod icon;
+pub mod image;
+pub mod layer;
 pub mod layout;
 pub mod modifier;
+pub mod paint;
+pub mod parallel;
+pub mod path;
+pub mod recording;
 pub mod renderer;
+pub mod shadow;
+pub mod svg;
+pub mod transform;
 pub mod ui_context;
 pub mod widgets;
 
 // Core exports
-pub use config::{get_system_font, get_font_with_fallback, Theme, ThemeColors};
-pub use core::{App, run, DebugInfo, InputState};
+pub use animation::{
+    Animatable, AnimationSpec, Easing, Edge, Keyframes, Sequence, Spring, Timeline, Transition,
+    Tween,
+};
+pub use commands::{CommandRegistry, CommandScope, KeyBinding, KeyChord};
+pub use config::{get_system_font, get_font_with_fallback, ClickConfig, Theme, ThemeColors};
+pub use core::{App, run, run_with_options, RunOptions, DebugInfo, FrameRunner, ImePreedit, InputState, PointerButton};
+pub use winit::window::CursorIcon;
+pub use drag::{DragResponse, DropResponse};
+pub use headless::Headless;
+pub use icon::{Icon, draw_icon};
+pub use image::{Image, ImageFit, ImageHandle, ImageRegion, ImageSource, ImageStyle, NineSlice, Sampling};
 pub use layout::Rect;
 pub use modifier::Modifier;
-pub use renderer::Color;
-pub use ui_context::{Ui, AnimatedValue, ScrollState};
+pub use paint::{Gradient, GradientKind, Paint};
+pub use path::{FillRule, LineCap, LineJoin, Path, StrokeStyle, fill_path, stroke_path};
+pub use recording::InputRecording;
+pub use renderer::{BlendMode, Border, BorderStyle, Color, CornerRadii};
+pub use shadow::BoxShadow;
+pub use svg::{Svg, SvgAtlas, SvgHandle, SvgSource};
+pub use transform::Transform;
+pub use ui_context::{Ui, AnimatedValue, InteractionAnimation, Response, ScrollState};
 pub use widgets::*;
 
 // Zen shortcuts
@@ -20,7 +50,7 @@ pub fn pad(v: f32) -> Modifier {
     Modifier::DEFAULT.p(v)
 }
 
-pub fn bg(c: Color) -> Modifier {
+pub fn bg(c: impl Into<Paint>) -> Modifier {
     Modifier::DEFAULT.b(c)
 }
 
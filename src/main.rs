@@ -3,10 +3,10 @@ use rustui::config::{Theme, spacing};
 use rustui::core::{App, InputState, StateStore, run};
 use rustui::renderer::{FontAtlas, clear};
 use rustui::widgets::{
-    button, card, column, divider, row, scroll_view, sidebar_item, spacer, stat_card, text,
-    text_heading, text_input, text_muted,
+    animated_visibility, button, card, column, divider, row, scroll_view, sidebar_item_with, spacer,
+    stat_card_with, text, text_heading, text_input, text_muted,
 };
-use rustui::{bg, pad, sz, Ui};
+use rustui::{bg, pad, sz, Icon, Modifier, Transition, Ui};
 use num_format::{Locale, ToFormattedString};
 use std::cell::RefCell;
 
@@ -33,7 +33,7 @@ impl App for MyApp {
         let mut ui = Ui::new(frame, w, h, font, atlas, state, input);
 
         // Toggle de tema com F2
-        if input.key_just_pressed(winit::event::VirtualKeyCode::F2) {
+        if ui.command("theme.toggle", "Alterna entre tema claro e escuro", "F2") {
             let mut store = ui.state.borrow_mut();
             store.theme = if store.theme.name == "Dark" {
                 Theme::light()
@@ -63,15 +63,15 @@ impl App for MyApp {
             let spacer_rect = spacer(ui, 20.0);
             ui.cursor.y = spacer_rect.y + spacer_rect.h; // Advance cursor
 
-            let (clicked_dashboard, dashboard_rect) = sidebar_item(ui, "📊 Dashboard", tab_val == 0);
+            let (clicked_dashboard, dashboard_rect) = sidebar_item_with(ui, Modifier::new().icon(Icon::Home), "Dashboard", tab_val == 0);
             if clicked_dashboard { active_tab.set(0); }
             ui.cursor.y = dashboard_rect.y + dashboard_rect.h; // Advance cursor
 
-            let (clicked_analytics, analytics_rect) = sidebar_item(ui, "📈 Analytics", tab_val == 1);
+            let (clicked_analytics, analytics_rect) = sidebar_item_with(ui, Modifier::new().icon(Icon::Chart), "Analytics", tab_val == 1);
             if clicked_analytics { active_tab.set(1); }
             ui.cursor.y = analytics_rect.y + analytics_rect.h; // Advance cursor
 
-            let (clicked_settings, settings_rect) = sidebar_item(ui, "⚙️ Settings", tab_val == 2);
+            let (clicked_settings, settings_rect) = sidebar_item_with(ui, Modifier::new().icon(Icon::Settings), "Settings", tab_val == 2);
             if clicked_settings { active_tab.set(2); }
             ui.cursor.y = settings_rect.y + settings_rect.h; // Advance cursor
 
@@ -86,12 +86,12 @@ impl App for MyApp {
             ui.cursor.y = spacer_rect_3.y + spacer_rect_3.h; // Advance cursor
 
             // Botão com animação
-            let (clicked_boost, boost_btn_rect) = button(ui, bg(self.theme.colors.primary).s(210.0, 45.0), "🚀 Boost Sales");
+            let (clicked_boost, boost_btn_rect) = button(ui, bg(self.theme.colors.primary).s(210.0, 45.0).icon(Icon::TrendingUp), "Boost Sales");
             if clicked_boost { revenue.set(revenue_val + 1500); }
             ui.cursor.y = boost_btn_rect.y + boost_btn_rect.h; // Advance cursor
 
             // Botão para adicionar usuários
-            let (clicked_add_users, add_users_btn_rect) = button(ui, bg(self.theme.colors.success).s(210.0, 45.0), "+ Add Users");
+            let (clicked_add_users, add_users_btn_rect) = button(ui, bg(self.theme.colors.success).s(210.0, 45.0).icon(Icon::Plus), "Add Users");
             if clicked_add_users { users.set(users.get() + 100); }
             ui.cursor.y = add_users_btn_rect.y + add_users_btn_rect.h; // Advance cursor
         });
@@ -126,15 +126,15 @@ impl App for MyApp {
                 let original_cursor_y_for_row = ui.cursor.y;
 
                 let rev_str = format!("$ {}K", (revenue_val / 1000).to_formatted_string(&Locale::en));
-                let stat_card1_rect = stat_card(ui, "TOTAL REVENUE", &rev_str, self.theme.colors.success);
+                let stat_card1_rect = stat_card_with(ui, Modifier::new().icon(Icon::Chart), "TOTAL REVENUE", &rev_str, self.theme.colors.success);
                 ui.cursor.x = original_cursor_x_for_row + stat_card1_rect.w + spacing::LG;
                 ui.cursor.y = original_cursor_y_for_row; // Reset Y for next card in "row"
 
-                let stat_card2_rect = stat_card(ui, "ACTIVE USERS", &users.get().to_formatted_string(&Locale::en), self.theme.colors.primary);
+                let stat_card2_rect = stat_card_with(ui, Modifier::new().icon(Icon::Users), "ACTIVE USERS", &users.get().to_formatted_string(&Locale::en), self.theme.colors.primary);
                 ui.cursor.x = original_cursor_x_for_row + stat_card1_rect.w + spacing::LG + stat_card2_rect.w + spacing::LG;
                 ui.cursor.y = original_cursor_y_for_row; // Reset Y for next card in "row"
 
-                let stat_card3_rect = stat_card(ui, "CONVERSION", "12.5%", self.theme.colors.error);
+                let stat_card3_rect = stat_card_with(ui, Modifier::new().icon(Icon::TrendingUp), "CONVERSION", "12.5%", self.theme.colors.error);
                 
                 // After the "row" of stat cards, advance Y by max height of the cards, and reset X
                 let max_stat_card_height = stat_card1_rect.h.max(stat_card2_rect.h).max(stat_card3_rect.h);
@@ -177,8 +177,8 @@ impl App for MyApp {
                 let spacer_rect_5 = spacer(ui, 40.0);
                 ui.cursor.y = spacer_rect_5.y + spacer_rect_5.h; // Advance cursor
 
-                // Settings section
-                if tab_val == 2 {
+                // Settings section (entra/sai com fade + expand)
+                let settings_rect = animated_visibility(ui, tab_val == 2, Transition::fade().and(Transition::expand()), |ui| {
                     let heading_rect_3 = text_heading(ui, "⚙️ CONFIGURAÇÕES");
                     ui.cursor.y = heading_rect_3.y + heading_rect_3.h; // Advance cursor
                     let spacer_rect_6 = spacer(ui, 20.0);
@@ -209,7 +209,8 @@ impl App for MyApp {
                         ui_card_content.cursor.y = text_muted_rect_in_card.y + text_muted_rect_in_card.h; // Advance cursor
                     });
                     ui.cursor.y = card_rect_3.y + card_rect_3.h; // Advance cursor after card
-                }
+                });
+                ui.cursor.y = settings_rect.y + settings_rect.h; // Advance cursor
 
                 let spacer_rect_8 = spacer(ui, 100.0); // Espaço extra para scroll
                 ui.cursor.y = spacer_rect_8.y + spacer_rect_8.h; // Advance cursor
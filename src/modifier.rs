@@ -1,19 +1,34 @@
-use crate::renderer::Color;
+use crate::icon::Icon;
+use crate::image::NineSlice;
+use crate::paint::Paint;
+use crate::renderer::{Border, Color, CornerRadii};
 
 #[derive(Clone, Copy, Debug)]
 pub struct Modifier {
     pub padding: f32,
-    pub background: Option<Color>,
+    /// Cor sólida ou gradiente
+    pub background: Option<Paint>,
+    /// Skin nine-slice desenhada sobre o fundo
+    pub nine_slice: Option<NineSlice>,
     pub width: Option<f32>,
     pub height: Option<f32>,
+    pub border: Option<Border>,
+    /// Ícone antes do texto (`button`, `sidebar_item_with`, `stat_card_with`)
+    pub icon: Option<Icon>,
+    /// Raios dos cantos; `None` usa o padrão de cada widget
+    pub radius: Option<CornerRadii>,
 }
 
 impl Modifier {
     pub const DEFAULT: Modifier = Modifier {
         padding: 0.0,
         background: None,
+        nine_slice: None,
         width: None,
         height: None,
+        border: None,
+        icon: None,
+        radius: None,
     };
     pub fn new() -> Self {
         Self::DEFAULT
@@ -23,7 +38,7 @@ impl Modifier {
     pub fn p(self, v: f32) -> Self {
         self.padding(v)
     }
-    pub fn b(self, c: Color) -> Self {
+    pub fn b(self, c: impl Into<Paint>) -> Self {
         self.background(c)
     }
     pub fn s(self, w: f32, h: f32) -> Self {
@@ -34,8 +49,13 @@ impl Modifier {
         self.padding = val;
         self
     }
-    pub fn background(mut self, color: Color) -> Self {
-        self.background = Some(color);
+    pub fn background(mut self, paint: impl Into<Paint>) -> Self {
+        self.background = Some(paint.into());
+        self
+    }
+    /// Fundo com imagem nine-slice (`NineSlice::new(ui.load_image("painel.png")?, 12.0)`)
+    pub fn nine_slice(mut self, slice: NineSlice) -> Self {
+        self.nine_slice = Some(slice);
         self
     }
     pub fn size(mut self, w: f32, h: f32) -> Self {
@@ -43,4 +63,25 @@ impl Modifier {
         self.height = Some(h);
         self
     }
+    pub fn border(mut self, width: f32, color: Color) -> Self {
+        self.border = Some(Border::new(width, color));
+        self
+    }
+    /// Borda completa (larguras por lado, tracejada...)
+    pub fn border_with(mut self, border: Border) -> Self {
+        self.border = Some(border);
+        self
+    }
+    pub fn icon(mut self, icon: Icon) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+    pub fn radius(mut self, r: f32) -> Self {
+        self.radius = Some(CornerRadii::all(r));
+        self
+    }
+    pub fn radii(mut self, top_left: f32, top_right: f32, bottom_right: f32, bottom_left: f32) -> Self {
+        self.radius = Some(CornerRadii::new(top_left, top_right, bottom_right, bottom_left));
+        self
+    }
 }
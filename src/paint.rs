@@ -0,0 +1,291 @@
+//! Tintas: cor sólida ou gradiente (linear, radial, cônico) com várias paradas.
+//! Qualquer primitiva que aceita `impl Into<Paint>` aceita tanto `Color` quanto `Gradient`;
+//! a geometria do gradiente é relativa à caixa da forma pintada, como no CSS.
+
+use crate::layout::Rect;
+use crate::renderer::Color;
+use glam::Vec2;
+use std::f32::consts::TAU;
+
+/// Máximo de paradas por gradiente (mantém `Paint` e `Modifier` `Copy`)
+pub const MAX_GRADIENT_STOPS: usize = 8;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GradientKind {
+    /// Direção em radianos: 0 = esquerda → direita, positivo gira no sentido horário
+    Linear { angle: f32 },
+    /// Centro como fração da caixa; raio como fração da distância ao canto mais distante
+    Radial { center: (f32, f32), radius: f32 },
+    /// Centro como fração da caixa; a volta começa em `angle` (0 = direita) no sentido horário
+    Conic { center: (f32, f32), angle: f32 },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Gradient {
+    pub kind: GradientKind,
+    stops: [(f32, Color); MAX_GRADIENT_STOPS],
+    count: usize,
+}
+
+impl Gradient {
+    fn with_kind(kind: GradientKind) -> Self {
+        Self {
+            kind,
+            stops: [(0.0, Color::TRANSPARENT); MAX_GRADIENT_STOPS],
+            count: 0,
+        }
+    }
+
+    pub fn linear(angle: f32) -> Self {
+        Self::with_kind(GradientKind::Linear { angle })
+    }
+
+    pub fn radial(cx: f32, cy: f32, radius: f32) -> Self {
+        Self::with_kind(GradientKind::Radial {
+            center: (cx, cy),
+            radius,
+        })
+    }
+
+    pub fn conic(cx: f32, cy: f32, angle: f32) -> Self {
+        Self::with_kind(GradientKind::Conic {
+            center: (cx, cy),
+            angle,
+        })
+    }
+
+    /// Atalho: gradiente linear de duas cores
+    pub fn between(angle: f32, from: Color, to: Color) -> Self {
+        Self::linear(angle).stop(0.0, from).stop(1.0, to)
+    }
+
+    /// Acrescenta uma parada em `offset` (0..1). Acima de `MAX_GRADIENT_STOPS` o gradiente
+    /// é reamostrado: sai a parada interna que a interpolação das vizinhas melhor reproduz
+    pub fn stop(mut self, offset: f32, color: Color) -> Self {
+        let mut stops = [(0.0, Color::TRANSPARENT); MAX_GRADIENT_STOPS + 1];
+        stops[..self.count].copy_from_slice(self.stops());
+        stops[self.count] = (offset.clamp(0.0, 1.0), color);
+        let mut stops = &mut stops[..self.count + 1];
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        if stops.len() > MAX_GRADIENT_STOPS {
+            let removed = (1..stops.len() - 1)
+                .min_by(|&a, &b| stop_error(stops, a).total_cmp(&stop_error(stops, b)))
+                .unwrap_or(stops.len() - 1);
+            stops.copy_within(removed + 1.., removed);
+            stops = &mut stops[..MAX_GRADIENT_STOPS];
+        }
+        self.count = stops.len();
+        self.stops[..self.count].copy_from_slice(stops);
+        self
+    }
+
+    pub fn stops(&self) -> &[(f32, Color)] {
+        &self.stops[..self.count]
+    }
+
+    /// Cor na posição `t` (0..1) ao longo das paradas
+    pub fn color_at(&self, t: f32) -> Color {
+        let stops = self.stops();
+        let Some(&(first_offset, first)) = stops.first() else {
+            return Color::TRANSPARENT;
+        };
+        if t <= first_offset {
+            return first;
+        }
+        for pair in stops.windows(2) {
+            let ((o0, c0), (o1, c1)) = (pair[0], pair[1]);
+            if t <= o1 {
+                let span = o1 - o0;
+                let local = if span > 0.0 { (t - o0) / span } else { 1.0 };
+                return Color::lerp(c0, c1, local);
+            }
+        }
+        stops[stops.len() - 1].1
+    }
+}
+
+/// Quanto a cor da parada `i` se afasta da interpolação entre as vizinhas
+fn stop_error(stops: &[(f32, Color)], i: usize) -> f32 {
+    let ((o0, c0), (o, c), (o1, c1)) = (stops[i - 1], stops[i], stops[i + 1]);
+    let span = o1 - o0;
+    let t = if span > 0.0 { (o - o0) / span } else { 0.5 };
+    let expected = Color::lerp(c0, c1, t);
+    [(c.r, expected.r), (c.g, expected.g), (c.b, expected.b), (c.a, expected.a)]
+        .iter()
+        .map(|&(a, b)| (a as f32 - b as f32).abs())
+        .sum()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Paint {
+    Solid(Color),
+    Gradient(Gradient),
+}
+
+impl Paint {
+    /// Aplica `f` a cada cor (a cor sólida ou cada parada), p.ex. para hover/press
+    pub fn map(self, f: impl Fn(Color) -> Color) -> Self {
+        match self {
+            Paint::Solid(c) => Paint::Solid(f(c)),
+            Paint::Gradient(mut g) => {
+                for stop in &mut g.stops[..g.count] {
+                    stop.1 = f(stop.1);
+                }
+                Paint::Gradient(g)
+            }
+        }
+    }
+
+    /// Prepara a tinta para pintar a forma cuja caixa é `bounds`
+    pub fn shader(&self, bounds: Rect) -> Shader {
+        match *self {
+            Paint::Solid(c) => Shader::Solid(c),
+            Paint::Gradient(g) => {
+                let at = |(fx, fy): (f32, f32)| Vec2::new(bounds.x + bounds.w * fx, bounds.y + bounds.h * fy);
+                let geometry = match g.kind {
+                    GradientKind::Linear { angle } => {
+                        // Como no CSS: a linha cobre a caixa de forma que os cantos caem em 0 e 1
+                        let dir = Vec2::new(angle.cos(), angle.sin());
+                        let half = (bounds.w * dir.x).abs() / 2.0 + (bounds.h * dir.y).abs() / 2.0;
+                        let start = at((0.5, 0.5)) - dir * half;
+                        Geometry::Linear {
+                            start,
+                            step: dir / (2.0 * half).max(f32::EPSILON),
+                        }
+                    }
+                    GradientKind::Radial { center, radius } => {
+                        let c = at(center);
+                        let corner = [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)]
+                            .map(|p| at(p).distance(c))
+                            .into_iter()
+                            .fold(0.0, f32::max);
+                        Geometry::Radial {
+                            center: c,
+                            inv_radius: 1.0 / (corner * radius).max(f32::EPSILON),
+                        }
+                    }
+                    GradientKind::Conic { center, angle } => Geometry::Conic {
+                        center: at(center),
+                        start: angle,
+                    },
+                };
+                Shader::Gradient(g, geometry)
+            }
+        }
+    }
+}
+
+impl Default for Paint {
+    fn default() -> Self {
+        Paint::Solid(Color::TRANSPARENT)
+    }
+}
+
+impl From<Color> for Paint {
+    fn from(c: Color) -> Self {
+        Paint::Solid(c)
+    }
+}
+
+impl From<Gradient> for Paint {
+    fn from(g: Gradient) -> Self {
+        Paint::Gradient(g)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum Geometry {
+    Linear { start: Vec2, step: Vec2 },
+    Radial { center: Vec2, inv_radius: f32 },
+    Conic { center: Vec2, start: f32 },
+}
+
+/// Tinta resolvida para uma caixa: devolve a cor de cada pixel
+#[derive(Clone, Copy, Debug)]
+pub enum Shader {
+    Solid(Color),
+    Gradient(Gradient, Geometry),
+}
+
+impl Shader {
+    /// Cor sólida, se houver (permite caminhos rápidos nas primitivas)
+    pub fn solid(&self) -> Option<Color> {
+        match self {
+            Shader::Solid(c) => Some(*c),
+            Shader::Gradient(..) => None,
+        }
+    }
+
+    /// Cor no centro do pixel `(x, y)`
+    pub fn at(&self, x: i32, y: i32) -> Color {
+        match self {
+            Shader::Solid(c) => *c,
+            Shader::Gradient(g, geometry) => {
+                let p = Vec2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let t = match *geometry {
+                    Geometry::Linear { start, step } => (p - start).dot(step),
+                    Geometry::Radial { center, inv_radius } => p.distance(center) * inv_radius,
+                    Geometry::Conic { center, start } => {
+                        let d = p - center;
+                        (d.y.atan2(d.x) - start).rem_euclid(TAU) / TAU
+                    }
+                };
+                g.color_at(t)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RED: Color = Color { r: 255, g: 0, b: 0, a: 255 };
+    const BLUE: Color = Color { r: 0, g: 0, b: 255, a: 255 };
+
+    #[test]
+    fn extra_stops_are_resampled_instead_of_dropped() {
+        // Rampa de cinza com 11 paradas colineares e um pico no meio
+        let mut gradient = Gradient::linear(0.0);
+        for i in 0..=10 {
+            let offset = i as f32 / 10.0;
+            let v = if i == 5 { 255 } else { i * 20 };
+            gradient = gradient.stop(offset, Color { r: v, g: v, b: v, a: 255 });
+        }
+
+        let stops = gradient.stops();
+        assert_eq!(stops.len(), MAX_GRADIENT_STOPS);
+        // As pontas e o pico sobrevivem; saem só paradas que a interpolação já reproduz
+        assert_eq!(stops[0], (0.0, Color { r: 0, g: 0, b: 0, a: 255 }));
+        assert_eq!(stops[stops.len() - 1], (1.0, Color { r: 200, g: 200, b: 200, a: 255 }));
+        assert_eq!(gradient.color_at(0.5).r, 255);
+        assert!((gradient.color_at(0.15).r as i32 - 30).abs() <= 1);
+    }
+
+    #[test]
+    fn shaders_follow_each_gradient_kind() {
+        let bounds = Rect { x: 0.0, y: 0.0, w: 100.0, h: 100.0 };
+
+        // Linear, esquerda → direita
+        let linear = Paint::from(Gradient::between(0.0, RED, BLUE)).shader(bounds);
+        assert!(linear.at(0, 50).r > 250);
+        assert!(linear.at(99, 50).b > 250);
+        assert_eq!(linear.at(50, 0), linear.at(50, 99));
+
+        // Radial: centro na primeira parada, cantos na última
+        let radial = Paint::from(Gradient::radial(0.5, 0.5, 1.0).stop(0.0, RED).stop(1.0, BLUE))
+            .shader(bounds);
+        assert!(radial.at(50, 50).r > 250);
+        assert!(radial.at(0, 0).b > 250);
+        assert_eq!(radial.at(20, 50), radial.at(79, 50));
+
+        // Cônico: começa à direita e gira no sentido horário
+        let conic = Paint::from(Gradient::conic(0.5, 0.5, 0.0).stop(0.0, RED).stop(1.0, BLUE))
+            .shader(bounds);
+        let (right, below, left) = (conic.at(99, 50), conic.at(50, 99), conic.at(0, 50));
+        assert!(right.r > 250);
+        assert!(below.b > 50 && below.b < 80);
+        assert!(left.b > 120 && left.b < 135);
+    }
+}
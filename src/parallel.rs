@@ -0,0 +1,294 @@
+//! Rasterização em faixas horizontais paralelas (pool de threads fixo, sem dependências).
+//! Buffers grandes são divididos em faixas de linhas e cada faixa é pintada numa thread do pool;
+//! abaixo de `render::PARALLEL_MIN_PIXELS` tudo roda na thread atual.
+//! A divisão acontece uma vez só: chamadas feitas de dentro de uma faixa (p.ex. as primitivas
+//! de uma display list) rodam em série na thread que pinta a faixa.
+
+use crate::config::render;
+use std::cell::Cell;
+use std::ops::Range;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::thread;
+
+thread_local! {
+    /// Verdadeiro enquanto a thread pinta uma faixa: divisões aninhadas rodam em série
+    static IN_BAND: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Threads disponíveis (limitadas por `render::MAX_RASTER_THREADS`)
+pub fn available_threads() -> usize {
+    static THREADS: OnceLock<usize> = OnceLock::new();
+    *THREADS.get_or_init(|| {
+        thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+            .clamp(1, render::MAX_RASTER_THREADS)
+    })
+}
+
+/// Threads para `work_pixels` pixels de trabalho distribuídos em `rows` linhas
+fn threads_for(work_pixels: usize, rows: usize) -> usize {
+    if work_pixels < render::PARALLEL_MIN_PIXELS || IN_BAND.with(Cell::get) {
+        1
+    } else {
+        available_threads().min(rows)
+    }
+}
+
+/// Divide `buffer` (linhas de `stride` bytes) em faixas e chama `paint(faixa, primeira_linha, linhas)`
+/// em paralelo quando o buffer é grande o bastante
+pub fn for_each_band(buffer: &mut [u8], stride: usize, paint: impl Fn(&mut [u8], u32, u32) + Sync) {
+    if stride == 0 || buffer.is_empty() {
+        return;
+    }
+    let rows = buffer.len() / stride;
+    split_bands(buffer, stride, threads_for(buffer.len() / 4, rows), 0, &paint);
+}
+
+/// Como `for_each_band`, só nas linhas `rows`; `work_pixels` (a área realmente pintada)
+/// decide se vale dividir entre threads
+pub fn for_each_band_in(
+    buffer: &mut [u8],
+    stride: usize,
+    rows: Range<u32>,
+    work_pixels: usize,
+    paint: impl Fn(&mut [u8], u32, u32) + Sync,
+) {
+    let start = rows.start as usize * stride;
+    let end = (rows.end as usize * stride).min(buffer.len());
+    if stride == 0 || start >= end {
+        return;
+    }
+    let threads = threads_for(work_pixels, (end - start) / stride);
+    split_bands(&mut buffer[start..end], stride, threads, rows.start, &paint);
+}
+
+fn split_bands(
+    buffer: &mut [u8],
+    stride: usize,
+    threads: usize,
+    first_row: u32,
+    paint: &(impl Fn(&mut [u8], u32, u32) + Sync),
+) {
+    let rows = buffer.len() / stride;
+    if threads <= 1 {
+        paint(buffer, first_row, rows as u32);
+        return;
+    }
+
+    let rows_per_band = rows.div_ceil(threads);
+    let mut bands = buffer.chunks_mut(rows_per_band * stride);
+    // A thread atual pinta a primeira faixa enquanto o pool pinta as outras
+    let first = bands.next();
+    let jobs = bands.enumerate().map(|(i, band)| {
+        let first = first_row + ((i + 1) * rows_per_band) as u32;
+        Box::new(move || paint(band, first, (band.len() / stride) as u32)) as Box<dyn FnOnce() + Send>
+    });
+    pool().scoped(jobs, || {
+        if let Some(band) = first {
+            paint(band, first_row, (band.len() / stride) as u32);
+        }
+    });
+}
+
+/// Roda `f` marcando a thread como dentro de uma faixa; o pânico volta como `Err`
+fn in_band(f: impl FnOnce()) -> thread::Result<()> {
+    let outer = IN_BAND.with(|flag| flag.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    IN_BAND.with(|flag| flag.set(outer));
+    result
+}
+
+// ============================================================================
+// POOL DE THREADS
+// ============================================================================
+
+type Job = Box<dyn FnOnce() + Send + 'static>;
+
+/// Threads de rasterização criadas uma vez e reaproveitadas por todos os frames
+struct Pool {
+    sender: Sender<Job>,
+}
+
+fn pool() -> &'static Pool {
+    static POOL: OnceLock<Pool> = OnceLock::new();
+    POOL.get_or_init(|| {
+        let (sender, receiver) = mpsc::channel::<Job>();
+        let receiver = Arc::new(Mutex::new(receiver));
+        // A thread que divide o trabalho pinta uma faixa: o pool tem uma thread a menos
+        for i in 1..available_threads() {
+            let receiver = Arc::clone(&receiver);
+            thread::Builder::new()
+                .name(format!("raster-{i}"))
+                .spawn(move || {
+                    loop {
+                        let job = receiver.lock().unwrap_or_else(|e| e.into_inner()).recv();
+                        match job {
+                            Ok(job) => job(),
+                            Err(_) => break,
+                        }
+                    }
+                })
+                .expect("falha ao criar thread de rasterização");
+        }
+        Pool { sender }
+    })
+}
+
+impl Pool {
+    /// Roda `jobs` no pool e `local` na thread atual, ambos marcados como dentro de uma faixa.
+    /// Só retorna (ou propaga pânico) depois que todos os jobs terminaram, por isso eles podem
+    /// emprestar dados da pilha de quem chama
+    fn scoped<'a>(&self, jobs: impl Iterator<Item = Box<dyn FnOnce() + Send + 'a>>, local: impl FnOnce()) {
+        let latch = Latch::new();
+        let local = {
+            // Espera os jobs mesmo se algo abaixo entrar em pânico
+            let _wait = WaitOnDrop(&latch);
+            for job in jobs {
+                latch.add();
+                let latch = &latch;
+                let job: Box<dyn FnOnce() + Send + '_> = Box::new(move || {
+                    let result = in_band(job);
+                    latch.count_down(result.is_err());
+                });
+                // SAFETY: `_wait` bloqueia até o `Latch` zerar antes de `latch` e os empréstimos
+                // de `jobs` saírem de escopo, inclusive durante um unwind
+                let job: Job = unsafe { std::mem::transmute::<Box<dyn FnOnce() + Send + '_>, Job>(job) };
+                // Sem threads para receber, o job roda aqui mesmo
+                if let Err(mpsc::SendError(job)) = self.sender.send(job) {
+                    job();
+                }
+            }
+            in_band(local)
+        };
+
+        if let Err(payload) = local {
+            panic::resume_unwind(payload);
+        }
+        assert!(!latch.wait(), "pânico ao pintar uma faixa numa thread do pool");
+    }
+}
+
+/// Bloqueia no `Latch` ao sair de escopo
+struct WaitOnDrop<'a>(&'a Latch);
+
+impl Drop for WaitOnDrop<'_> {
+    fn drop(&mut self) {
+        self.0.wait();
+    }
+}
+
+/// Contador de jobs pendentes; `wait` bloqueia até zerar e diz se algum entrou em pânico
+struct Latch {
+    state: Mutex<(usize, bool)>,
+    done: Condvar,
+}
+
+impl Latch {
+    fn new() -> Self {
+        Self {
+            state: Mutex::new((0, false)),
+            done: Condvar::new(),
+        }
+    }
+
+    fn add(&self) {
+        self.state.lock().unwrap_or_else(|e| e.into_inner()).0 += 1;
+    }
+
+    fn count_down(&self, panicked: bool) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        state.0 -= 1;
+        state.1 |= panicked;
+        if state.0 == 0 {
+            self.done.notify_all();
+        }
+    }
+
+    fn wait(&self) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        while state.0 > 0 {
+            state = self.done.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+        state.1
+    }
+}
+
+/// Preenche `pixels` (RGBA) com `px` copiando blocos cada vez maiores (memcpy largo)
+pub fn fill_pixels(pixels: &mut [u8], px: [u8; 4]) {
+    if pixels.len() < 4 {
+        return;
+    }
+    pixels[..4].copy_from_slice(&px);
+    let mut filled = 4;
+    while filled < pixels.len() {
+        let n = filled.min(pixels.len() - filled);
+        pixels.copy_within(..n, filled);
+        filled += n;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    #[test]
+    fn bands_cover_every_row_once() {
+        let (w, h) = (1024, 1024);
+        let mut buffer = vec![0u8; w * h * 4];
+        for_each_band(&mut buffer, w * 4, |band, first, rows| {
+            for (row, line) in (first..first + rows).zip(band.chunks_exact_mut(w * 4)) {
+                line[0] = line[0].wrapping_add(1);
+                line[1] = row as u8;
+            }
+        });
+        for (row, line) in buffer.chunks_exact(w * 4).enumerate() {
+            assert_eq!((line[0], line[1]), (1, row as u8));
+        }
+    }
+
+    #[test]
+    fn nested_splits_run_on_the_band_thread() {
+        let (w, h) = (1024, 1024);
+        let mut buffer = vec![0u8; w * h * 4];
+        for_each_band(&mut buffer, w * 4, |band, _, _| {
+            let outer = thread::current().id();
+            // Uma primitiva grande chamada de dentro da faixa não divide de novo
+            for_each_band(band, w * 4, |inner, _, _| {
+                assert_eq!(thread::current().id(), outer);
+                inner.fill(1);
+            });
+        });
+        assert!(buffer.iter().all(|b| *b == 1));
+    }
+
+    #[test]
+    fn worker_panics_propagate_after_every_band_finished() {
+        let (w, h) = (64, 64);
+        let mut buffer = vec![0u8; w * h * 4];
+        let finished = AtomicUsize::new(0);
+        // Quatro faixas de 16 linhas; a segunda roda no pool e entra em pânico
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            split_bands(&mut buffer, w * 4, 4, 0, &|band: &mut [u8], first, _| {
+                if first == 16 {
+                    panic!("faixa com defeito");
+                }
+                thread::sleep(Duration::from_millis(20));
+                band.fill(1);
+                finished.fetch_add(1, Ordering::SeqCst);
+            });
+        }));
+        assert!(result.is_err());
+
+        // Ao propagar, todas as outras faixas já tinham terminado de escrever no buffer
+        assert_eq!(finished.into_inner(), 3);
+        for (row, line) in buffer.chunks_exact(w * 4).enumerate() {
+            let expected = if (16..32).contains(&row) { 0 } else { 1 };
+            assert!(line.iter().all(|&b| b == expected), "linha {row}");
+        }
+    }
+}
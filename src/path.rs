@@ -0,0 +1,693 @@
+//! Paths vetoriais com preenchimento e contorno anti-aliased
+//! Curvas são achatadas em polilinhas na construção; o rasterizador amostra 16 sub-linhas por
+//! pixel com cobertura horizontal exata e suporta as regras non-zero e even-odd.
+
+use crate::layout::Rect;
+use crate::paint::{Paint, Shader};
+use crate::renderer::{CornerRadii, blend_pixel};
+use glam::{Affine2, Vec2};
+use std::f32::consts::PI;
+
+/// Erro máximo (px) ao achatar curvas em segmentos
+const FLATTEN_TOLERANCE: f32 = 0.2;
+/// Sub-linhas de amostragem vertical por pixel
+const SUBSAMPLES: usize = 16;
+
+// ============================================================================
+// PATH
+// ============================================================================
+
+#[derive(Clone, Debug, Default, PartialEq)]
+struct SubPath {
+    points: Vec<Vec2>,
+    closed: bool,
+}
+
+/// Sequência de sub-paths (move/line/quad/cubic/arc/close)
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Path {
+    subpaths: Vec<SubPath>,
+}
+
+impl Path {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn last_point(&self) -> Option<Vec2> {
+        self.subpaths.last().and_then(|s| s.points.last().copied())
+    }
+
+    fn push_point(&mut self, p: Vec2) {
+        match self.subpaths.last_mut() {
+            Some(sub) if !sub.closed => sub.points.push(p),
+            _ => self.subpaths.push(SubPath {
+                points: vec![p],
+                closed: false,
+            }),
+        }
+    }
+
+    pub fn move_to(mut self, x: f32, y: f32) -> Self {
+        self.subpaths.push(SubPath {
+            points: vec![Vec2::new(x, y)],
+            closed: false,
+        });
+        self
+    }
+
+    pub fn line_to(mut self, x: f32, y: f32) -> Self {
+        self.push_point(Vec2::new(x, y));
+        self
+    }
+
+    pub fn quad_to(mut self, cx: f32, cy: f32, x: f32, y: f32) -> Self {
+        let p0 = self.last_point().unwrap_or(Vec2::new(cx, cy));
+        let (p1, p2) = (Vec2::new(cx, cy), Vec2::new(x, y));
+        let dd = (p0 - 2.0 * p1 + p2).length();
+        let n = segments_for(dd);
+        for i in 1..=n {
+            let t = i as f32 / n as f32;
+            let mt = 1.0 - t;
+            self.push_point(p0 * mt * mt + p1 * 2.0 * mt * t + p2 * t * t);
+        }
+        self
+    }
+
+    pub fn cubic_to(mut self, c1x: f32, c1y: f32, c2x: f32, c2y: f32, x: f32, y: f32) -> Self {
+        let p0 = self.last_point().unwrap_or(Vec2::new(c1x, c1y));
+        let (p1, p2, p3) = (Vec2::new(c1x, c1y), Vec2::new(c2x, c2y), Vec2::new(x, y));
+        let dd = (p0 - 2.0 * p1 + p2).length().max((p1 - 2.0 * p2 + p3).length()) * 1.5;
+        let n = segments_for(dd);
+        for i in 1..=n {
+            let t = i as f32 / n as f32;
+            let mt = 1.0 - t;
+            self.push_point(
+                p0 * mt * mt * mt + p1 * 3.0 * mt * mt * t + p2 * 3.0 * mt * t * t + p3 * t * t * t,
+            );
+        }
+        self
+    }
+
+    /// Arco de círculo com centro `(cx, cy)`, começando em `start` e varrendo `sweep` radianos
+    /// (positivo = sentido horário na tela). Liga com uma reta a partir do ponto atual.
+    pub fn arc(mut self, cx: f32, cy: f32, r: f32, start: f32, sweep: f32) -> Self {
+        let r = r.abs();
+        let step = if r > FLATTEN_TOLERANCE {
+            2.0 * (1.0 - FLATTEN_TOLERANCE / r).acos()
+        } else {
+            PI / 2.0
+        };
+        let n = ((sweep.abs() / step).ceil() as usize).clamp(1, 256);
+        for i in 0..=n {
+            let a = start + sweep * i as f32 / n as f32;
+            self.push_point(Vec2::new(cx + r * a.cos(), cy + r * a.sin()));
+        }
+        self
+    }
+
+    pub fn close(mut self) -> Self {
+        if let Some(sub) = self.subpaths.last_mut() {
+            sub.closed = true;
+        }
+        self
+    }
+
+    // ------------------------------------------------------------------------
+    // FORMAS PRONTAS
+    // ------------------------------------------------------------------------
+
+    pub fn rect(x: f32, y: f32, w: f32, h: f32) -> Self {
+        Self::new()
+            .move_to(x, y)
+            .line_to(x + w, y)
+            .line_to(x + w, y + h)
+            .line_to(x, y + h)
+            .close()
+    }
+
+    pub fn circle(cx: f32, cy: f32, r: f32) -> Self {
+        Self::new()
+            .move_to(cx + r, cy)
+            .arc(cx, cy, r, 0.0, 2.0 * PI)
+            .close()
+    }
+
+    /// Retângulo com raios por canto (limitados ao tamanho)
+    pub fn rounded_rect(x: f32, y: f32, w: f32, h: f32, radii: impl Into<CornerRadii>) -> Self {
+        let r = radii.into().clamped(w, h);
+        Self::new()
+            .move_to(x + r.top_left, y)
+            .line_to(x + w - r.top_right, y)
+            .arc(x + w - r.top_right, y + r.top_right, r.top_right, -PI / 2.0, PI / 2.0)
+            .line_to(x + w, y + h - r.bottom_right)
+            .arc(x + w - r.bottom_right, y + h - r.bottom_right, r.bottom_right, 0.0, PI / 2.0)
+            .line_to(x + r.bottom_left, y + h)
+            .arc(x + r.bottom_left, y + h - r.bottom_left, r.bottom_left, PI / 2.0, PI / 2.0)
+            .line_to(x, y + r.top_left)
+            .arc(x + r.top_left, y + r.top_left, r.top_left, PI, PI / 2.0)
+            .close()
+    }
+
+    /// Acrescenta os sub-paths de `other`
+    pub fn extend(mut self, other: Path) -> Self {
+        self.subpaths.extend(other.subpaths);
+        self
+    }
+
+    pub fn transform(mut self, affine: &Affine2) -> Self {
+        for sub in &mut self.subpaths {
+            for p in &mut sub.points {
+                *p = affine.transform_point2(*p);
+            }
+        }
+        self
+    }
+
+    /// Bounding box (mínimo, máximo) dos pontos achatados
+    pub fn bounds(&self) -> Option<(Vec2, Vec2)> {
+        let mut points = self.subpaths.iter().flat_map(|s| s.points.iter());
+        let first = *points.next()?;
+        Some(points.fold((first, first), |(min, max), p| (min.min(*p), max.max(*p))))
+    }
+}
+
+fn segments_for(deviation: f32) -> usize {
+    ((deviation / FLATTEN_TOLERANCE).sqrt().ceil() as usize / 2).clamp(1, 128)
+}
+
+// ============================================================================
+// PREENCHIMENTO
+// ============================================================================
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FillRule {
+    #[default]
+    NonZero,
+    EvenOdd,
+}
+
+#[derive(Clone, Copy)]
+struct Edge {
+    x0: f32,
+    y0: f32,
+    x1: f32,
+    y1: f32,
+    dir: i32,
+}
+
+/// Rasteriza polígonos fechados chamando `paint(x, y, cobertura)` para cada pixel coberto
+pub fn rasterize(
+    polygons: &[Vec<Vec2>],
+    rule: FillRule,
+    sw: u32,
+    sh: u32,
+    mut paint: impl FnMut(u32, u32, f32),
+) {
+    let mut edges = Vec::new();
+    let (mut min, mut max) = (Vec2::splat(f32::INFINITY), Vec2::splat(f32::NEG_INFINITY));
+    for poly in polygons.iter().filter(|p| p.len() >= 2) {
+        for (i, &a) in poly.iter().enumerate() {
+            let b = poly[(i + 1) % poly.len()];
+            min = min.min(a);
+            max = max.max(a);
+            if a.y == b.y {
+                continue;
+            }
+            let (top, bottom, dir) = if a.y < b.y { (a, b, 1) } else { (b, a, -1) };
+            edges.push(Edge {
+                x0: top.x,
+                y0: top.y,
+                x1: bottom.x,
+                y1: bottom.y,
+                dir,
+            });
+        }
+    }
+    if edges.is_empty() {
+        return;
+    }
+
+    let x_start = (min.x.floor() as i32).max(0);
+    let x_end = (max.x.ceil() as i32 + 1).min(sw as i32);
+    let y_start = (min.y.floor() as i32).max(0);
+    let y_end = (max.y.ceil() as i32).min(sh as i32);
+    if x_start >= x_end || y_start >= y_end {
+        return;
+    }
+
+    let width = (x_end - x_start) as usize;
+    let mut coverage = vec![0.0f32; width + 1];
+    let mut interior = vec![0.0f32; width + 1];
+    let mut crossings: Vec<(f32, i32)> = Vec::new();
+    let sample_weight = 1.0 / SUBSAMPLES as f32;
+
+    // Lista de arestas ativas: só as que cruzam a linha de pixels atual são testadas
+    edges.sort_by(|a, b| a.y0.total_cmp(&b.y0));
+    let mut next_edge = 0;
+    let mut active: Vec<Edge> = Vec::new();
+
+    for py in y_start..y_end {
+        let (row_top, row_bottom) = (py as f32, py as f32 + 1.0);
+        active.retain(|e| e.y1 > row_top);
+        while next_edge < edges.len() && edges[next_edge].y0 < row_bottom {
+            if edges[next_edge].y1 > row_top {
+                active.push(edges[next_edge]);
+            }
+            next_edge += 1;
+        }
+        if active.is_empty() {
+            continue;
+        }
+        // Colunas tocadas nesta linha (limita a limpeza e a pintura)
+        let (mut lo, mut hi) = (width, 0);
+
+        for s in 0..SUBSAMPLES {
+            let sy = py as f32 + (s as f32 + 0.5) / SUBSAMPLES as f32;
+            crossings.clear();
+            for e in &active {
+                if sy >= e.y0 && sy < e.y1 {
+                    let t = (sy - e.y0) / (e.y1 - e.y0);
+                    crossings.push((e.x0 + (e.x1 - e.x0) * t, e.dir));
+                }
+            }
+            crossings.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+            let mut winding = 0;
+            for pair in crossings.windows(2) {
+                winding += pair[0].1;
+                let inside = match rule {
+                    FillRule::NonZero => winding != 0,
+                    FillRule::EvenOdd => winding % 2 != 0,
+                };
+                if !inside {
+                    continue;
+                }
+                // Span [xa, xb) em coordenadas relativas a x_start
+                let xa = (pair[0].0 - x_start as f32).clamp(0.0, width as f32);
+                let xb = (pair[1].0 - x_start as f32).clamp(0.0, width as f32);
+                if xb <= xa {
+                    continue;
+                }
+                let (ia, ib) = (xa.floor() as usize, xb.floor() as usize);
+                lo = lo.min(ia);
+                hi = hi.max(ib + 1);
+                if ia == ib {
+                    coverage[ia] += (xb - xa) * sample_weight;
+                } else {
+                    coverage[ia] += (ia as f32 + 1.0 - xa) * sample_weight;
+                    coverage[ib] += (xb - ib as f32) * sample_weight;
+                    // Pixels inteiros entre as pontas: diferença acumulada
+                    interior[ia + 1] += sample_weight;
+                    interior[ib] -= sample_weight;
+                }
+            }
+        }
+
+        let mut run = 0.0;
+        let hi = hi.min(width);
+        for (i, (c, d)) in (lo..).zip(coverage[lo..hi.max(lo)].iter().zip(&interior[lo..hi.max(lo)])) {
+            run += d;
+            let value = (c + run).min(1.0);
+            if value > 0.001 {
+                paint((x_start + i as i32) as u32, py as u32, value);
+            }
+        }
+        if lo < hi {
+            coverage[lo..=hi].fill(0.0);
+            interior[lo..=hi].fill(0.0);
+        }
+    }
+}
+
+fn polygons_of(path: &Path) -> Vec<Vec<Vec2>> {
+    path.subpaths
+        .iter()
+        .filter(|s| s.points.len() >= 3)
+        .map(|s| s.points.clone())
+        .collect()
+}
+
+/// Tinta resolvida sobre a caixa do path
+fn path_shader(path: &Path, paint: Paint) -> Shader {
+    let (min, max) = path.bounds().unwrap_or_default();
+    paint.shader(Rect {
+        x: min.x,
+        y: min.y,
+        w: max.x - min.x,
+        h: max.y - min.y,
+    })
+}
+
+pub fn fill_path(f: &mut [u8], path: &Path, rule: FillRule, paint: impl Into<Paint>, sw: u32, sh: u32) {
+    let shader = path_shader(path, paint.into());
+    rasterize(&polygons_of(path), rule, sw, sh, |x, y, coverage| {
+        let idx = ((y * sw + x) * 4) as usize;
+        blend_pixel(&mut f[idx..idx + 4], shader.at(x as i32, y as i32), coverage);
+    });
+}
+
+// ============================================================================
+// CONTORNO
+// ============================================================================
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum LineJoin {
+    #[default]
+    Miter,
+    Round,
+    Bevel,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum LineCap {
+    #[default]
+    Butt,
+    Round,
+    Square,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StrokeStyle {
+    pub width: f32,
+    pub join: LineJoin,
+    pub cap: LineCap,
+    /// Acima desta razão comprimento/largura a junção miter vira bevel
+    pub miter_limit: f32,
+    /// Comprimentos alternados traço/espaço (vazio = contínuo); ver `dash_pattern`
+    pub dash: Vec<f32>,
+    pub dash_offset: f32,
+}
+
+impl StrokeStyle {
+    pub fn new(width: f32) -> Self {
+        Self {
+            width,
+            join: LineJoin::Miter,
+            cap: LineCap::Butt,
+            miter_limit: 4.0,
+            dash: Vec::new(),
+            dash_offset: 0.0,
+        }
+    }
+
+    pub fn join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+
+    pub fn cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+
+    pub fn miter_limit(mut self, limit: f32) -> Self {
+        self.miter_limit = limit;
+        self
+    }
+
+    /// Padrão traço/espaço como `stroke-dasharray`: listas ímpares são repetidas
+    /// (`[5]` = `[5, 5]`) e entradas negativas ou não finitas desenham contínuo
+    pub fn dash(mut self, pattern: &[f32], offset: f32) -> Self {
+        self.dash = pattern.to_vec();
+        self.dash_offset = offset;
+        self
+    }
+}
+
+/// Polígonos (todos com a mesma orientação) cuja união non-zero é o contorno do path
+pub fn stroke_polygons(path: &Path, style: &StrokeStyle) -> Vec<Vec<Vec2>> {
+    let hw = style.width / 2.0;
+    let mut out = Vec::new();
+    if hw <= 0.0 {
+        return out;
+    }
+
+    for sub in &path.subpaths {
+        let mut points = sub.points.clone();
+        points.dedup_by(|a, b| a.distance_squared(*b) < 1e-8);
+        if sub.closed && points.len() > 2 && points[0].distance_squared(points[points.len() - 1]) < 1e-8 {
+            points.pop();
+        }
+
+        let pieces = match dash_pattern(&style.dash) {
+            Some(pattern) => {
+                let offset = if style.dash_offset.is_finite() { style.dash_offset } else { 0.0 };
+                dash_polyline(&points, sub.closed, &pattern, offset)
+            }
+            None => vec![(points, sub.closed)],
+        };
+        for (mut points, closed) in pieces {
+            // Traços de comprimento zero (pontilhado) viram um único ponto com cap
+            points.dedup_by(|a, b| a.distance_squared(*b) < 1e-8);
+            stroke_polyline(&points, closed, hw, style, &mut out);
+        }
+    }
+
+    for poly in &mut out {
+        if signed_area(poly) < 0.0 {
+            poly.reverse();
+        }
+    }
+    out
+}
+
+pub fn stroke_path(f: &mut [u8], path: &Path, style: &StrokeStyle, paint: impl Into<Paint>, sw: u32, sh: u32) {
+    let shader = path_shader(path, paint.into());
+    rasterize(&stroke_polygons(path, style), FillRule::NonZero, sw, sh, |x, y, coverage| {
+        let idx = ((y * sw + x) * 4) as usize;
+        blend_pixel(&mut f[idx..idx + 4], shader.at(x as i32, y as i32), coverage);
+    });
+}
+
+fn signed_area(poly: &[Vec2]) -> f32 {
+    poly.iter()
+        .zip(poly.iter().cycle().skip(1))
+        .map(|(a, b)| a.perp_dot(*b))
+        .sum::<f32>()
+        / 2.0
+}
+
+fn circle_polygon(c: Vec2, r: f32) -> Vec<Vec2> {
+    let n = ((2.0 * PI / (2.0 * (1.0 - FLATTEN_TOLERANCE / r.max(FLATTEN_TOLERANCE * 2.0)).acos())).ceil() as usize)
+        .clamp(8, 128);
+    (0..n)
+        .map(|i| {
+            let a = 2.0 * PI * i as f32 / n as f32;
+            c + Vec2::new(a.cos(), a.sin()) * r
+        })
+        .collect()
+}
+
+fn stroke_polyline(points: &[Vec2], closed: bool, hw: f32, style: &StrokeStyle, out: &mut Vec<Vec<Vec2>>) {
+    if points.is_empty() {
+        return;
+    }
+    if points.len() == 1 {
+        // Ponto isolado: só aparece com caps round/square
+        let p = points[0];
+        match style.cap {
+            LineCap::Round => out.push(circle_polygon(p, hw)),
+            LineCap::Square => out.push(vec![
+                p + Vec2::new(-hw, -hw),
+                p + Vec2::new(hw, -hw),
+                p + Vec2::new(hw, hw),
+                p + Vec2::new(-hw, hw),
+            ]),
+            LineCap::Butt => {}
+        }
+        return;
+    }
+
+    let count = points.len();
+    let segments = if closed { count } else { count - 1 };
+    for i in 0..segments {
+        let (a, b) = (points[i], points[(i + 1) % count]);
+        let n = (b - a).normalize_or_zero().perp() * hw;
+        out.push(vec![a + n, b + n, b - n, a - n]);
+    }
+
+    // Junções nos vértices internos (e no de fechamento)
+    let joints: Vec<usize> = if closed { (0..count).collect() } else { (1..count - 1).collect() };
+    for i in joints {
+        let p = points[i];
+        let prev = points[(i + count - 1) % count];
+        let next = points[(i + 1) % count];
+        let d0 = (p - prev).normalize_or_zero();
+        let d1 = (next - p).normalize_or_zero();
+        let cross = d0.perp_dot(d1);
+        if cross.abs() < 1e-6 && d0.dot(d1) > 0.0 {
+            continue;
+        }
+
+        if style.join == LineJoin::Round {
+            out.push(circle_polygon(p, hw));
+            continue;
+        }
+        // Lado externo da curva
+        let side = if cross > 0.0 { -1.0 } else { 1.0 };
+        let o0 = d0.perp() * hw * side;
+        let o1 = d1.perp() * hw * side;
+        let bisector = (o0 + o1).normalize_or_zero();
+        let cos_half = bisector.dot(o0 / hw);
+
+        if style.join == LineJoin::Miter && cos_half > 1e-6 && 1.0 / cos_half <= style.miter_limit {
+            let miter = p + bisector * (hw / cos_half);
+            out.push(vec![p, p + o0, miter, p + o1]);
+        } else {
+            out.push(vec![p, p + o0, p + o1]);
+        }
+    }
+
+    if !closed {
+        for (end, inward) in [(points[0], points[1]), (points[count - 1], points[count - 2])] {
+            let d = (end - inward).normalize_or_zero();
+            let n = d.perp() * hw;
+            match style.cap {
+                LineCap::Butt => {}
+                LineCap::Round => out.push(circle_polygon(end, hw)),
+                LineCap::Square => out.push(vec![end + n, end + n + d * hw, end - n + d * hw, end - n]),
+            }
+        }
+    }
+}
+
+/// Padrão efetivo de traços, como no SVG: `None` (contorno contínuo) se estiver vazio, tiver
+/// entrada negativa ou não finita, ou somar zero; listas ímpares são repetidas para ficar pares
+fn dash_pattern(pattern: &[f32]) -> Option<Vec<f32>> {
+    if pattern.iter().any(|d| !d.is_finite() || *d < 0.0) || pattern.iter().sum::<f32>() <= 0.0 {
+        return None;
+    }
+    let mut even = pattern.to_vec();
+    if even.len() % 2 == 1 {
+        even.extend_from_slice(pattern);
+    }
+    Some(even)
+}
+
+/// Quebra uma polilinha nos trechos "ligados" do padrão de traços
+fn dash_polyline(points: &[Vec2], closed: bool, pattern: &[f32], offset: f32) -> Vec<(Vec<Vec2>, bool)> {
+    let mut pts = points.to_vec();
+    if closed && let Some(&first) = points.first() {
+        pts.push(first);
+    }
+    let total: f32 = pattern.iter().sum();
+    if total <= 0.0 || pts.len() < 2 {
+        return vec![(points.to_vec(), closed)];
+    }
+
+    // Posição inicial dentro do padrão
+    let mut index = 0;
+    let mut remaining = pattern[0];
+    let mut skip = offset.rem_euclid(total);
+    while skip > 0.0 {
+        if skip >= remaining {
+            skip -= remaining;
+            index = (index + 1) % pattern.len();
+            remaining = pattern[index];
+        } else {
+            remaining -= skip;
+            skip = 0.0;
+        }
+    }
+
+    let mut out = Vec::new();
+    let mut current: Vec<Vec2> = if index % 2 == 0 { vec![pts[0]] } else { Vec::new() };
+    for seg in pts.windows(2) {
+        let (mut a, b) = (seg[0], seg[1]);
+        let mut len = a.distance(b);
+        while len > 0.0 {
+            let step = remaining.min(len);
+            let p = a + (b - a) * (step / len);
+            if index % 2 == 0 {
+                current.push(p);
+            }
+            remaining -= step;
+            len -= step;
+            a = p;
+            if remaining <= 1e-6 {
+                if index % 2 == 0 && current.len() >= 2 {
+                    out.push((std::mem::take(&mut current), false));
+                }
+                current.clear();
+                index = (index + 1) % pattern.len();
+                remaining = pattern[index];
+                if index % 2 == 0 {
+                    current.push(a);
+                }
+            }
+        }
+    }
+    if current.len() >= 2 {
+        out.push((current, false));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line() -> Path {
+        Path::new().move_to(0.0, 10.0).line_to(30.0, 10.0)
+    }
+
+    fn x_extent(polygons: &[Vec<Vec2>]) -> (f32, f32) {
+        polygons
+            .iter()
+            .flatten()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(lo, hi), p| (lo.min(p.x), hi.max(p.x)))
+    }
+
+    #[test]
+    fn odd_dash_patterns_repeat_like_svg() {
+        // [5] = [5, 5]: três traços num segmento de 30px
+        let dashed = stroke_polygons(&line(), &StrokeStyle::new(2.0).dash(&[5.0], 0.0));
+        assert_eq!(dashed.len(), 3);
+        assert_eq!(x_extent(&dashed), (0.0, 25.0));
+
+        // O deslocamento começa no meio do padrão
+        let shifted = stroke_polygons(&line(), &StrokeStyle::new(2.0).dash(&[5.0], 5.0));
+        assert_eq!(shifted.len(), 3);
+        assert_eq!(x_extent(&shifted), (5.0, 30.0));
+    }
+
+    #[test]
+    fn invalid_dash_patterns_draw_solid() {
+        let solid = stroke_polygons(&line(), &StrokeStyle::new(2.0));
+        assert_eq!(solid.len(), 1);
+        for pattern in [&[5.0, -1.0][..], &[f32::NAN, 5.0], &[f32::INFINITY], &[0.0, 0.0]] {
+            let style = StrokeStyle::new(2.0).dash(pattern, 0.0);
+            assert_eq!(stroke_polygons(&line(), &style), solid, "padrão {pattern:?}");
+        }
+        let style = StrokeStyle::new(2.0).dash(&[5.0], f32::NAN);
+        assert_eq!(stroke_polygons(&line(), &style).len(), 3);
+    }
+
+    #[test]
+    fn fill_rules_differ_on_nested_shapes() {
+        // Dois retângulos com a mesma orientação, um dentro do outro
+        let path = Path::rect(0.0, 0.0, 20.0, 20.0).extend(Path::rect(5.0, 5.0, 10.0, 10.0));
+        let coverage_at = |rule| {
+            let mut center = 0.0;
+            rasterize(&polygons_of(&path), rule, 20, 20, |x, y, coverage| {
+                if (x, y) == (10, 10) {
+                    center = coverage;
+                }
+            });
+            center
+        };
+        assert!((coverage_at(FillRule::NonZero) - 1.0).abs() < 1e-3);
+        assert_eq!(coverage_at(FillRule::EvenOdd), 0.0);
+    }
+
+    #[test]
+    fn caps_extend_open_ends_by_half_the_width() {
+        let path = Path::new().move_to(10.0, 10.0).line_to(30.0, 10.0);
+        let extent = |cap| x_extent(&stroke_polygons(&path, &StrokeStyle::new(4.0).cap(cap)));
+        assert_eq!(extent(LineCap::Butt), (10.0, 30.0));
+        assert_eq!(extent(LineCap::Square), (8.0, 32.0));
+        let (lo, hi) = extent(LineCap::Round);
+        assert!((lo - 8.0).abs() < 1e-3 && (hi - 32.0).abs() < 1e-3);
+    }
+}
@@ -0,0 +1,340 @@
+//! Gravação e replay determinístico de input
+//! Cada frame guarda o `InputState` completo e o instante relativo ao início da sessão,
+//! para reproduzir bug reports na janela (`RUSTUI_REPLAY`) ou no backend headless.
+
+use crate::core::{ImePreedit, InputState};
+use std::fmt::Write as _;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+const HEADER: &str = "rustui-recording 1";
+
+#[derive(Clone)]
+pub struct RecordedFrame {
+    /// Tempo desde o início da gravação
+    pub time: Duration,
+    pub input: InputState,
+}
+
+#[derive(Clone, Default)]
+pub struct InputRecording {
+    pub width: u32,
+    pub height: u32,
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl InputRecording {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            frames: Vec::new(),
+        }
+    }
+
+    pub fn push(&mut self, time: Duration, input: &InputState) {
+        self.frames.push(RecordedFrame {
+            time,
+            input: input.clone(),
+        });
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_text())
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Formato texto, um frame por linha com campos `chave=valor` separados por tab
+    pub fn to_text(&self) -> String {
+        let mut out = format!("{}\nsize {} {}\n", HEADER, self.width, self.height);
+        for frame in &self.frames {
+            let input = &frame.input;
+            let mut line = format!(
+                "t={}\tmouse={},{}\tdown={}\tpressed={}\treleased={}\tclicks={},{},{}\tpress={}\tscroll={}",
+                frame.time.as_micros(),
+                input.mouse_pos.0,
+                input.mouse_pos.1,
+                bits(&input.mouse_down),
+                bits(&input.mouse_pressed),
+                bits(&input.mouse_released),
+                input.click_count[0],
+                input.click_count[1],
+                input.click_count[2],
+                input
+                    .press_pos
+                    .iter()
+                    .map(|(x, y)| format!("{},{}", x, y))
+                    .collect::<Vec<_>>()
+                    .join(";"),
+                input.scroll_delta,
+            );
+            let _ = write!(line, "\tkeys={}", indices(&input.keys_pressed));
+            let _ = write!(line, "\tjust={}", indices(&input.keys_just_pressed));
+            if let Some(ch) = input.char_input {
+                let _ = write!(line, "\tchar={}", ch as u32);
+            }
+            if let Some(commit) = &input.ime_commit {
+                let _ = write!(line, "\tcommit={}", hex(commit));
+            }
+            if let Some(preedit) = &input.ime_preedit {
+                let _ = write!(line, "\tpreedit={}", hex(&preedit.text));
+                if let Some((start, end)) = preedit.cursor {
+                    let _ = write!(line, ",{},{}", start, end);
+                }
+            }
+            out.push_str(&line);
+            out.push('\n');
+        }
+        out
+    }
+
+    pub fn parse(text: &str) -> io::Result<Self> {
+        let mut lines = text.lines();
+        if lines.next() != Some(HEADER) {
+            return Err(invalid("cabeçalho de gravação inválido"));
+        }
+        let size: Vec<u32> = lines
+            .next()
+            .and_then(|l| l.strip_prefix("size "))
+            .ok_or_else(|| invalid("linha de tamanho ausente"))?
+            .split(' ')
+            .map(|v| v.parse().map_err(|_| invalid("tamanho inválido")))
+            .collect::<io::Result<_>>()?;
+        if size.len() != 2 {
+            return Err(invalid("tamanho inválido"));
+        }
+
+        let mut recording = Self::new(size[0], size[1]);
+        for line in lines.filter(|l| !l.is_empty()) {
+            recording.frames.push(parse_frame(line)?);
+        }
+        Ok(recording)
+    }
+}
+
+fn parse_frame(line: &str) -> io::Result<RecordedFrame> {
+    let mut time = Duration::ZERO;
+    let mut input = InputState::default();
+
+    for field in line.split('\t') {
+        let (key, value) = field
+            .split_once('=')
+            .ok_or_else(|| invalid("campo sem '='"))?;
+        match key {
+            "t" => time = Duration::from_micros(num(value)?),
+            "mouse" => input.mouse_pos = pair(value)?,
+            "down" => input.mouse_down = parse_bits(value)?,
+            "pressed" => input.mouse_pressed = parse_bits(value)?,
+            "released" => input.mouse_released = parse_bits(value)?,
+            "clicks" => {
+                let counts = list::<u32>(value)?;
+                for (slot, count) in input.click_count.iter_mut().zip(counts) {
+                    *slot = count;
+                }
+            }
+            "press" => {
+                for (slot, pos) in input.press_pos.iter_mut().zip(value.split(';')) {
+                    *slot = pair(pos)?;
+                }
+            }
+            "scroll" => input.scroll_delta = num(value)?,
+            "keys" => {
+                for idx in list::<usize>(value)? {
+                    if let Some(key) = input.keys_pressed.get_mut(idx) {
+                        *key = true;
+                    }
+                }
+            }
+            "just" => {
+                for idx in list::<usize>(value)? {
+                    if let Some(key) = input.keys_just_pressed.get_mut(idx) {
+                        *key = true;
+                    }
+                }
+            }
+            "char" => input.char_input = char::from_u32(num(value)?),
+            "commit" => input.ime_commit = Some(unhex(value)?),
+            "preedit" => {
+                let mut parts = value.split(',');
+                let text = unhex(parts.next().unwrap_or(""))?;
+                let cursor = match (parts.next(), parts.next()) {
+                    (Some(start), Some(end)) => Some((num(start)?, num(end)?)),
+                    _ => None,
+                };
+                // O widget fatia o texto com esse range: precisa caber e cair em caracteres
+                if let Some((start, end)) = cursor
+                    && (start > end || !text.is_char_boundary(start) || !text.is_char_boundary(end))
+                {
+                    return Err(invalid(&format!("cursor de preedit inválido: {}..{}", start, end)));
+                }
+                input.ime_preedit = Some(ImePreedit { text, cursor });
+            }
+            _ => {} // Campos desconhecidos de versões futuras são ignorados
+        }
+    }
+
+    input.mouse_clicked = input.mouse_down[0];
+    input.mouse_just_clicked = input.mouse_pressed[0];
+    Ok(RecordedFrame { time, input })
+}
+
+// ============================================================================
+// HELPERS DE (DE)SERIALIZAÇÃO
+// ============================================================================
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+fn num<T: std::str::FromStr>(value: &str) -> io::Result<T> {
+    value
+        .parse()
+        .map_err(|_| invalid(&format!("número inválido: {:?}", value)))
+}
+
+fn list<T: std::str::FromStr>(value: &str) -> io::Result<Vec<T>> {
+    value
+        .split(',')
+        .filter(|v| !v.is_empty())
+        .map(num)
+        .collect()
+}
+
+fn pair(value: &str) -> io::Result<(f32, f32)> {
+    let (x, y) = value
+        .split_once(',')
+        .ok_or_else(|| invalid("par inválido"))?;
+    Ok((num(x)?, num(y)?))
+}
+
+fn bits(values: &[bool; 3]) -> String {
+    values.iter().map(|&b| if b { '1' } else { '0' }).collect()
+}
+
+fn parse_bits(value: &str) -> io::Result<[bool; 3]> {
+    let mut out = [false; 3];
+    for (slot, ch) in out.iter_mut().zip(value.chars()) {
+        *slot = ch == '1';
+    }
+    Ok(out)
+}
+
+fn indices(values: &[bool]) -> String {
+    values
+        .iter()
+        .enumerate()
+        .filter(|(_, v)| **v)
+        .map(|(i, _)| i.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn hex(text: &str) -> String {
+    text.bytes().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn unhex(value: &str) -> io::Result<String> {
+    let bytes = (0..value.len())
+        .step_by(2)
+        .map(|i| {
+            value
+                .get(i..i + 2)
+                .and_then(|b| u8::from_str_radix(b, 16).ok())
+                .ok_or_else(|| invalid("hex inválido"))
+        })
+        .collect::<io::Result<Vec<u8>>>()?;
+    String::from_utf8(bytes).map_err(|_| invalid("UTF-8 inválido"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> InputRecording {
+        let mut recording = InputRecording::new(640, 480);
+
+        let mut input = InputState {
+            mouse_pos: (12.5, 300.0),
+            ..Default::default()
+        };
+        recording.push(Duration::ZERO, &input);
+
+        input.mouse_down = [true, false, true];
+        input.mouse_pressed = [true, false, false];
+        input.click_count = [2, 0, 1];
+        input.press_pos = [(12.5, 300.0), (0.0, 0.0), (4.0, -1.5)];
+        input.scroll_delta = -20.0;
+        input.keys_pressed[3] = true;
+        input.keys_pressed[200] = true;
+        input.keys_just_pressed[3] = true;
+        input.char_input = Some('ç');
+        input.ime_commit = Some("日本\tx".to_string());
+        input.ime_preedit = Some(ImePreedit {
+            text: "にほ".to_string(),
+            cursor: Some((3, 6)),
+        });
+        recording.push(Duration::from_micros(16_667), &input);
+        recording
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let recording = sample();
+        let path = std::env::temp_dir().join(format!("rustui-recording-{}.rec", std::process::id()));
+        recording.save(&path).unwrap();
+        let loaded = InputRecording::load(&path);
+        let _ = std::fs::remove_file(&path);
+        let loaded = loaded.unwrap();
+
+        assert_eq!((loaded.width, loaded.height), (640, 480));
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded.to_text(), recording.to_text());
+
+        let frame = &loaded.frames[1];
+        assert_eq!(frame.time, Duration::from_micros(16_667));
+        let input = &frame.input;
+        assert_eq!(input.mouse_pos, (12.5, 300.0));
+        assert_eq!(input.mouse_down, [true, false, true]);
+        assert_eq!(input.click_count, [2, 0, 1]);
+        assert_eq!(input.press_pos[2], (4.0, -1.5));
+        assert_eq!(input.scroll_delta, -20.0);
+        assert!(input.keys_pressed[3] && input.keys_pressed[200] && !input.keys_pressed[4]);
+        assert!(input.keys_just_pressed[3] && !input.keys_just_pressed[200]);
+        assert_eq!(input.char_input, Some('ç'));
+        assert_eq!(input.ime_commit.as_deref(), Some("日本\tx"));
+        let preedit = input.ime_preedit.as_ref().unwrap();
+        assert_eq!((preedit.text.as_str(), preedit.cursor), ("にほ", Some((3, 6))));
+        // Estados derivados do botão primário são reconstruídos
+        assert!(input.mouse_clicked && input.mouse_just_clicked);
+    }
+
+    #[test]
+    fn rejects_malformed_recordings() {
+        for text in [
+            "",
+            "outro-formato 1\nsize 1 1\n",
+            "rustui-recording 1\n",
+            "rustui-recording 1\nsize 640\n",
+            "rustui-recording 1\nsize 640 480\nt=abc\n",
+            "rustui-recording 1\nsize 640 480\nt=0\tmouse=1\n",
+            "rustui-recording 1\nsize 640 480\nt=0\tcommit=zz\n",
+            "rustui-recording 1\nsize 640 480\nt=0\tpreedit=e381ab,1,99\n",
+            "rustui-recording 1\nsize 640 480\nt=0\tpreedit=e381ab,1,2\n",
+            "rustui-recording 1\nsize 640 480\nt=0\tpreedit=e381ab,3,0\n",
+        ] {
+            assert!(InputRecording::parse(text).is_err(), "{:?}", text);
+        }
+    }
+}
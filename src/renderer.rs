@@ -1,5 +1,13 @@
 use ab_glyph::{Font, FontArc, PxScale, ScaleFont};
+use crate::config::render;
+use crate::layout::Rect;
+use crate::paint::{Paint, Shader};
+use crate::path::{LineCap, Path, StrokeStyle, stroke_path};
+use glam::{Affine2, Vec2, Vec4};
 use std::collections::HashMap;
+use crate::parallel::{fill_pixels, for_each_band, for_each_band_in};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Color {
@@ -75,8 +83,11 @@ impl Color {
 pub struct CachedGlyph {
     pub width: u32,
     pub height: u32,
+    /// Posição do bitmap relativa ao início da linha (topo, já somado o ascent)
     pub offset_x: f32,
     pub offset_y: f32,
+    /// Avanço horizontal até o próximo glifo
+    pub advance: f32,
     pub data: Vec<f32>,
 }
 
@@ -97,6 +108,8 @@ impl FontAtlas {
             let scale = PxScale::from(size);
             let scaled_font = font.as_scaled(scale);
             let glyph = scaled_font.scaled_glyph(c);
+            let advance = scaled_font.h_advance(glyph.id);
+            let ascent = scaled_font.ascent();
             if let Some(outlined) = font.outline_glyph(glyph) {
                 let bounds = outlined.px_bounds();
                 let width = bounds.width() as u32;
@@ -113,7 +126,8 @@ impl FontAtlas {
                         width,
                         height,
                         offset_x: bounds.min.x,
-                        offset_y: bounds.min.y,
+                        offset_y: bounds.min.y + ascent,
+                        advance,
                         data,
                     },
                 );
@@ -125,6 +139,7 @@ impl FontAtlas {
                         height: 0,
                         offset_x: 0.0,
                         offset_y: 0.0,
+                        advance,
                         data: vec![],
                     },
                 );
@@ -132,6 +147,303 @@ impl FontAtlas {
         }
         self.cache.get(&key).unwrap()
     }
+
+    /// Glifo já rasterizado (sem inserir); usado pela pintura em paralelo
+    pub fn get(&self, c: char, size: f32) -> Option<&CachedGlyph> {
+        self.cache.get(&(c, size as u32))
+    }
+
+    /// Garante que todos os glifos de `text` estão no cache
+    pub fn warm(&mut self, font: &FontArc, size: f32, text: &str) {
+        for c in text.chars() {
+            self.get_or_insert(font, c, size);
+        }
+    }
+}
+
+// ============================================================================
+// BLENDING
+// ============================================================================
+
+/// Modos de mistura de uma camada com o que está por baixo (separáveis, como no CSS)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum BlendMode {
+    #[default]
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+}
+
+impl BlendMode {
+    /// Mistura um canal (0..1) da fonte `s` sobre o fundo `b`
+    pub fn blend(self, b: f32, s: f32) -> f32 {
+        match self {
+            BlendMode::Normal => s,
+            BlendMode::Multiply => b * s,
+            BlendMode::Screen => b + s - b * s,
+            BlendMode::Overlay => {
+                if b <= 0.5 {
+                    2.0 * b * s
+                } else {
+                    1.0 - 2.0 * (1.0 - b) * (1.0 - s)
+                }
+            }
+            BlendMode::Darken => b.min(s),
+            BlendMode::Lighten => b.max(s),
+        }
+    }
+}
+
+/// Espaço em que as cores são misturadas. Os buffers guardam sempre sRGB pré-multiplicado;
+/// em `Linear` cada mistura decodifica para luz linear e recodifica (texto e bordas mais fiéis).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BlendSpace {
+    #[default]
+    Srgb,
+    Linear,
+}
+
+static LINEAR_BLENDING: AtomicBool = AtomicBool::new(render::LINEAR_BLENDING);
+
+pub fn set_blend_space(space: BlendSpace) {
+    LINEAR_BLENDING.store(space == BlendSpace::Linear, Ordering::Relaxed);
+}
+
+pub fn blend_space() -> BlendSpace {
+    if LINEAR_BLENDING.load(Ordering::Relaxed) {
+        BlendSpace::Linear
+    } else {
+        BlendSpace::Srgb
+    }
+}
+
+struct GammaTables {
+    to_linear: [f32; 256],
+    /// Luz linear (quantizada em 4096 passos) -> sRGB
+    to_srgb: Vec<f32>,
+}
+
+fn gamma() -> &'static GammaTables {
+    static TABLES: OnceLock<GammaTables> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let to_linear = std::array::from_fn(|i| {
+            let c = i as f32 / 255.0;
+            if c <= 0.04045 { c / 12.92 } else { ((c + 0.055) / 1.055).powf(2.4) }
+        });
+        let to_srgb = (0..4096)
+            .map(|i| {
+                let l = i as f32 / 4095.0;
+                if l <= 0.003_130_8 { l * 12.92 } else { 1.055 * l.powf(1.0 / 2.4) - 0.055 }
+            })
+            .collect();
+        GammaTables { to_linear, to_srgb }
+    })
+}
+
+/// Cor pré-multiplicada em ponto flutuante (0..1)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Premul {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+impl Premul {
+    /// `c` (alpha não pré-multiplicado) com a opacidade extra `coverage`
+    #[inline]
+    pub fn from_color(c: Color, coverage: f32) -> Self {
+        let a = c.a as f32 / 255.0 * coverage.clamp(0.0, 1.0);
+        let k = a / 255.0;
+        Self {
+            r: c.r as f32 * k,
+            g: c.g as f32 * k,
+            b: c.b as f32 * k,
+            a,
+        }
+    }
+
+    /// Lê um pixel do buffer (já pré-multiplicado)
+    #[inline]
+    pub fn load(p: &[u8]) -> Self {
+        Self {
+            r: p[0] as f32 / 255.0,
+            g: p[1] as f32 / 255.0,
+            b: p[2] as f32 / 255.0,
+            a: p[3] as f32 / 255.0,
+        }
+    }
+
+    #[inline]
+    pub fn store(self, p: &mut [u8]) {
+        let q = |v: f32| (v.clamp(0.0, 1.0) * 255.0 + 0.5) as u8;
+        p[0] = q(self.r);
+        p[1] = q(self.g);
+        p[2] = q(self.b);
+        p[3] = q(self.a);
+    }
+
+    pub fn scale(self, k: f32) -> Self {
+        Self {
+            r: self.r * k,
+            g: self.g * k,
+            b: self.b * k,
+            a: self.a * k,
+        }
+    }
+
+    /// Converte os canais (des-pré-multiplicando) com `f` e pré-multiplica de novo
+    fn map_channels(self, f: impl Fn(f32) -> f32) -> Self {
+        if self.a <= 0.0 {
+            return Self::default();
+        }
+        let k = self.a;
+        Self {
+            r: f((self.r / k).min(1.0)) * k,
+            g: f((self.g / k).min(1.0)) * k,
+            b: f((self.b / k).min(1.0)) * k,
+            a: k,
+        }
+    }
+
+    fn to_linear(self) -> Self {
+        let t = gamma();
+        self.map_channels(|v| t.to_linear[(v * 255.0 + 0.5) as usize])
+    }
+
+    fn to_srgb(self) -> Self {
+        let t = gamma();
+        self.map_channels(|v| t.to_srgb[(v * 4095.0 + 0.5) as usize])
+    }
+}
+
+/// Núcleo de todas as primitivas: compõe `src` (pré-multiplicada, sRGB) sobre o pixel
+/// com o modo dado, respeitando o alpha do destino (camadas transparentes)
+#[inline]
+pub fn blend_premul(p: &mut [u8], src: Premul, mode: BlendMode) {
+    if src.a <= 0.0 {
+        return;
+    }
+    if mode == BlendMode::Normal && src.a >= 1.0 {
+        src.store(p);
+        return;
+    }
+    let linear = blend_space() == BlendSpace::Linear;
+    if mode == BlendMode::Normal && !linear {
+        // Caminho comum: "over" pré-multiplicado nos 4 canais de uma vez (SIMD via glam)
+        let s = Vec4::new(src.r, src.g, src.b, src.a) * 255.0;
+        let d = Vec4::new(p[0] as f32, p[1] as f32, p[2] as f32, p[3] as f32);
+        let out = (s + d * (1.0 - src.a) + 0.5).min(Vec4::splat(255.0));
+        p.copy_from_slice(&[out.x as u8, out.y as u8, out.z as u8, out.w as u8]);
+        return;
+    }
+    let (s, d) = if linear {
+        (src.to_linear(), Premul::load(p).to_linear())
+    } else {
+        (src, Premul::load(p))
+    };
+
+    let out_a = s.a + d.a * (1.0 - s.a);
+    let channel = |sc: f32, dc: f32| -> f32 {
+        if mode == BlendMode::Normal || d.a <= 0.0 {
+            return sc + dc * (1.0 - s.a);
+        }
+        // Fórmula de composição do W3C: a mistura só vale onde há fundo
+        let (su, du) = (sc / s.a, dc / d.a);
+        sc * (1.0 - d.a) + s.a * d.a * mode.blend(du, su) + dc * (1.0 - s.a)
+    };
+    let out = Premul {
+        r: channel(s.r, d.r),
+        g: channel(s.g, d.g),
+        b: channel(s.b, d.b),
+        a: out_a,
+    };
+    if linear { out.to_srgb() } else { out }.store(p);
+}
+
+/// Pinta `c` com cobertura `coverage` sobre um pixel (operador "over")
+#[inline]
+pub fn blend_pixel(p: &mut [u8], c: Color, coverage: f32) {
+    blend_premul(p, Premul::from_color(c, coverage), BlendMode::Normal);
+}
+
+/// Como `blend_pixel`, com um modo de mistura
+#[inline]
+pub fn blend_pixel_with(p: &mut [u8], c: Color, coverage: f32, mode: BlendMode) {
+    blend_premul(p, Premul::from_color(c, coverage), mode);
+}
+
+/// Compõe a área `bounds` de uma camada (mesmo tamanho do frame, pré-multiplicada) com opacidade e modo
+pub fn composite_layer(f: &mut [u8], layer: &[u8], bounds: Rect, opacity: f32, mode: BlendMode, sw: u32, sh: u32) {
+    let opacity = opacity.clamp(0.0, 1.0);
+    let x0 = bounds.x.max(0.0) as usize;
+    let x1 = ((bounds.x + bounds.w).max(0.0) as usize).min(sw as usize).max(x0);
+    let y0 = bounds.y.max(0.0) as u32;
+    let y1 = ((bounds.y + bounds.h).max(0.0) as u32).min(sh).max(y0);
+    let stride = sw as usize * 4;
+    let area = (x1 - x0) * (y1 - y0) as usize;
+
+    for_each_band_in(f, stride, y0..y1, area, |band, first, _| {
+        for (py, line) in (first as usize..).zip(band.chunks_exact_mut(stride)) {
+            let src = &layer[py * stride + x0 * 4..py * stride + x1 * 4];
+            for (d, s) in line[x0 * 4..x1 * 4].chunks_exact_mut(4).zip(src.chunks_exact(4)) {
+                if s[3] == 0 {
+                    continue;
+                }
+                if s[3] == 255 && opacity >= 1.0 && mode == BlendMode::Normal {
+                    d.copy_from_slice(s);
+                    continue;
+                }
+                blend_premul(d, Premul::load(s).scale(opacity), mode);
+            }
+        }
+    });
+}
+
+/// Compõe uma camada transformada: cada pixel de `dest` é levado de volta à camada por
+/// `to_layer` e amostrado bilinearmente (pré-multiplicado, sem franjas escuras)
+pub fn composite_layer_transformed(f: &mut [u8], layer: &[u8], to_layer: &Affine2, dest: Rect, sw: u32, sh: u32) {
+    let x_start = (dest.x.floor() as i32).max(0);
+    let x_end = ((dest.x + dest.w).ceil() as i32).min(sw as i32);
+    let y_start = (dest.y.floor() as i32).max(0);
+    let y_end = ((dest.y + dest.h).ceil() as i32).min(sh as i32);
+
+    let texel = |x: i32, y: i32| -> [f32; 4] {
+        if x < 0 || y < 0 || x >= sw as i32 || y >= sh as i32 {
+            return [0.0; 4];
+        }
+        let i = ((y as u32 * sw + x as u32) * 4) as usize;
+        [layer[i], layer[i + 1], layer[i + 2], layer[i + 3]].map(|v| v as f32 / 255.0)
+    };
+
+    for py in y_start..y_end {
+        for px in x_start..x_end {
+            let q = to_layer.transform_point2(Vec2::new(px as f32 + 0.5, py as f32 + 0.5)) - 0.5;
+            let (qx, qy) = (q.x.floor(), q.y.floor());
+            let (tx, ty) = (q.x - qx, q.y - qy);
+            let (ix, iy) = (qx as i32, qy as i32);
+
+            let mut acc = [0.0f32; 4];
+            for (dx, dy, w) in [
+                (0, 0, (1.0 - tx) * (1.0 - ty)),
+                (1, 0, tx * (1.0 - ty)),
+                (0, 1, (1.0 - tx) * ty),
+                (1, 1, tx * ty),
+            ] {
+                let t = texel(ix + dx, iy + dy);
+                for (a, v) in acc.iter_mut().zip(t) {
+                    *a += v * w;
+                }
+            }
+
+            let [r, g, b, a] = acc;
+            let idx = ((py as u32 * sw + px as u32) * 4) as usize;
+            blend_premul(&mut f[idx..idx + 4], Premul { r, g, b, a }, BlendMode::Normal);
+        }
+    }
 }
 
 pub fn draw_text_smooth(
@@ -142,230 +454,521 @@ pub fn draw_text_smooth(
     x: f32,
     y: f32,
     text: &str,
-    col: Color,
+    col: impl Into<Paint>,
     sw: u32,
     sh: u32,
 ) {
-    let scale = PxScale::from(size);
-    let scaled_font = font.as_scaled(scale);
-    let mut caret = ab_glyph::point(x, y + scaled_font.ascent());
+    atlas.warm(font, size, text);
+    let paint = col.into();
+    draw_text_cached(frame, atlas, text_line(font, size, x, y, text, &paint), text, paint, sw, sh);
+}
+
+/// Caixa da linha de texto em (x, y) com altura `size`; gradientes cobrem a linha inteira,
+/// então a largura só é medida quando a tinta não é sólida
+pub fn text_line(font: &FontArc, size: f32, x: f32, y: f32, text: &str, paint: &Paint) -> Rect {
+    let w = match paint {
+        Paint::Solid(_) => 0.0,
+        Paint::Gradient(_) => measure_text(font, size, text),
+    };
+    Rect { x, y, w, h: size }
+}
+
+/// Como `draw_text_smooth`, com um atlas só de leitura (glifos ausentes são pulados).
+/// `line` vem de `text_line`: a altura é o tamanho da fonte
+pub fn draw_text_cached(
+    frame: &mut [u8],
+    atlas: &FontAtlas,
+    line: Rect,
+    text: &str,
+    col: impl Into<Paint>,
+    sw: u32,
+    sh: u32,
+) {
+    let shader = col.into().shader(line);
+    let mut caret_x = line.x;
 
     for c in text.chars() {
-        let cached = atlas.get_or_insert(font, c, size);
+        let Some(cached) = atlas.get(c, line.h) else {
+            continue;
+        };
         if cached.width > 0 {
-            let start_x = (caret.x + cached.offset_x) as i32;
-            let start_y = (caret.y + cached.offset_y) as i32;
+            let start_x = (caret_x + cached.offset_x).floor() as i32;
+            let start_y = (line.y + cached.offset_y).floor() as i32;
             for py in 0..cached.height {
+                let final_y = start_y + py as i32;
+                if final_y < 0 || final_y >= sh as i32 {
+                    continue;
+                }
                 for px in 0..cached.width {
                     let v = cached.data[(py * cached.width + px) as usize];
-                    if v > 0.01 {
-                        let final_x = start_x + px as i32;
-                        let final_y = start_y + py as i32;
-                        if final_x >= 0
-                            && final_x < sw as i32
-                            && final_y >= 0
-                            && final_y < sh as i32
-                        {
-                            let idx = ((final_y as u32 * sw + final_x as u32) * 4) as usize;
-                            let alpha = v * (col.a as f32 / 255.0);
-                            frame[idx] =
-                                (col.r as f32 * alpha + frame[idx] as f32 * (1.0 - alpha)) as u8;
-                            frame[idx + 1] = (col.g as f32 * alpha
-                                + frame[idx + 1] as f32 * (1.0 - alpha))
-                                as u8;
-                            frame[idx + 2] = (col.b as f32 * alpha
-                                + frame[idx + 2] as f32 * (1.0 - alpha))
-                                as u8;
-                            frame[idx + 3] = 255;
-                        }
+                    let final_x = start_x + px as i32;
+                    if v > render::TEXT_ALPHA_THRESHOLD && final_x >= 0 && final_x < sw as i32 {
+                        let idx = ((final_y as u32 * sw + final_x as u32) * 4) as usize;
+                        blend_pixel(&mut frame[idx..idx + 4], shader.at(final_x, final_y), v);
                     }
                 }
             }
         }
-        caret.x += scaled_font.h_advance(font.glyph_id(c));
-    }
-}
-
-pub fn draw_icon_plus(f: &mut [u8], x: i32, y: i32, size: i32, col: Color, sw: u32, sh: u32) {
-    let mid = size / 2;
-    for i in 0..size {
-        let px1 = x + mid;
-        let py1 = y + i;
-        let px2 = x + i;
-        let py2 = y + mid;
-        if px1 >= 0 && px1 < sw as i32 && py1 >= 0 && py1 < sh as i32 {
-            let idx = ((py1 as u32 * sw + px1 as u32) * 4) as usize;
-            f[idx] = col.r;
-            f[idx + 1] = col.g;
-            f[idx + 2] = col.b;
-            f[idx + 3] = 255;
-        }
-        if px2 >= 0 && px2 < sw as i32 && py2 >= 0 && py2 < sh as i32 {
-            let idx = ((py2 as u32 * sw + px2 as u32) * 4) as usize;
-            f[idx] = col.r;
-            f[idx + 1] = col.g;
-            f[idx + 2] = col.b;
-            f[idx + 3] = 255;
-        }
+        caret_x += cached.advance;
     }
 }
 
-pub fn draw_icon_chart(f: &mut [u8], x: i32, y: i32, size: i32, col: Color, sw: u32, sh: u32) {
-    for i in 0..3 {
-        let h = (i + 1) * (size / 3);
-        let wx = size / 4;
-        draw_rect(f, x + i * (wx + 1), y + size - h, wx, h, col, sw, sh);
-    }
+/// Mede a largura (soma dos advances) de um texto em pixels
+pub fn measure_text(font: &FontArc, size: f32, text: &str) -> f32 {
+    let scaled_font = font.as_scaled(PxScale::from(size));
+    text.chars()
+        .map(|c| scaled_font.h_advance(font.glyph_id(c)))
+        .sum()
 }
 
-// OTIMIZAÇÃO HÍBRIDA 🚀: Desenho de retângulos arredondados ultra-rápido
+/// Retângulo arredondado por SDF: posição sub-pixel, raio por canto (limitado ao tamanho,
+/// então `radius.full` vira pílula/círculo) e cobertura única por pixel
 pub fn draw_rounded_rect(
     f: &mut [u8],
     x: f32,
     y: f32,
     w: f32,
     h: f32,
-    r: f32,
-    c: Color,
+    r: impl Into<CornerRadii>,
+    c: impl Into<Paint>,
     sw: u32,
     sh: u32,
 ) {
-    if r <= 0.0 {
-        draw_rect(f, x as i32, y as i32, w as i32, h as i32, c, sw, sh);
+    if w <= 0.0 || h <= 0.0 {
         return;
     }
+    let rect = Rect { x, y, w, h };
+    let shader = c.into().shader(rect);
+    let radii = r.into().clamped(w, h);
 
-    // 1. Centro Sólido (Fast Fill)
-    draw_rect(
-        f,
-        (x + r) as i32,
-        y as i32,
-        (w - 2.0 * r) as i32,
-        h as i32,
-        c,
-        sw,
-        sh,
-    );
-    draw_rect(
-        f,
-        x as i32,
-        (y + r) as i32,
-        r as i32,
-        (h - 2.0 * r) as i32,
-        c,
-        sw,
-        sh,
-    );
-    draw_rect(
-        f,
-        (x + w - r) as i32,
-        (y + r) as i32,
-        r as i32,
-        (h - 2.0 * r) as i32,
-        c,
-        sw,
-        sh,
-    );
+    // Faixas em que a borda é reta: dentro delas a cobertura não precisa do SDF
+    let left = radii.top_left.max(radii.bottom_left);
+    let right = radii.top_right.max(radii.bottom_right);
+    let top = radii.top_left.max(radii.top_right);
+    let bottom = radii.bottom_left.max(radii.bottom_right);
 
-    // 2. Cantos Arredondados (Math only for corners)
-    let corners = [
-        (x + r, y + r),
-        (x + w - r, y + r),
-        (x + r, y + h - r),
-        (x + w - r, y + h - r),
-    ];
-    for (i, (cx, cy)) in corners.iter().enumerate() {
-        let x_range = if i % 2 == 0 {
-            (x as i32)..(cx.clone() as i32)
-        } else {
-            (cx.clone() as i32)..(x as i32 + w as i32)
-        };
-        let y_range = if i < 2 {
-            (y as i32)..(cy.clone() as i32)
-        } else {
-            (cy.clone() as i32)..(y as i32 + h as i32)
-        };
+    let x_start = (x.floor() as i32).max(0);
+    let x_end = ((x + w).ceil() as i32).min(sw as i32);
+    let y_start = (y.floor() as i32).max(0);
+    let y_end = ((y + h).ceil() as i32).min(sh as i32);
+
+    if x_start >= x_end || y_start >= y_end {
+        return;
+    }
+
+    let stride = sw as usize * 4;
+    let area = ((x_end - x_start) * (y_end - y_start)) as usize;
+    let rows = y_start as u32..y_end as u32;
+    for_each_band_in(f, stride, rows, area, |band, first, count| {
+        for py in first as i32..(first + count) as i32 {
+            let (y0, y1) = (py as f32, py as f32 + 1.0);
+            // Fração vertical do pixel dentro do retângulo
+            let cover_y = (y1.min(y + h) - y0.max(y)).clamp(0.0, 1.0);
+            let straight_row = y0 >= y + top && y1 <= y + h - bottom;
+            let row = (py as u32 - first) as usize * stride;
 
-        for py in y_range {
-            for px in x_range.clone() {
-                if px < 0 || py < 0 || px >= sw as i32 || py >= sh as i32 {
+            for px in x_start..x_end {
+                let (x0, x1) = (px as f32, px as f32 + 1.0);
+                let coverage = if straight_row || (x0 >= x + left && x1 <= x + w - right) {
+                    (x1.min(x + w) - x0.max(x)).clamp(0.0, 1.0) * cover_y
+                } else {
+                    (0.5 - rounded_rect_distance(x0 + 0.5, y0 + 0.5, rect, &radii)).clamp(0.0, 1.0)
+                };
+                if coverage <= 0.0 {
                     continue;
                 }
-                let dx = px as f32 - cx;
-                let dy = py as f32 - cy;
-                let dist = (dx * dx + dy * dy).sqrt();
-                if dist <= r {
-                    let idx = ((py as u32 * sw + px as u32) * 4) as usize;
-                    let a = if dist <= r - 1.0 { 1.0 } else { r - dist };
-                    let al = (c.a as f32 * a) / 255.0;
-                    f[idx] = (c.r as f32 * al + f[idx] as f32 * (1.0 - al)) as u8;
-                    f[idx + 1] = (c.g as f32 * al + f[idx + 1] as f32 * (1.0 - al)) as u8;
-                    f[idx + 2] = (c.b as f32 * al + f[idx + 2] as f32 * (1.0 - al)) as u8;
-                    f[idx + 3] = 255;
+                let color = shader.at(px, py);
+                let idx = row + px as usize * 4;
+                let p = &mut band[idx..idx + 4];
+                if coverage >= 1.0 && color.a == 255 {
+                    p.copy_from_slice(&[color.r, color.g, color.b, 255]);
+                } else {
+                    blend_pixel(p, color, coverage);
                 }
             }
         }
-    }
+    });
 }
 
-pub fn draw_shadow(f: &mut [u8], x: f32, y: f32, w: f32, h: f32, r: f32, b: f32, sw: u32, sh: u32) {
-    draw_rounded_rect(
-        f,
-        x + 2.0,
-        y + 4.0,
-        w,
-        h,
-        r + b,
-        Color {
-            r: 0,
-            g: 0,
-            b: 0,
-            a: 50,
-        },
-        sw,
-        sh,
-    );
+/// Contorno de retângulo arredondado (anel), usado p.ex. no anel de foco
+pub fn draw_rounded_ring(
+    f: &mut [u8],
+    rect: Rect,
+    r: f32,
+    thickness: f32,
+    c: impl Into<Paint>,
+    sw: u32,
+    sh: u32,
+) {
+    let Rect { x, y, w, h } = rect;
+    let c = c.into().shader(rect);
+    let r = r.min(w / 2.0).min(h / 2.0).max(0.0);
+    let x_start = (x.floor() as i32).max(0);
+    let x_end = ((x + w).ceil() as i32).min(sw as i32);
+    let y_start = (y.floor() as i32).max(0);
+    let y_end = ((y + h).ceil() as i32).min(sh as i32);
+
+    let (cx, cy) = (x + w / 2.0, y + h / 2.0);
+    for py in y_start..y_end {
+        for px in x_start..x_end {
+            // Distância assinada até a borda externa (negativa dentro)
+            let qx = (px as f32 + 0.5 - cx).abs() - (w / 2.0 - r);
+            let qy = (py as f32 + 0.5 - cy).abs() - (h / 2.0 - r);
+            let outside = (qx.max(0.0).powi(2) + qy.max(0.0).powi(2)).sqrt();
+            let dist = outside + qx.max(qy).min(0.0) - r;
+
+            let coverage = (0.5 - dist).clamp(0.0, 1.0) * (dist + thickness + 0.5).clamp(0.0, 1.0);
+            if coverage <= 0.0 {
+                continue;
+            }
+            let idx = ((py as u32 * sw + px as u32) * 4) as usize;
+            blend_pixel(&mut f[idx..idx + 4], c.at(px, py), coverage);
+        }
+    }
 }
 
 pub fn clear(f: &mut [u8], c: Color) {
-    for p in f.chunks_exact_mut(4) {
-        p[0] = c.r;
-        p[1] = c.g;
-        p[2] = c.b;
-        p[3] = c.a;
-    }
+    let mut px = [0u8; 4];
+    Premul::from_color(c, 1.0).store(&mut px);
+    for_each_band(f, 4, |band, _, _| fill_pixels(band, px));
 }
 
-pub fn draw_rect(f: &mut [u8], x: i32, y: i32, w: i32, h: i32, c: Color, sw: u32, sh: u32) {
-    let x_start = x.max(0);
-    let x_end = (x + w).min(sw as i32);
-    let y_start = y.max(0);
-    let y_end = (y + h).min(sh as i32);
+pub fn draw_rect(f: &mut [u8], x: i32, y: i32, w: i32, h: i32, c: impl Into<Paint>, sw: u32, sh: u32) {
+    let bounds = Rect {
+        x: x as f32,
+        y: y as f32,
+        w: w as f32,
+        h: h as f32,
+    };
+    fill_rect_shader(f, bounds, &c.into().shader(bounds), sw, sh);
+}
+
+/// Preenche um retângulo com uma tinta já resolvida (a caixa do gradiente pode ser maior);
+/// as bordas são arredondadas para o pixel mais próximo
+pub fn fill_rect_shader(f: &mut [u8], rect: Rect, shader: &Shader, sw: u32, sh: u32) {
+    let x_start = (rect.x.round() as i32).max(0);
+    let x_end = ((rect.x + rect.w).round() as i32).min(sw as i32);
+    let y_start = (rect.y.round() as i32).max(0);
+    let y_end = ((rect.y + rect.h).round() as i32).min(sh as i32);
 
     if x_start >= x_end || y_start >= y_end {
         return;
     }
 
+    let stride = sw as usize * 4;
+    let (left, right) = (x_start as usize * 4, x_end as usize * 4);
+    let area = ((x_end - x_start) * (y_end - y_start)) as usize;
+    let rows = y_start as u32..y_end as u32;
+
+    // Cor sólida opaca: cada linha é um memcpy largo
+    if let Some(c) = shader.solid()
+        && c.a == 255
+    {
+        for_each_band_in(f, stride, rows, area, |band, _, _| {
+            for line in band.chunks_exact_mut(stride) {
+                fill_pixels(&mut line[left..right], [c.r, c.g, c.b, 255]);
+            }
+        });
+        return;
+    }
+
+    for_each_band_in(f, stride, rows, area, |band, first, _| {
+        for (py, line) in (first as i32..).zip(band.chunks_exact_mut(stride)) {
+            for (px, p) in (x_start..).zip(line[left..right].chunks_exact_mut(4)) {
+                let c = shader.at(px, py);
+                if c.a == 255 {
+                    p[0] = c.r;
+                    p[1] = c.g;
+                    p[2] = c.b;
+                    p[3] = 255;
+                } else {
+                    blend_pixel(p, c, 1.0);
+                }
+            }
+        }
+    });
+}
+
+// ============================================================================
+// RAIOS POR CANTO E BORDAS
+// ============================================================================
+
+/// Raios dos quatro cantos (um `f32` vira o mesmo raio em todos)
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CornerRadii {
+    pub top_left: f32,
+    pub top_right: f32,
+    pub bottom_right: f32,
+    pub bottom_left: f32,
+}
+
+impl CornerRadii {
+    pub const ZERO: CornerRadii = CornerRadii::all(0.0);
+
+    pub const fn all(r: f32) -> Self {
+        Self {
+            top_left: r,
+            top_right: r,
+            bottom_right: r,
+            bottom_left: r,
+        }
+    }
+
+    pub const fn new(top_left: f32, top_right: f32, bottom_right: f32, bottom_left: f32) -> Self {
+        Self {
+            top_left,
+            top_right,
+            bottom_right,
+            bottom_left,
+        }
+    }
+
+    pub fn is_uniform(&self) -> bool {
+        self.top_left == self.top_right
+            && self.top_left == self.bottom_right
+            && self.top_left == self.bottom_left
+    }
+
+    /// Reduz os raios para caber em `w` x `h` como no CSS (9999 vira pílula/círculo)
+    pub fn clamped(self, w: f32, h: f32) -> Self {
+        let fit = |len: f32, a: f32, b: f32| if a + b > len { len / (a + b) } else { 1.0 };
+        let scale = fit(w, self.top_left, self.top_right)
+            .min(fit(w, self.bottom_left, self.bottom_right))
+            .min(fit(h, self.top_left, self.bottom_left))
+            .min(fit(h, self.top_right, self.bottom_right));
+        let max = (w.min(h) / 2.0).max(0.0);
+        let r = |v: f32| (v.max(0.0) * scale).min(max);
+        Self::new(r(self.top_left), r(self.top_right), r(self.bottom_right), r(self.bottom_left))
+    }
+}
+
+impl From<f32> for CornerRadii {
+    fn from(r: f32) -> Self {
+        Self::all(r)
+    }
+}
+
+/// Distância assinada (negativa dentro) até a borda de um retângulo arredondado por canto.
+/// Os raios já devem estar limitados com `CornerRadii::clamped`.
+pub fn rounded_rect_distance(px: f32, py: f32, rect: Rect, radii: &CornerRadii) -> f32 {
+    let dx = px - (rect.x + rect.w / 2.0);
+    let dy = py - (rect.y + rect.h / 2.0);
+    let r = match (dx < 0.0, dy < 0.0) {
+        (true, true) => radii.top_left,
+        (false, true) => radii.top_right,
+        (false, false) => radii.bottom_right,
+        (true, false) => radii.bottom_left,
+    };
+    let qx = dx.abs() - (rect.w / 2.0 - r);
+    let qy = dy.abs() - (rect.h / 2.0 - r);
+    let outside = (qx.max(0.0).powi(2) + qy.max(0.0).powi(2)).sqrt();
+    outside + qx.max(qy).min(0.0) - r
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum BorderStyle {
+    #[default]
+    Solid,
+    Dashed,
+    Dotted,
+}
+
+/// Larguras por lado (top, right, bottom, left), cor e estilo
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Border {
+    pub widths: [f32; 4],
+    pub color: Color,
+    pub style: BorderStyle,
+}
+
+impl Border {
+    pub fn new(width: f32, color: Color) -> Self {
+        Self {
+            widths: [width; 4],
+            color,
+            style: BorderStyle::Solid,
+        }
+    }
+
+    pub fn sides(mut self, top: f32, right: f32, bottom: f32, left: f32) -> Self {
+        self.widths = [top, right, bottom, left];
+        self
+    }
+
+    /// Tracejada (dashed/dotted usam a maior largura em todos os lados)
+    pub fn dashed(mut self) -> Self {
+        self.style = BorderStyle::Dashed;
+        self
+    }
+
+    pub fn dotted(mut self) -> Self {
+        self.style = BorderStyle::Dotted;
+        self
+    }
+}
+
+/// Borda desenhada por dentro de `rect` (como `box-sizing: border-box`)
+pub fn draw_border(
+    f: &mut [u8],
+    rect: Rect,
+    radii: impl Into<CornerRadii>,
+    border: &Border,
+    sw: u32,
+    sh: u32,
+) {
+    let radii = radii.into().clamped(rect.w, rect.h);
+    let [top, right, bottom, left] = border.widths.map(|w| w.max(0.0));
+
+    if border.style != BorderStyle::Solid {
+        let width = top.max(right).max(bottom).max(left);
+        if width <= 0.0 {
+            return;
+        }
+        // O traço fica centrado numa linha recuada meia largura para dentro
+        let half = width / 2.0;
+        let inset = |r: f32| (r - half).max(0.0);
+        let path = Path::rounded_rect(
+            rect.x + half,
+            rect.y + half,
+            rect.w - width,
+            rect.h - width,
+            CornerRadii::new(
+                inset(radii.top_left),
+                inset(radii.top_right),
+                inset(radii.bottom_right),
+                inset(radii.bottom_left),
+            ),
+        );
+        let style = match border.style {
+            BorderStyle::Dotted => StrokeStyle::new(width).cap(LineCap::Round).dash(&[0.0, width * 2.0], 0.0),
+            _ => StrokeStyle::new(width).dash(&[width * 3.0, width * 2.0], 0.0),
+        };
+        stroke_path(f, &path, &style, border.color, sw, sh);
+        return;
+    }
+
+    let inner = Rect {
+        x: rect.x + left,
+        y: rect.y + top,
+        w: rect.w - left - right,
+        h: rect.h - top - bottom,
+    };
+    let inner_radii = CornerRadii::new(
+        radii.top_left - left.max(top),
+        radii.top_right - right.max(top),
+        radii.bottom_right - right.max(bottom),
+        radii.bottom_left - left.max(bottom),
+    )
+    .clamped(inner.w, inner.h);
+
+    let x_start = (rect.x.floor() as i32).max(0);
+    let x_end = ((rect.x + rect.w).ceil() as i32).min(sw as i32);
+    let y_start = (rect.y.floor() as i32).max(0);
+    let y_end = ((rect.y + rect.h).ceil() as i32).min(sh as i32);
+    // Pixels inteiros dentro do buraco nas linhas em que ele tem lados retos são pulados
+    let hole_top = inner.y + inner_radii.top_left.max(inner_radii.top_right);
+    let hole_bottom = inner.y + inner.h - inner_radii.bottom_left.max(inner_radii.bottom_right);
+    let skip = (inner.x.ceil() as i32, (inner.x + inner.w).floor() as i32);
     for py in y_start..y_end {
-        let line_start = ((py as u32 * sw + x_start as u32) * 4) as usize;
-        let line_end = ((py as u32 * sw + x_end as u32) * 4) as usize;
-        for p in f[line_start..line_end].chunks_exact_mut(4) {
-            if c.a == 255 {
-                p[0] = c.r;
-                p[1] = c.g;
-                p[2] = c.b;
-                p[3] = 255;
+        let straight_row = py as f32 >= hole_top && py as f32 + 1.0 <= hole_bottom;
+        let interior = if straight_row { skip.0..skip.1 } else { 0..0 };
+        for px in (x_start..x_end).filter(|px| !interior.contains(px)) {
+            let (cx, cy) = (px as f32 + 0.5, py as f32 + 0.5);
+            let outer = (0.5 - rounded_rect_distance(cx, cy, rect, &radii)).clamp(0.0, 1.0);
+            if outer <= 0.0 {
+                continue;
+            }
+            let hole = if inner.w > 0.0 && inner.h > 0.0 {
+                (0.5 - rounded_rect_distance(cx, cy, inner, &inner_radii)).clamp(0.0, 1.0)
             } else {
-                let al = c.a as f32 / 255.0;
-                p[0] = (c.r as f32 * al + p[0] as f32 * (1.0 - al)) as u8;
-                p[1] = (c.g as f32 * al + p[1] as f32 * (1.0 - al)) as u8;
-                p[2] = (c.b as f32 * al + p[2] as f32 * (1.0 - al)) as u8;
-                p[3] = 255;
+                0.0
+            };
+            let coverage = outer * (1.0 - hole);
+            if coverage > 0.0 {
+                let idx = ((py as u32 * sw + px as u32) * 4) as usize;
+                blend_pixel(&mut f[idx..idx + 4], border.color, coverage);
             }
         }
     }
 }
 
+// ============================================================================
+// COMANDOS DE DESENHO ADIADOS (overlay)
+// ============================================================================
+
+/// Primitiva gravada para ser desenhada depois do frame (ex: preview de arrasto)
+#[derive(Clone, Debug)]
+pub enum DrawCommand {
+    Rect {
+        x: i32,
+        y: i32,
+        w: i32,
+        h: i32,
+        color: Color,
+    },
+    RoundedRect {
+        x: f32,
+        y: f32,
+        w: f32,
+        h: f32,
+        r: f32,
+        color: Color,
+    },
+    Text {
+        x: f32,
+        y: f32,
+        size: f32,
+        text: String,
+        color: Color,
+    },
+}
+
+/// Executa uma lista de comandos de desenho, na ordem. Em frames grandes cada faixa de
+/// linhas é pintada numa thread, com os comandos deslocados para a faixa.
+pub fn paint_commands(
+    frame: &mut [u8],
+    atlas: &mut FontAtlas,
+    font: &FontArc,
+    commands: &[DrawCommand],
+    sw: u32,
+    sh: u32,
+) {
+    if commands.is_empty() {
+        return;
+    }
+    for command in commands {
+        if let DrawCommand::Text { size, text, .. } = command {
+            atlas.warm(font, *size, text);
+        }
+    }
+    let atlas = &*atlas;
+    debug_assert_eq!(frame.len(), (sw * sh * 4) as usize);
+    for_each_band(frame, (sw * 4) as usize, |band, first, rows| {
+        let dy = first as f32;
+        for command in commands {
+            match command {
+                DrawCommand::Rect { x, y, w, h, color } => {
+                    draw_rect(band, *x, *y - first as i32, *w, *h, *color, sw, rows);
+                }
+                DrawCommand::RoundedRect { x, y, w, h, r, color } => {
+                    draw_rounded_rect(band, *x, *y - dy, *w, *h, *r, *color, sw, rows);
+                }
+                DrawCommand::Text {
+                    x,
+                    y,
+                    size,
+                    text,
+                    color,
+                } => {
+                    let paint = Paint::from(*color);
+                    let line = text_line(font, *size, *x, *y - dy, text, &paint);
+                    draw_text_cached(band, atlas, line, text, paint, sw, rows);
+                }
+            }
+        }
+    });
+}
+
 // ============================================================================
 // DEBUG OVERLAY
 // ============================================================================
@@ -480,3 +1083,119 @@ pub fn draw_debug_bounds(
     // Right
     draw_rect(frame, (x + w) as i32 - 1, y as i32, 1, h as i32, color, sw, sh);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const W: u32 = 64;
+    const H: u32 = 48;
+
+    fn white_frame() -> Vec<u8> {
+        vec![255; (W * H * 4) as usize]
+    }
+
+    fn at(frame: &[u8], x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * W + x) * 4) as usize;
+        [frame[i], frame[i + 1], frame[i + 2], frame[i + 3]]
+    }
+
+    const WHITE: [u8; 4] = [255; 4];
+    const BLACK: [u8; 4] = [0, 0, 0, 255];
+
+    #[test]
+    fn borders_follow_per_side_widths_and_dashes() {
+        let mut frame = white_frame();
+        let rect = Rect { x: 10.0, y: 10.0, w: 40.0, h: 30.0 };
+        let border = Border::new(0.0, Color::BLACK).sides(1.0, 4.0, 2.0, 4.0);
+        draw_border(&mut frame, rect, 0.0, &border, W, H);
+
+        // Cada lado com a sua largura, desenhada por dentro; o miolo fica intacto
+        assert_eq!(at(&frame, 30, 10), BLACK);
+        assert_eq!(at(&frame, 30, 11), WHITE);
+        assert_eq!(at(&frame, 30, 38), BLACK);
+        assert_eq!(at(&frame, 30, 37), WHITE);
+        assert_eq!((at(&frame, 13, 25), at(&frame, 14, 25)), (BLACK, WHITE));
+        assert_eq!((at(&frame, 46, 25), at(&frame, 45, 25)), (BLACK, WHITE));
+        assert_eq!(at(&frame, 9, 25), WHITE);
+
+        // Tracejada: o lado de cima alterna traços e vãos
+        let mut frame = white_frame();
+        draw_border(&mut frame, rect, 0.0, &Border::new(2.0, Color::BLACK).dashed(), W, H);
+        let top: Vec<bool> = (14..46).map(|x| at(&frame, x, 11)[0] < 128).collect();
+        assert!(top.iter().any(|&ink| ink) && top.iter().any(|&ink| !ink));
+        assert_eq!(at(&frame, 30, 25), WHITE);
+    }
+
+    #[test]
+    fn rounded_rects_clamp_radii_and_cover_each_pixel_once() {
+        // `radius.full`: vira pílula em vez de quebrar
+        let mut frame = white_frame();
+        draw_rounded_rect(&mut frame, 10.0, 10.0, 40.0, 20.0, 9999.0, Color::BLACK, W, H);
+        assert_eq!(at(&frame, 10, 10), WHITE);
+        assert_eq!(at(&frame, 49, 29), WHITE);
+        assert_eq!(at(&frame, 11, 20), BLACK);
+        assert_eq!(at(&frame, 30, 10), BLACK);
+
+        // Raio por canto: só o de cima à esquerda é arredondado
+        let mut frame = white_frame();
+        let radii = CornerRadii::new(10.0, 0.0, 0.0, 0.0);
+        draw_rounded_rect(&mut frame, 10.0, 10.0, 40.0, 20.0, radii, Color::BLACK, W, H);
+        assert_eq!(at(&frame, 10, 10), WHITE);
+        assert_eq!((at(&frame, 49, 10), at(&frame, 49, 29), at(&frame, 10, 29)), (BLACK, BLACK, BLACK));
+
+        // Translúcido: todo pixel coberto recebe a mesma cor (sem faixas misturadas duas vezes)
+        let mut frame = white_frame();
+        let gray = Color { r: 0, g: 0, b: 0, a: 128 };
+        draw_rounded_rect(&mut frame, 10.0, 10.0, 40.0, 20.0, 6.0, gray, W, H);
+        let inner = at(&frame, 30, 20);
+        assert!(inner[0] > 100 && inner[0] < 155);
+        for y in 12..28 {
+            for x in 16..44 {
+                assert_eq!(at(&frame, x, y), inner, "({x}, {y})");
+            }
+        }
+
+        // Posição sub-pixel: a coluna cortada ao meio fica com meia cobertura
+        let mut frame = white_frame();
+        draw_rounded_rect(&mut frame, 10.5, 10.0, 20.0, 20.0, 0.0, Color::BLACK, W, H);
+        assert_eq!(at(&frame, 11, 20), BLACK);
+        assert!((at(&frame, 10, 20)[0] as i32 - 128).abs() <= 2);
+    }
+
+    #[test]
+    fn blending_is_premultiplied_and_keeps_destination_alpha() {
+        let half_red = Color { r: 255, g: 0, b: 0, a: 128 };
+
+        // Alvo transparente (camada): guarda pré-multiplicado, sem forçar alpha 255
+        let mut p = [0u8; 4];
+        blend_pixel(&mut p, half_red, 1.0);
+        assert_eq!(p, [128, 0, 0, 128]);
+        // Compor essa camada sobre branco dá o mesmo que pintar direto no branco
+        let mut direct = [255u8; 4];
+        blend_pixel(&mut direct, half_red, 1.0);
+        let mut composed = [255u8; 4];
+        blend_premul(&mut composed, Premul::load(&p), BlendMode::Normal);
+        assert_eq!(composed, direct);
+        assert_eq!(direct, [255, 127, 127, 255]);
+
+        // Modos de mistura sobre um cinza médio
+        let gray = [128u8, 128, 128, 255];
+        let mut p = gray;
+        blend_pixel_with(&mut p, Color { r: 255, g: 0, b: 0, a: 255 }, 1.0, BlendMode::Multiply);
+        assert_eq!(p, [128, 0, 0, 255]);
+        let mut p = gray;
+        blend_pixel_with(&mut p, Color { r: 0, g: 0, b: 255, a: 255 }, 1.0, BlendMode::Screen);
+        assert_eq!(p, [128, 128, 255, 255]);
+
+        // Luz linear: a ida e volta preserva a cor
+        let c = Premul::from_color(Color { r: 200, g: 100, b: 30, a: 255 }, 1.0);
+        let back = c.to_linear().to_srgb();
+        for (a, b) in [(c.r, back.r), (c.g, back.g), (c.b, back.b)] {
+            assert!((a - b).abs() < 1.0 / 255.0);
+        }
+        // Metade da luz entre preto e branco é um cinza ~0.735 em sRGB, não 0.5
+        let half_light = Premul { r: 0.5, g: 0.5, b: 0.5, a: 1.0 }.to_srgb();
+        assert!((half_light.r - 0.735).abs() < 0.01);
+    }
+}
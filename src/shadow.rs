@@ -0,0 +1,303 @@
+//! Sombras de caixa (box-shadow) com blur gaussiano, spread, offset e inset.
+//! A máscara de cada combinação de tamanho/raio/parâmetros é calculada uma vez e fica em cache
+//! no `StateStore`, então listas de cards não re-borram a cada frame.
+
+use crate::layout::Rect;
+use crate::renderer::{Color, CornerRadii, blend_pixel, rounded_rect_distance};
+use std::collections::HashMap;
+
+/// Acima deste número de máscaras o cache é esvaziado (tamanhos animados geram muitas chaves)
+const MAX_CACHED_MASKS: usize = 256;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoxShadow {
+    pub offset: (f32, f32),
+    /// Raio do blur como no CSS (desvio padrão = blur / 2)
+    pub blur: f32,
+    /// Expande (ou, negativo, contrai) a forma antes do blur
+    pub spread: f32,
+    pub color: Color,
+    /// Sombra interna, desenhada dentro da caixa
+    pub inset: bool,
+}
+
+impl BoxShadow {
+    pub fn new(color: Color) -> Self {
+        Self {
+            offset: (0.0, 0.0),
+            blur: 0.0,
+            spread: 0.0,
+            color,
+            inset: false,
+        }
+    }
+
+    pub fn offset(mut self, x: f32, y: f32) -> Self {
+        self.offset = (x, y);
+        self
+    }
+
+    pub fn blur(mut self, blur: f32) -> Self {
+        self.blur = blur.max(0.0);
+        self
+    }
+
+    pub fn spread(mut self, spread: f32) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    pub fn inset(mut self) -> Self {
+        self.inset = true;
+        self
+    }
+}
+
+/// Parâmetros quantizados em meio pixel que determinam uma máscara
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct MaskKey {
+    size: (i32, i32),
+    radii: [i32; 4],
+    offset: (i32, i32),
+    blur: i32,
+    spread: i32,
+    inset: bool,
+}
+
+fn half_px(v: f32) -> i32 {
+    (v * 2.0).round() as i32
+}
+
+/// Alfa (0..1) da sombra em volta (ou dentro) de uma caixa na origem
+struct ShadowMask {
+    /// Posição do canto da máscara relativa ao canto da caixa
+    origin: (i32, i32),
+    width: usize,
+    height: usize,
+    alpha: Vec<f32>,
+}
+
+#[derive(Default)]
+pub struct ShadowCache {
+    masks: HashMap<MaskKey, ShadowMask>,
+}
+
+impl ShadowCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.masks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.masks.is_empty()
+    }
+
+    fn mask(&mut self, key: MaskKey) -> &ShadowMask {
+        if self.masks.len() >= MAX_CACHED_MASKS && !self.masks.contains_key(&key) {
+            self.masks.clear();
+        }
+        self.masks.entry(key).or_insert_with(|| build_mask(key))
+    }
+}
+
+/// Cobertura anti-aliased de um retângulo arredondado (SDF) no centro do pixel
+fn rounded_coverage(px: f32, py: f32, rect: Rect, radii: &CornerRadii) -> f32 {
+    if rect.w <= 0.0 || rect.h <= 0.0 {
+        return 0.0;
+    }
+    (0.5 - rounded_rect_distance(px, py, rect, radii)).clamp(0.0, 1.0)
+}
+
+fn build_mask(key: MaskKey) -> ShadowMask {
+    let f = |v: i32| v as f32 / 2.0;
+    let (w, h) = (f(key.size.0), f(key.size.1));
+    let (blur, spread) = (f(key.blur), f(key.spread));
+    let radii = CornerRadii::new(f(key.radii[0]), f(key.radii[1]), f(key.radii[2]), f(key.radii[3]));
+    let (ox, oy) = (f(key.offset.0), f(key.offset.1));
+    let sigma = blur / 2.0;
+    let margin = (sigma * 3.0).ceil() as i32;
+    // No inset o spread encolhe o "buraco" que projeta a sombra para dentro
+    let spread = if key.inset { -spread } else { spread };
+    let shape = Rect {
+        x: ox - spread,
+        y: oy - spread,
+        w: w + spread * 2.0,
+        h: h + spread * 2.0,
+    };
+    let grow = |r: f32| (r + spread).max(0.0);
+    let shape_radii = CornerRadii::new(
+        grow(radii.top_left),
+        grow(radii.top_right),
+        grow(radii.bottom_right),
+        grow(radii.bottom_left),
+    )
+    .clamped(shape.w, shape.h);
+    let bbox = Rect { x: 0.0, y: 0.0, w, h };
+
+    // Área coberta: em volta da forma deslocada (externa) ou a própria caixa (interna)
+    let (x0, y0, x1, y1) = if key.inset {
+        (-margin, -margin, w.ceil() as i32 + margin, h.ceil() as i32 + margin)
+    } else {
+        (
+            shape.x.floor() as i32 - margin,
+            shape.y.floor() as i32 - margin,
+            (shape.x + shape.w).ceil() as i32 + margin,
+            (shape.y + shape.h).ceil() as i32 + margin,
+        )
+    };
+    let (width, height) = ((x1 - x0).max(0) as usize, (y1 - y0).max(0) as usize);
+
+    let mut alpha = vec![0.0f32; width * height];
+    for (i, a) in alpha.iter_mut().enumerate() {
+        let px = (x0 + (i % width) as i32) as f32 + 0.5;
+        let py = (y0 + (i / width) as i32) as f32 + 0.5;
+        let c = rounded_coverage(px, py, shape, &shape_radii);
+        *a = if key.inset { 1.0 - c } else { c };
+    }
+    gaussian_blur(&mut alpha, width, height, sigma);
+
+    // Externa: escondida sob a caixa (como no CSS). Interna: só dentro da caixa.
+    for (i, a) in alpha.iter_mut().enumerate() {
+        let px = (x0 + (i % width) as i32) as f32 + 0.5;
+        let py = (y0 + (i / width) as i32) as f32 + 0.5;
+        let inside = rounded_coverage(px, py, bbox, &radii);
+        *a *= if key.inset { inside } else { 1.0 - inside };
+    }
+
+    ShadowMask {
+        origin: (x0, y0),
+        width,
+        height,
+        alpha,
+    }
+}
+
+/// Blur gaussiano separável; fora da máscara as bordas são estendidas
+fn gaussian_blur(data: &mut [f32], width: usize, height: usize, sigma: f32) {
+    if sigma < 0.25 || width == 0 || height == 0 {
+        return;
+    }
+    let radius = (sigma * 3.0).ceil() as i32;
+    let kernel: Vec<f32> = (-radius..=radius)
+        .map(|i| (-(i * i) as f32 / (2.0 * sigma * sigma)).exp())
+        .collect();
+    let total: f32 = kernel.iter().sum();
+    let kernel: Vec<f32> = kernel.iter().map(|k| k / total).collect();
+
+    let mut line = Vec::new();
+    let mut pass = |data: &mut [f32], len: usize, count: usize, index: &dyn Fn(usize, usize) -> usize| {
+        for n in 0..count {
+            line.clear();
+            line.extend((0..len).map(|i| data[index(n, i)]));
+            for i in 0..len {
+                let mut sum = 0.0;
+                for (k, weight) in kernel.iter().enumerate() {
+                    let j = (i as i32 + k as i32 - radius).clamp(0, len as i32 - 1) as usize;
+                    sum += line[j] * weight;
+                }
+                data[index(n, i)] = sum;
+            }
+        }
+    };
+    pass(data, width, height, &|row, i| row * width + i);
+    pass(data, height, width, &|col, i| i * width + col);
+}
+
+/// Desenha uma sombra para a caixa `rect` com raios `radius`
+pub fn draw_box_shadow(
+    f: &mut [u8],
+    cache: &mut ShadowCache,
+    rect: Rect,
+    radius: impl Into<CornerRadii>,
+    shadow: &BoxShadow,
+    sw: u32,
+    sh: u32,
+) {
+    if shadow.color.a == 0 {
+        return;
+    }
+    let r = radius.into().clamped(rect.w, rect.h);
+    let key = MaskKey {
+        size: (half_px(rect.w), half_px(rect.h)),
+        radii: [r.top_left, r.top_right, r.bottom_right, r.bottom_left].map(half_px),
+        offset: (half_px(shadow.offset.0), half_px(shadow.offset.1)),
+        blur: half_px(shadow.blur),
+        spread: half_px(shadow.spread),
+        inset: shadow.inset,
+    };
+    let mask = cache.mask(key);
+    // A máscara é alinhada ao pixel: a caixa é arredondada para a grade
+    let bx = rect.x.round() as i32 + mask.origin.0;
+    let by = rect.y.round() as i32 + mask.origin.1;
+
+    for my in 0..mask.height {
+        let py = by + my as i32;
+        if py < 0 || py >= sh as i32 {
+            continue;
+        }
+        for mx in 0..mask.width {
+            let px = bx + mx as i32;
+            let a = mask.alpha[my * mask.width + mx];
+            if px < 0 || px >= sw as i32 || a <= 0.002 {
+                continue;
+            }
+            let idx = ((py as u32 * sw + px as u32) * 4) as usize;
+            blend_pixel(&mut f[idx..idx + 4], shadow.color, a);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SIZE: u32 = 100;
+    const BOX: Rect = Rect {
+        x: 30.0,
+        y: 30.0,
+        w: 40.0,
+        h: 40.0,
+    };
+
+    fn red(frame: &[u8], x: u32, y: u32) -> u8 {
+        frame[((y * SIZE + x) * 4) as usize]
+    }
+
+    #[test]
+    fn blurred_shadows_fade_out_and_follow_the_offset() {
+        let mut frame = vec![255u8; (SIZE * SIZE * 4) as usize];
+        let mut cache = ShadowCache::new();
+        let shadow = BoxShadow::new(Color::BLACK).offset(0.0, 8.0).blur(12.0);
+        draw_box_shadow(&mut frame, &mut cache, BOX, 6.0, &shadow, SIZE, SIZE);
+
+        // Escondida sob a caixa, mais forte embaixo (offset) e sumindo com a distância
+        assert_eq!(red(&frame, 50, 50), 255);
+        assert!(red(&frame, 50, 72) < red(&frame, 50, 27));
+        assert!(red(&frame, 50, 72) < red(&frame, 50, 80));
+        assert!(red(&frame, 50, 80) < red(&frame, 50, 90));
+        assert_eq!(red(&frame, 5, 5), 255);
+
+        // Mesmo tamanho em outra posição reaproveita a máscara
+        let moved = Rect { x: 10.0, ..BOX };
+        draw_box_shadow(&mut frame, &mut cache, moved, 6.0, &shadow, SIZE, SIZE);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn inset_shadows_stay_inside_the_box() {
+        let mut frame = vec![255u8; (SIZE * SIZE * 4) as usize];
+        let mut cache = ShadowCache::new();
+        let shadow = BoxShadow::new(Color::BLACK).blur(8.0).spread(2.0).inset();
+        draw_box_shadow(&mut frame, &mut cache, BOX, 0.0, &shadow, SIZE, SIZE);
+
+        // Escura junto à borda, clareando até sumir no centro; nada vaza para fora
+        assert!(red(&frame, 31, 50) < red(&frame, 36, 50));
+        assert!(red(&frame, 36, 50) < red(&frame, 42, 50));
+        assert_eq!(red(&frame, 50, 50), 255);
+        assert_eq!(red(&frame, 28, 50), 255);
+        assert_eq!(red(&frame, 50, 72), 255);
+    }
+}
@@ -0,0 +1,1080 @@
+//! Ícones SVG: um subconjunto prático (path `d`, rect, circle, ellipse, line, polyline,
+//! polygon, grupos, fill/stroke, viewBox e transforms) convertido em paths vetoriais.
+//! O bitmap de cada tamanho fica em cache no `SvgAtlas`, como os glifos no `FontAtlas`.
+
+use crate::image::{Image, ImageFit, ImageHandle, ImageRegion, ImageSource, ImageStyle, Sampling, draw_image_region};
+use crate::layout::Rect;
+use crate::path::{FillRule, LineCap, LineJoin, Path, StrokeStyle, fill_path, stroke_path};
+use crate::renderer::Color;
+use glam::{Affine2, Vec2};
+use std::collections::HashMap;
+use std::f32::consts::{FRAC_PI_2, TAU};
+use std::io;
+
+/// Bitmaps guardados por (SVG, largura, altura): sobra para os ícones de uma tela em vários
+/// tamanhos, e um SVG com tamanho animado (uma chave nova por frame) não cresce sem limite
+const MAX_CACHED_BITMAPS: usize = 256;
+
+/// Elementos cujo conteúdo não é desenhado diretamente
+const SKIPPED_ELEMENTS: [&str; 13] = [
+    "defs",
+    "clipPath",
+    "mask",
+    "symbol",
+    "title",
+    "desc",
+    "metadata",
+    "style",
+    "linearGradient",
+    "radialGradient",
+    "pattern",
+    "marker",
+    "filter",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Segment {
+    MoveTo(Vec2),
+    LineTo(Vec2),
+    QuadTo(Vec2, Vec2),
+    CubicTo(Vec2, Vec2, Vec2),
+    Close,
+}
+
+impl Segment {
+    fn map(self, m: &Affine2) -> Self {
+        let p = |v: Vec2| m.transform_point2(v);
+        match self {
+            Segment::MoveTo(a) => Segment::MoveTo(p(a)),
+            Segment::LineTo(a) => Segment::LineTo(p(a)),
+            Segment::QuadTo(c, a) => Segment::QuadTo(p(c), p(a)),
+            Segment::CubicTo(c1, c2, a) => Segment::CubicTo(p(c1), p(c2), p(a)),
+            Segment::Close => Segment::Close,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SvgPaint {
+    Color(Color),
+    /// `currentColor`: a cor passada ao desenhar
+    Current,
+}
+
+/// Forma já no espaço do viewBox (transforms aplicados)
+#[derive(Clone, Debug)]
+struct Shape {
+    segments: Vec<Segment>,
+    fill: Option<SvgPaint>,
+    fill_opacity: f32,
+    fill_rule: FillRule,
+    stroke: Option<SvgPaint>,
+    stroke_opacity: f32,
+    stroke_width: f32,
+    cap: LineCap,
+    join: LineJoin,
+    miter_limit: f32,
+}
+
+/// Estilo herdado de `<svg>`/`<g>` pelos filhos
+#[derive(Clone, Copy, Debug)]
+struct Inherited {
+    transform: Affine2,
+    fill: Option<SvgPaint>,
+    fill_opacity: f32,
+    fill_rule: FillRule,
+    stroke: Option<SvgPaint>,
+    stroke_opacity: f32,
+    stroke_width: f32,
+    cap: LineCap,
+    join: LineJoin,
+    miter_limit: f32,
+}
+
+impl Default for Inherited {
+    // Valores iniciais da especificação
+    fn default() -> Self {
+        Self {
+            transform: Affine2::IDENTITY,
+            fill: Some(SvgPaint::Color(Color::BLACK)),
+            fill_opacity: 1.0,
+            fill_rule: FillRule::NonZero,
+            stroke: None,
+            stroke_opacity: 1.0,
+            stroke_width: 1.0,
+            cap: LineCap::Butt,
+            join: LineJoin::Miter,
+            miter_limit: 4.0,
+        }
+    }
+}
+
+/// Documento SVG convertido em formas vetoriais
+#[derive(Clone, Debug)]
+pub struct Svg {
+    view_box: Rect,
+    size: (f32, f32),
+    shapes: Vec<Shape>,
+}
+
+impl Svg {
+    pub fn parse(source: &str) -> io::Result<Self> {
+        let mut pos = 0;
+        let mut stack: Vec<(String, Inherited)> = Vec::new();
+        let mut skip_depth = 0usize;
+        let mut root: Option<(Rect, (f32, f32))> = None;
+        let mut shapes = Vec::new();
+
+        while let Some(tag) = next_tag(source, &mut pos)? {
+            if skip_depth > 0 {
+                if tag.end {
+                    skip_depth -= 1;
+                } else if !tag.self_closing {
+                    skip_depth += 1;
+                }
+                continue;
+            }
+            if tag.end {
+                if stack.last().is_some_and(|(name, _)| *name == tag.name) {
+                    stack.pop();
+                }
+                continue;
+            }
+            if SKIPPED_ELEMENTS.contains(&tag.name.as_str()) {
+                if !tag.self_closing {
+                    skip_depth = 1;
+                }
+                continue;
+            }
+
+            let parent = stack.last().map(|(_, s)| *s).unwrap_or_default();
+            let style = inherit(&parent, &tag)?;
+            match tag.name.as_str() {
+                "svg" | "g" | "a" => {
+                    if tag.name == "svg" && root.is_none() {
+                        root = Some(root_viewport(&tag));
+                    }
+                    if !tag.self_closing {
+                        stack.push((tag.name, style));
+                    }
+                }
+                name => {
+                    let mut segments = Vec::new();
+                    shape_segments(name, &tag, &mut segments)?;
+                    if !segments.is_empty() {
+                        shapes.push(Shape {
+                            segments: segments.iter().map(|s| s.map(&style.transform)).collect(),
+                            fill: style.fill,
+                            fill_opacity: style.fill_opacity,
+                            fill_rule: style.fill_rule,
+                            stroke: style.stroke,
+                            stroke_opacity: style.stroke_opacity,
+                            // Traço escala com o transform (média dos eixos)
+                            stroke_width: style.stroke_width * style.transform.matrix2.determinant().abs().sqrt(),
+                            cap: style.cap,
+                            join: style.join,
+                            miter_limit: style.miter_limit,
+                        });
+                    }
+                }
+            }
+        }
+
+        let (view_box, size) = root.ok_or_else(|| invalid("documento sem elemento <svg>"))?;
+        Ok(Self {
+            view_box,
+            size,
+            shapes,
+        })
+    }
+
+    pub fn decode(bytes: &[u8]) -> io::Result<Self> {
+        Self::parse(std::str::from_utf8(bytes).map_err(|_| invalid("SVG não é UTF-8"))?)
+    }
+
+    pub fn open(path: impl AsRef<std::path::Path>) -> io::Result<Self> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Tamanho natural (`width`/`height` do `<svg>` ou o do viewBox)
+    pub fn size(&self) -> (f32, f32) {
+        self.size
+    }
+
+    /// Desenha ajustando o viewBox dentro de `rect` (centralizado, proporção mantida);
+    /// `current` é a cor usada por `currentColor`
+    pub fn render(&self, f: &mut [u8], rect: Rect, current: Color, sw: u32, sh: u32) {
+        if self.view_box.w <= 0.0 || self.view_box.h <= 0.0 {
+            return;
+        }
+        let placed = ImageFit::Contain.place(self.view_box.w, self.view_box.h, rect);
+        let scale = placed.w / self.view_box.w;
+        let to_px = Affine2::from_translation(Vec2::new(placed.x, placed.y))
+            * Affine2::from_scale(Vec2::splat(scale))
+            * Affine2::from_translation(-Vec2::new(self.view_box.x, self.view_box.y));
+
+        let resolve = |paint: SvgPaint, opacity: f32| {
+            let c = match paint {
+                SvgPaint::Color(c) => c,
+                SvgPaint::Current => current,
+            };
+            c.alpha((c.a as f32 * opacity.clamp(0.0, 1.0)).round() as u8)
+        };
+        for shape in &self.shapes {
+            let path = build_path(&shape.segments, &to_px);
+            if let Some(fill) = shape.fill {
+                fill_path(f, &path, shape.fill_rule, resolve(fill, shape.fill_opacity), sw, sh);
+            }
+            if let Some(stroke) = shape.stroke
+                && shape.stroke_width > 0.0
+            {
+                let style = StrokeStyle::new(shape.stroke_width * scale)
+                    .cap(shape.cap)
+                    .join(shape.join)
+                    .miter_limit(shape.miter_limit);
+                stroke_path(f, &path, &style, resolve(stroke, shape.stroke_opacity), sw, sh);
+            }
+        }
+    }
+}
+
+fn build_path(segments: &[Segment], m: &Affine2) -> Path {
+    segments.iter().fold(Path::new(), |path, segment| match segment.map(m) {
+        Segment::MoveTo(p) => path.move_to(p.x, p.y),
+        Segment::LineTo(p) => path.line_to(p.x, p.y),
+        Segment::QuadTo(c, p) => path.quad_to(c.x, c.y, p.x, p.y),
+        Segment::CubicTo(c1, c2, p) => path.cubic_to(c1.x, c1.y, c2.x, c2.y, p.x, p.y),
+        Segment::Close => path.close(),
+    })
+}
+
+// ============================================================================
+// CACHE POR TAMANHO
+// ============================================================================
+
+/// Identifica um SVG no `SvgAtlas`; é um tipo próprio para não ser confundido com `ImageHandle`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SvgHandle(ImageHandle);
+
+/// De onde vem um SVG: arquivo, bytes em memória ou um já carregado
+#[derive(Clone, Copy, Debug)]
+pub enum SvgSource<'a> {
+    Path(&'a std::path::Path),
+    Bytes(&'a [u8]),
+    Handle(SvgHandle),
+}
+
+impl SvgSource<'_> {
+    /// Chave no atlas (mesmo hash de caminho/bytes do `ImageSource`)
+    pub fn handle(&self) -> SvgHandle {
+        match *self {
+            SvgSource::Path(path) => SvgHandle(ImageSource::Path(path).handle()),
+            SvgSource::Bytes(bytes) => SvgHandle(ImageSource::Bytes(bytes).handle()),
+            SvgSource::Handle(handle) => handle,
+        }
+    }
+}
+
+impl<'a> From<&'a str> for SvgSource<'a> {
+    fn from(path: &'a str) -> Self {
+        SvgSource::Path(std::path::Path::new(path))
+    }
+}
+
+impl<'a> From<&'a std::path::Path> for SvgSource<'a> {
+    fn from(path: &'a std::path::Path) -> Self {
+        SvgSource::Path(path)
+    }
+}
+
+impl<'a> From<&'a [u8]> for SvgSource<'a> {
+    fn from(bytes: &'a [u8]) -> Self {
+        SvgSource::Bytes(bytes)
+    }
+}
+
+/// `include_bytes!("icone.svg")`
+impl<'a, const N: usize> From<&'a [u8; N]> for SvgSource<'a> {
+    fn from(bytes: &'a [u8; N]) -> Self {
+        SvgSource::Bytes(bytes)
+    }
+}
+
+impl From<SvgHandle> for SvgSource<'_> {
+    fn from(handle: SvgHandle) -> Self {
+        SvgSource::Handle(handle)
+    }
+}
+
+/// Documentos SVG carregados e seus bitmaps por tamanho
+#[derive(Default)]
+pub struct SvgAtlas {
+    docs: HashMap<SvgHandle, Option<Svg>>,
+    bitmaps: HashMap<(SvgHandle, u32, u32), Image>,
+}
+
+impl SvgAtlas {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Carrega (uma vez) e retorna o handle; `None` se o SVG não pôde ser lido
+    pub fn load(&mut self, source: SvgSource) -> Option<SvgHandle> {
+        let handle = source.handle();
+        let entry = self.docs.entry(handle).or_insert_with(|| {
+            let result = match source {
+                SvgSource::Path(path) => Svg::open(path),
+                SvgSource::Bytes(bytes) => Svg::decode(bytes),
+                SvgSource::Handle(_) => return None,
+            };
+            result
+                .inspect_err(|e| log::warn!("Falha ao carregar SVG {:?}: {}", source, e))
+                .ok()
+        });
+        entry.as_ref().map(|_| handle)
+    }
+
+    pub fn get(&self, handle: SvgHandle) -> Option<&Svg> {
+        self.docs.get(&handle).and_then(Option::as_ref)
+    }
+
+    /// Bitmap do SVG rasterizado em `width x height` (gerado na primeira vez)
+    pub fn bitmap(&mut self, handle: SvgHandle, width: u32, height: u32) -> Option<&Image> {
+        let key = (handle, width, height);
+        if !self.bitmaps.contains_key(&key) {
+            let rect = Rect {
+                x: 0.0,
+                y: 0.0,
+                w: width as f32,
+                h: height as f32,
+            };
+            let image = rasterize(self.get(handle)?, rect, width, height)?;
+            if self.bitmaps.len() >= MAX_CACHED_BITMAPS {
+                self.bitmaps.clear();
+            }
+            self.bitmaps.insert(key, image);
+        }
+        self.bitmaps.get(&key)
+    }
+
+    /// Número de bitmaps em cache
+    pub fn len(&self) -> usize {
+        self.bitmaps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bitmaps.is_empty()
+    }
+}
+
+/// Rasteriza `svg` ajustado a `rect` num bitmap `width x height` (`None` se não couber na memória)
+fn rasterize(svg: &Svg, rect: Rect, width: u32, height: u32) -> Option<Image> {
+    let len = (width as usize).checked_mul(height as usize)?.checked_mul(4)?;
+    let mut pixels = vec![0u8; len];
+    svg.render(&mut pixels, rect, Color::BLACK, width, height);
+    Image::from_premultiplied(width, height, pixels).ok()
+}
+
+/// Desenha o SVG `handle` em `rect` (alinhado ao pixel); `color` recolore o ícone inteiro
+pub fn draw_svg(
+    f: &mut [u8],
+    atlas: &mut SvgAtlas,
+    handle: SvgHandle,
+    rect: Rect,
+    color: Option<Color>,
+    sw: u32,
+    sh: u32,
+) {
+    let (width, height) = (rect.w.round(), rect.h.round());
+    if width < 1.0 || height < 1.0 {
+        return;
+    }
+    let dest = Rect {
+        x: rect.x.round(),
+        y: rect.y.round(),
+        w: width,
+        h: height,
+    };
+    let style = ImageStyle {
+        fit: ImageFit::Fill,
+        sampling: Sampling::Nearest,
+        tint: color,
+    };
+    let whole = |w: f32, h: f32| Rect { x: 0.0, y: 0.0, w, h };
+
+    // Maior que o frame (p.ex. um `width` natural enorme): só a parte visível é rasterizada,
+    // sem passar pelo cache
+    if width > sw as f32 || height > sh as f32 {
+        let visible = dest.intersect(&whole(sw as f32, sh as f32));
+        let Some(svg) = atlas.get(handle) else {
+            return;
+        };
+        if visible.w < 1.0 || visible.h < 1.0 {
+            return;
+        }
+        let shifted = Rect {
+            x: dest.x - visible.x,
+            y: dest.y - visible.y,
+            ..dest
+        };
+        if let Some(window) = rasterize(svg, shifted, visible.w as u32, visible.h as u32) {
+            let region = ImageRegion::new(whole(visible.w, visible.h), visible);
+            draw_image_region(f, &window, &region, &style, sw, sh);
+        }
+        return;
+    }
+
+    let Some(bitmap) = atlas.bitmap(handle, width as u32, height as u32) else {
+        return;
+    };
+    draw_image_region(f, bitmap, &ImageRegion::new(whole(width, height), dest), &style, sw, sh);
+}
+
+// ============================================================================
+// XML
+// ============================================================================
+
+struct Tag {
+    name: String,
+    /// Atributos na ordem do documento; declarações de `style` vêm por último (vencem)
+    attrs: Vec<(String, String)>,
+    end: bool,
+    self_closing: bool,
+}
+
+impl Tag {
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs
+            .iter()
+            .rev()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn number(&self, name: &str) -> io::Result<f32> {
+        Ok(self.attr(name).map(length).transpose()?.unwrap_or(0.0))
+    }
+}
+
+fn invalid(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+/// Próxima tag a partir de `pos`, pulando texto, comentários, `<?...?>` e `<!...>`
+fn next_tag(src: &str, pos: &mut usize) -> io::Result<Option<Tag>> {
+    loop {
+        let Some(start) = src[*pos..].find('<').map(|i| *pos + i) else {
+            return Ok(None);
+        };
+        let rest = &src[start..];
+        let skip_until = |end: &str| {
+            rest.find(end)
+                .map(|i| start + i + end.len())
+                .ok_or_else(|| invalid("XML incompleto"))
+        };
+        if rest.starts_with("<!--") {
+            *pos = skip_until("-->")?;
+        } else if rest.starts_with("<![CDATA[") {
+            *pos = skip_until("]]>")?;
+        } else if rest.starts_with("<?") {
+            *pos = skip_until("?>")?;
+        } else if rest.starts_with("<!") {
+            *pos = skip_until(">")?;
+        } else {
+            let close = skip_until(">")?;
+            *pos = close;
+            return parse_tag(&src[start + 1..close - 1]).map(Some);
+        }
+    }
+}
+
+fn parse_tag(body: &str) -> io::Result<Tag> {
+    let (end, body) = match body.strip_prefix('/') {
+        Some(rest) => (true, rest),
+        None => (false, body),
+    };
+    let (self_closing, body) = match body.strip_suffix('/') {
+        Some(rest) => (true, rest),
+        None => (false, body),
+    };
+    let name_end = body.find(|c: char| c.is_whitespace()).unwrap_or(body.len());
+    let name = body[..name_end].to_string();
+    // Prefixos de namespace (`svg:path`) são ignorados
+    let name = name.rsplit(':').next().unwrap_or_default().to_string();
+
+    let mut attrs = Vec::new();
+    let mut rest = body[name_end..].trim_start();
+    while !rest.is_empty() {
+        let eq = rest.find('=').ok_or_else(|| invalid("atributo sem valor"))?;
+        let key = rest[..eq].trim().to_string();
+        let after = rest[eq + 1..].trim_start();
+        let quote = after.chars().next().ok_or_else(|| invalid("atributo sem valor"))?;
+        if quote != '"' && quote != '\'' {
+            return Err(invalid("valor de atributo sem aspas"));
+        }
+        let value_end = after[1..].find(quote).ok_or_else(|| invalid("aspas não fechadas"))? + 1;
+        attrs.push((key, after[1..value_end].to_string()));
+        rest = after[value_end + 1..].trim_start();
+    }
+
+    let style: Vec<(String, String)> = attrs
+        .iter()
+        .filter(|(key, _)| key == "style")
+        .flat_map(|(_, value)| value.split(';'))
+        .filter_map(|decl| decl.split_once(':'))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect();
+    attrs.extend(style);
+
+    Ok(Tag {
+        name,
+        attrs,
+        end,
+        self_closing,
+    })
+}
+
+// ============================================================================
+// ESTILO E ATRIBUTOS
+// ============================================================================
+
+fn root_viewport(tag: &Tag) -> (Rect, (f32, f32)) {
+    let dimension = |name: &str| tag.attr(name).and_then(|v| length(v).ok());
+    let view_box = tag.attr("viewBox").and_then(|v| numbers(v).ok()).and_then(|v| match v[..] {
+        [x, y, w, h] => Some(Rect { x, y, w, h }),
+        _ => None,
+    });
+    let (width, height) = (dimension("width"), dimension("height"));
+    let view_box = view_box.unwrap_or(Rect {
+        x: 0.0,
+        y: 0.0,
+        w: width.unwrap_or(0.0),
+        h: height.unwrap_or(0.0),
+    });
+    (view_box, (width.unwrap_or(view_box.w), height.unwrap_or(view_box.h)))
+}
+
+fn inherit(parent: &Inherited, tag: &Tag) -> io::Result<Inherited> {
+    let mut style = *parent;
+    if let Some(t) = tag.attr("transform") {
+        style.transform = parent.transform * parse_transform(t)?;
+    }
+    if let Some(paint) = tag.attr("fill").and_then(parse_paint) {
+        style.fill = paint;
+    }
+    if let Some(paint) = tag.attr("stroke").and_then(parse_paint) {
+        style.stroke = paint;
+    }
+    let opacity = |name: &str| tag.attr(name).and_then(|v| v.trim().parse::<f32>().ok());
+    // `opacity` de grupo é aproximada multiplicando nas tintas dos filhos
+    let group = opacity("opacity").unwrap_or(1.0);
+    style.fill_opacity *= opacity("fill-opacity").unwrap_or(1.0) * group;
+    style.stroke_opacity *= opacity("stroke-opacity").unwrap_or(1.0) * group;
+    if let Some(width) = tag.attr("stroke-width") {
+        style.stroke_width = length(width)?;
+    }
+    if let Some(limit) = opacity("stroke-miterlimit") {
+        style.miter_limit = limit;
+    }
+    match tag.attr("fill-rule") {
+        Some("evenodd") => style.fill_rule = FillRule::EvenOdd,
+        Some("nonzero") => style.fill_rule = FillRule::NonZero,
+        _ => {}
+    }
+    match tag.attr("stroke-linecap") {
+        Some("round") => style.cap = LineCap::Round,
+        Some("square") => style.cap = LineCap::Square,
+        Some("butt") => style.cap = LineCap::Butt,
+        _ => {}
+    }
+    match tag.attr("stroke-linejoin") {
+        Some("round") => style.join = LineJoin::Round,
+        Some("bevel") => style.join = LineJoin::Bevel,
+        Some("miter") | Some("miter-clip") | Some("arcs") => style.join = LineJoin::Miter,
+        _ => {}
+    }
+    Ok(style)
+}
+
+/// `Some(None)` = "none"; `None` = valor não suportado (mantém o herdado)
+fn parse_paint(value: &str) -> Option<Option<SvgPaint>> {
+    let value = value.trim();
+    let rgb = |r: u8, g: u8, b: u8| Some(Some(SvgPaint::Color(Color { r, g, b, a: 255 })));
+    match value {
+        "none" | "transparent" => return Some(None),
+        "currentColor" => return Some(Some(SvgPaint::Current)),
+        "black" => return rgb(0, 0, 0),
+        "white" => return rgb(255, 255, 255),
+        "red" => return rgb(255, 0, 0),
+        "green" => return rgb(0, 128, 0),
+        "blue" => return rgb(0, 0, 255),
+        "yellow" => return rgb(255, 255, 0),
+        "orange" => return rgb(255, 165, 0),
+        "purple" => return rgb(128, 0, 128),
+        "gray" | "grey" => return rgb(128, 128, 128),
+        _ => {}
+    }
+    if let Some(hex) = value.strip_prefix('#') {
+        let digit = |i: usize| u8::from_str_radix(hex.get(i..i + 1)?, 16).ok();
+        let byte = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+        let (r, g, b, a) = match hex.len() {
+            3 => (digit(0)? * 17, digit(1)? * 17, digit(2)? * 17, 255),
+            6 => (byte(0)?, byte(2)?, byte(4)?, 255),
+            8 => (byte(0)?, byte(2)?, byte(4)?, byte(6)?),
+            _ => return None,
+        };
+        return Some(Some(SvgPaint::Color(Color { r, g, b, a })));
+    }
+    if let Some(args) = value.strip_prefix("rgb(").and_then(|v| v.strip_suffix(')')) {
+        let channels = numbers(args).ok()?;
+        if let [r, g, b] = channels[..] {
+            return rgb(r as u8, g as u8, b as u8);
+        }
+    }
+    None
+}
+
+fn parse_transform(value: &str) -> io::Result<Affine2> {
+    let mut m = Affine2::IDENTITY;
+    let mut rest = value;
+    loop {
+        rest = rest.trim_start_matches(|c: char| c.is_whitespace() || c == ',');
+        if rest.is_empty() {
+            return Ok(m);
+        }
+        let open = rest.find('(').ok_or_else(|| invalid("transform inválido"))?;
+        let close = open + rest[open..].find(')').ok_or_else(|| invalid("transform inválido"))?;
+        let args = numbers(&rest[open + 1..close])?;
+        let t = match (rest[..open].trim(), args.as_slice()) {
+            ("matrix", &[a, b, c, d, e, f]) => Affine2::from_cols_array(&[a, b, c, d, e, f]),
+            ("translate", &[x]) => Affine2::from_translation(Vec2::new(x, 0.0)),
+            ("translate", &[x, y]) => Affine2::from_translation(Vec2::new(x, y)),
+            ("scale", &[s]) => Affine2::from_scale(Vec2::splat(s)),
+            ("scale", &[x, y]) => Affine2::from_scale(Vec2::new(x, y)),
+            ("rotate", &[a]) => Affine2::from_angle(a.to_radians()),
+            ("rotate", &[a, x, y]) => {
+                let c = Vec2::new(x, y);
+                Affine2::from_translation(c) * Affine2::from_angle(a.to_radians()) * Affine2::from_translation(-c)
+            }
+            ("skewX", &[a]) => Affine2::from_cols_array(&[1.0, 0.0, a.to_radians().tan(), 1.0, 0.0, 0.0]),
+            ("skewY", &[a]) => Affine2::from_cols_array(&[1.0, a.to_radians().tan(), 0.0, 1.0, 0.0, 0.0]),
+            _ => return Err(invalid("transform não suportado")),
+        };
+        m *= t;
+        rest = &rest[close + 1..];
+    }
+}
+
+/// Comprimento em unidades do usuário (`px` aceito; outras unidades não)
+fn length(value: &str) -> io::Result<f32> {
+    let value = value.trim();
+    value
+        .strip_suffix("px")
+        .unwrap_or(value)
+        .trim()
+        .parse()
+        .map_err(|_| invalid("comprimento inválido"))
+}
+
+fn numbers(value: &str) -> io::Result<Vec<f32>> {
+    let mut scanner = Scanner::new(value);
+    let mut out = Vec::new();
+    while !scanner.at_end() {
+        out.push(scanner.number()?);
+    }
+    Ok(out)
+}
+
+// ============================================================================
+// GEOMETRIA
+// ============================================================================
+
+fn shape_segments(name: &str, tag: &Tag, out: &mut Vec<Segment>) -> io::Result<()> {
+    let n = |attr: &str| tag.number(attr);
+    match name {
+        "path" => parse_path_data(tag.attr("d").unwrap_or_default(), out)?,
+        "rect" => {
+            let (x, y, w, h) = (n("x")?, n("y")?, n("width")?, n("height")?);
+            if w <= 0.0 || h <= 0.0 {
+                return Ok(());
+            }
+            // rx/ry ausente copia o outro; ambos limitados à metade do lado
+            let (rx, ry) = match (tag.attr("rx"), tag.attr("ry")) {
+                (None, None) => (0.0, 0.0),
+                (Some(_), None) => (n("rx")?, n("rx")?),
+                (None, Some(_)) => (n("ry")?, n("ry")?),
+                (Some(_), Some(_)) => (n("rx")?, n("ry")?),
+            };
+            let (rx, ry) = (rx.clamp(0.0, w / 2.0), ry.clamp(0.0, h / 2.0));
+            let p = Vec2::new;
+            if rx <= 0.0 || ry <= 0.0 {
+                out.extend([
+                    Segment::MoveTo(p(x, y)),
+                    Segment::LineTo(p(x + w, y)),
+                    Segment::LineTo(p(x + w, y + h)),
+                    Segment::LineTo(p(x, y + h)),
+                    Segment::Close,
+                ]);
+            } else {
+                let corner = |out: &mut Vec<Segment>, from: Vec2, to: Vec2| {
+                    arc_to_cubics(from, EllipticArc::clockwise(rx, ry), to, out);
+                };
+                out.push(Segment::MoveTo(p(x + rx, y)));
+                out.push(Segment::LineTo(p(x + w - rx, y)));
+                corner(out, p(x + w - rx, y), p(x + w, y + ry));
+                out.push(Segment::LineTo(p(x + w, y + h - ry)));
+                corner(out, p(x + w, y + h - ry), p(x + w - rx, y + h));
+                out.push(Segment::LineTo(p(x + rx, y + h)));
+                corner(out, p(x + rx, y + h), p(x, y + h - ry));
+                out.push(Segment::LineTo(p(x, y + ry)));
+                corner(out, p(x, y + ry), p(x + rx, y));
+                out.push(Segment::Close);
+            }
+        }
+        "circle" | "ellipse" => {
+            let (cx, cy) = (n("cx")?, n("cy")?);
+            let (rx, ry) = if name == "circle" { (n("r")?, n("r")?) } else { (n("rx")?, n("ry")?) };
+            if rx <= 0.0 || ry <= 0.0 {
+                return Ok(());
+            }
+            let (left, right) = (Vec2::new(cx - rx, cy), Vec2::new(cx + rx, cy));
+            out.push(Segment::MoveTo(right));
+            arc_to_cubics(right, EllipticArc::clockwise(rx, ry), left, out);
+            arc_to_cubics(left, EllipticArc::clockwise(rx, ry), right, out);
+            out.push(Segment::Close);
+        }
+        "line" => {
+            out.push(Segment::MoveTo(Vec2::new(n("x1")?, n("y1")?)));
+            out.push(Segment::LineTo(Vec2::new(n("x2")?, n("y2")?)));
+        }
+        "polyline" | "polygon" => {
+            let points = numbers(tag.attr("points").unwrap_or_default())?;
+            for (i, pair) in points.chunks_exact(2).enumerate() {
+                let p = Vec2::new(pair[0], pair[1]);
+                out.push(if i == 0 { Segment::MoveTo(p) } else { Segment::LineTo(p) });
+            }
+            if name == "polygon" && !out.is_empty() {
+                out.push(Segment::Close);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Leitor de números da sintaxe SVG (`10-5`, `.5.5`, `1e-3`, flags `01` de arcos)
+struct Scanner<'a> {
+    s: &'a [u8],
+    i: usize,
+}
+
+impl<'a> Scanner<'a> {
+    fn new(s: &'a str) -> Self {
+        Self { s: s.as_bytes(), i: 0 }
+    }
+
+    fn skip_separators(&mut self) {
+        while self.i < self.s.len() && (self.s[self.i].is_ascii_whitespace() || self.s[self.i] == b',') {
+            self.i += 1;
+        }
+    }
+
+    fn at_end(&mut self) -> bool {
+        self.skip_separators();
+        self.i >= self.s.len()
+    }
+
+    /// Comando na posição atual, se houver
+    fn command(&mut self) -> Option<u8> {
+        self.skip_separators();
+        let c = *self.s.get(self.i)?;
+        if c.is_ascii_alphabetic() {
+            self.i += 1;
+            Some(c)
+        } else {
+            None
+        }
+    }
+
+    fn number(&mut self) -> io::Result<f32> {
+        self.skip_separators();
+        let start = self.i;
+        let digits = |s: &mut Self| {
+            while s.i < s.s.len() && s.s[s.i].is_ascii_digit() {
+                s.i += 1;
+            }
+        };
+        if matches!(self.s.get(self.i), Some(b'+' | b'-')) {
+            self.i += 1;
+        }
+        digits(self);
+        if self.s.get(self.i) == Some(&b'.') {
+            self.i += 1;
+            digits(self);
+        }
+        if matches!(self.s.get(self.i), Some(b'e' | b'E')) {
+            self.i += 1;
+            if matches!(self.s.get(self.i), Some(b'+' | b'-')) {
+                self.i += 1;
+            }
+            digits(self);
+        }
+        std::str::from_utf8(&self.s[start..self.i])
+            .ok()
+            .and_then(|text| text.parse().ok())
+            .ok_or_else(|| invalid("número inválido em SVG"))
+    }
+
+    fn point(&mut self) -> io::Result<Vec2> {
+        Ok(Vec2::new(self.number()?, self.number()?))
+    }
+
+    fn flag(&mut self) -> io::Result<bool> {
+        self.skip_separators();
+        match self.s.get(self.i) {
+            Some(b'0') => {
+                self.i += 1;
+                Ok(false)
+            }
+            Some(b'1') => {
+                self.i += 1;
+                Ok(true)
+            }
+            _ => Err(invalid("flag de arco inválida")),
+        }
+    }
+}
+
+/// Converte o atributo `d` (todos os comandos, absolutos e relativos) em segmentos
+fn parse_path_data(d: &str, out: &mut Vec<Segment>) -> io::Result<()> {
+    let mut s = Scanner::new(d);
+    let mut command: Option<u8> = None;
+    let (mut current, mut start) = (Vec2::ZERO, Vec2::ZERO);
+    // Último ponto de controle, para os comandos suaves S/T
+    let mut last_cubic: Option<Vec2> = None;
+    let mut last_quad: Option<Vec2> = None;
+    let mut open = false;
+
+    while !s.at_end() {
+        if let Some(c) = s.command() {
+            command = Some(c);
+        }
+        let c = command.ok_or_else(|| invalid("path sem comando inicial"))?;
+        let relative = c.is_ascii_lowercase();
+        let base = if relative { current } else { Vec2::ZERO };
+        let upper = c.to_ascii_uppercase();
+
+        // Desenho sem M depois de um Z (ou no início) recomeça do início do subpath
+        if upper != b'M' && upper != b'Z' && !open {
+            out.push(Segment::MoveTo(current));
+            start = current;
+            open = true;
+        }
+
+        let (mut cubic, mut quad) = (None, None);
+        match upper {
+            b'M' => {
+                current = base + s.point()?;
+                start = current;
+                out.push(Segment::MoveTo(current));
+                open = true;
+                // Pares seguintes são linhas
+                command = Some(if relative { b'l' } else { b'L' });
+            }
+            b'L' => {
+                current = base + s.point()?;
+                out.push(Segment::LineTo(current));
+            }
+            b'H' => {
+                current.x = base.x + s.number()?;
+                out.push(Segment::LineTo(current));
+            }
+            b'V' => {
+                current.y = base.y + s.number()?;
+                out.push(Segment::LineTo(current));
+            }
+            b'C' | b'S' => {
+                let c1 = if upper == b'C' {
+                    base + s.point()?
+                } else {
+                    last_cubic.map_or(current, |c| current * 2.0 - c)
+                };
+                let c2 = base + s.point()?;
+                current = base + s.point()?;
+                out.push(Segment::CubicTo(c1, c2, current));
+                cubic = Some(c2);
+            }
+            b'Q' | b'T' => {
+                let ctrl = if upper == b'Q' {
+                    base + s.point()?
+                } else {
+                    last_quad.map_or(current, |c| current * 2.0 - c)
+                };
+                current = base + s.point()?;
+                out.push(Segment::QuadTo(ctrl, current));
+                quad = Some(ctrl);
+            }
+            b'A' => {
+                let arc = EllipticArc {
+                    rx: s.number()?,
+                    ry: s.number()?,
+                    rotation: s.number()?,
+                    large: s.flag()?,
+                    sweep: s.flag()?,
+                };
+                let to = base + s.point()?;
+                arc_to_cubics(current, arc, to, out);
+                current = to;
+            }
+            b'Z' => {
+                out.push(Segment::Close);
+                current = start;
+                open = false;
+                // Z não tem argumentos: números depois dele são erro
+                command = None;
+            }
+            _ => return Err(invalid("comando de path não suportado")),
+        }
+        last_cubic = cubic;
+        last_quad = quad;
+    }
+    Ok(())
+}
+
+/// Parâmetros do comando `A`: raios, rotação do eixo x (graus) e as flags large-arc e sweep
+#[derive(Clone, Copy, Debug)]
+struct EllipticArc {
+    rx: f32,
+    ry: f32,
+    rotation: f32,
+    large: bool,
+    sweep: bool,
+}
+
+impl EllipticArc {
+    /// Arco curto no sentido horário, sem rotação (cantos de rect, metades de elipse)
+    fn clockwise(rx: f32, ry: f32) -> Self {
+        Self {
+            rx,
+            ry,
+            rotation: 0.0,
+            large: false,
+            sweep: true,
+        }
+    }
+}
+
+/// Arco elíptico do SVG (parametrização por extremos) em cúbicas de até 90°
+fn arc_to_cubics(from: Vec2, arc: EllipticArc, to: Vec2, out: &mut Vec<Segment>) {
+    if from == to {
+        return;
+    }
+    let EllipticArc {
+        rotation, large, sweep, ..
+    } = arc;
+    let (mut rx, mut ry) = (arc.rx.abs(), arc.ry.abs());
+    if rx == 0.0 || ry == 0.0 {
+        out.push(Segment::LineTo(to));
+        return;
+    }
+    let (sin, cos) = rotation.to_radians().sin_cos();
+    let rotate = |v: Vec2| Vec2::new(cos * v.x - sin * v.y, sin * v.x + cos * v.y);
+
+    // Ponto inicial no sistema do arco (especificação SVG, apêndice F.6.5)
+    let half = (from - to) / 2.0;
+    let p = Vec2::new(cos * half.x + sin * half.y, -sin * half.x + cos * half.y);
+    let lambda = (p.x * p.x) / (rx * rx) + (p.y * p.y) / (ry * ry);
+    if lambda > 1.0 {
+        rx *= lambda.sqrt();
+        ry *= lambda.sqrt();
+    }
+    let num = rx * rx * ry * ry - rx * rx * p.y * p.y - ry * ry * p.x * p.x;
+    let den = rx * rx * p.y * p.y + ry * ry * p.x * p.x;
+    let mut k = (num / den).max(0.0).sqrt();
+    if large == sweep {
+        k = -k;
+    }
+    let center_p = Vec2::new(k * rx * p.y / ry, -k * ry * p.x / rx);
+    let center = rotate(center_p) + (from + to) / 2.0;
+
+    let angle = |u: Vec2, v: Vec2| (u.x * v.y - u.y * v.x).atan2(u.dot(v));
+    let u = Vec2::new((p.x - center_p.x) / rx, (p.y - center_p.y) / ry);
+    let v = Vec2::new((-p.x - center_p.x) / rx, (-p.y - center_p.y) / ry);
+    let theta = angle(Vec2::X, u);
+    let mut delta = angle(u, v);
+    if !sweep && delta > 0.0 {
+        delta -= TAU;
+    } else if sweep && delta < 0.0 {
+        delta += TAU;
+    }
+
+    let n = (delta.abs() / FRAC_PI_2).ceil().max(1.0) as usize;
+    let step = delta / n as f32;
+    let handle = 4.0 / 3.0 * (step / 4.0).tan();
+    let point = |t: f32| center + rotate(Vec2::new(rx * t.cos(), ry * t.sin()));
+    let tangent = |t: f32| rotate(Vec2::new(-rx * t.sin(), ry * t.cos()));
+    for i in 0..n {
+        let (t0, t1) = (theta + step * i as f32, theta + step * (i + 1) as f32);
+        let end = if i + 1 == n { to } else { point(t1) };
+        out.push(Segment::CubicTo(
+            point(t0) + tangent(t0) * handle,
+            end - tangent(t1) * handle,
+            end,
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn document(body: &str) -> String {
+        format!(r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 10 10">{body}</svg>"#)
+    }
+
+    #[test]
+    fn malformed_input_is_an_error() {
+        let cases = [
+            // transform
+            r#"<g transform=")("><rect width="1" height="1"/></g>"#,
+            r#"<g transform="rotate(45"><rect width="1" height="1"/></g>"#,
+            r#"<g transform="scale(1) )("><rect width="1" height="1"/></g>"#,
+            r#"<g transform="skewX(1, 2)"><rect width="1" height="1"/></g>"#,
+            // d
+            r#"<path d="10 10"/>"#,
+            r#"<path d="M 0 0 L"/>"#,
+            r#"<path d="M 0 0 A 5 5 0 2 0 10 10"/>"#,
+            r#"<path d="M 0 0 Z 5 5"/>"#,
+            r#"<path d="M 1e 0"/>"#,
+            // atributos
+            r#"<rect width=10 height="1"/>"#,
+            r#"<rect width="10 height="1"/>"#,
+            r#"<rect width="ten" height="1"/>"#,
+            r#"<rect width"#,
+        ];
+        for body in cases {
+            assert!(Svg::parse(&document(body)).is_err(), "{body}");
+        }
+        assert!(Svg::parse(r#"<rect width="1" height="1"/>"#).is_err());
+    }
+
+    #[test]
+    fn huge_natural_size_rasterizes_only_the_visible_part() {
+        let source = br#"<svg width="100000" height="100000" viewBox="0 0 10 10"><rect width="10" height="10"/></svg>"#;
+        let mut atlas = SvgAtlas::new();
+        let handle = atlas.load(SvgSource::from(source)).expect("SVG válido");
+        assert_eq!(atlas.get(handle).map(Svg::size), Some((100000.0, 100000.0)));
+
+        let (sw, sh) = (32, 16);
+        let mut frame = vec![0u8; (sw * sh * 4) as usize];
+        let rect = Rect {
+            x: -50.0,
+            y: -50.0,
+            w: 100000.0,
+            h: 100000.0,
+        };
+        draw_svg(&mut frame, &mut atlas, handle, rect, None, sw, sh);
+        assert!(frame.chunks_exact(4).all(|p| p[3] == 255));
+        assert!(atlas.is_empty());
+    }
+}
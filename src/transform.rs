@@ -0,0 +1,129 @@
+//! Transformações afins 2D (glam) para subárvores: afetam pintura e hit-testing.
+//! O layout continua usando o retângulo sem transformação, como `transform` no CSS.
+
+use crate::core::InputState;
+use crate::layout::Rect;
+use glam::{Affine2, Vec2};
+
+/// Abaixo deste |determinante| a afim achata o conteúdo (escala zero) e não tem inversa útil
+const MIN_DETERMINANT: f32 = 1e-6;
+
+/// Afim aplicada em torno de um ponto fixo dado como fração do conteúdo
+/// (`(0.5, 0.5)` = centro, `(0, 0)` = canto superior esquerdo)
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Transform {
+    pub affine: Affine2,
+    pub origin: Vec2,
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform {
+        affine: Affine2::IDENTITY,
+        origin: Vec2::new(0.5, 0.5),
+    };
+
+    pub fn from_affine(affine: Affine2) -> Self {
+        Self {
+            affine,
+            ..Self::IDENTITY
+        }
+    }
+
+    pub fn translate(x: f32, y: f32) -> Self {
+        Self::from_affine(Affine2::from_translation(Vec2::new(x, y)))
+    }
+
+    pub fn scale(s: f32) -> Self {
+        Self::from_affine(Affine2::from_scale(Vec2::splat(s)))
+    }
+
+    /// Rotação em radianos (sentido horário na tela, com y para baixo)
+    pub fn rotate(radians: f32) -> Self {
+        Self::from_affine(Affine2::from_angle(radians))
+    }
+
+    /// Ponto fixo como fração do conteúdo
+    pub fn around(mut self, fx: f32, fy: f32) -> Self {
+        self.origin = Vec2::new(fx, fy);
+        self
+    }
+
+    /// Aplica `next` depois desta transformação
+    pub fn then(mut self, next: Transform) -> Self {
+        self.affine = next.affine * self.affine;
+        self
+    }
+
+    /// Matriz em coordenadas de tela para o conteúdo que ocupa `rect`
+    pub fn to_screen(&self, rect: Rect) -> Affine2 {
+        let pivot = Vec2::new(rect.x + rect.w * self.origin.x, rect.y + rect.h * self.origin.y);
+        Affine2::from_translation(pivot) * self.affine * Affine2::from_translation(-pivot)
+    }
+
+    /// Inversa de `to_screen(rect)`; `None` quando a afim é singular
+    pub fn to_local(&self, rect: Rect) -> Option<Affine2> {
+        let to_screen = self.to_screen(rect);
+        (to_screen.matrix2.determinant().abs() > MIN_DETERMINANT).then(|| to_screen.inverse())
+    }
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Self::IDENTITY
+    }
+}
+
+/// Bounding box alinhada aos eixos de `rect` transformado
+pub fn transformed_bounds(affine: &Affine2, rect: Rect) -> Rect {
+    let corners = [
+        Vec2::new(rect.x, rect.y),
+        Vec2::new(rect.x + rect.w, rect.y),
+        Vec2::new(rect.x, rect.y + rect.h),
+        Vec2::new(rect.x + rect.w, rect.y + rect.h),
+    ]
+    .map(|p| affine.transform_point2(p));
+    let min = corners.iter().fold(Vec2::splat(f32::INFINITY), |a, p| a.min(*p));
+    let max = corners.iter().fold(Vec2::splat(f32::NEG_INFINITY), |a, p| a.max(*p));
+    Rect {
+        x: min.x,
+        y: min.y,
+        w: max.x - min.x,
+        h: max.y - min.y,
+    }
+}
+
+/// Input visto de dentro da subárvore: posições do ponteiro levadas para o espaço local
+pub fn to_local_input(input: &InputState, to_local: &Affine2) -> InputState {
+    let map = |(x, y): (f32, f32)| {
+        let p = to_local.transform_point2(Vec2::new(x, y));
+        (p.x, p.y)
+    };
+    let mut local = input.clone();
+    local.mouse_pos = map(input.mouse_pos);
+    local.press_pos = input.press_pos.map(map);
+    local
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RECT: Rect = Rect { x: 10.0, y: 20.0, w: 100.0, h: 40.0 };
+
+    #[test]
+    fn local_undoes_screen() {
+        let t = Transform::rotate(0.3).then(Transform::scale(2.0)).around(0.0, 1.0);
+        let to_local = t.to_local(RECT).expect("afim inversível");
+        let p = Vec2::new(37.0, 51.0);
+        let back = to_local.transform_point2(t.to_screen(RECT).transform_point2(p));
+        assert!((back - p).length() < 1e-3);
+    }
+
+    #[test]
+    fn singular_transforms_have_no_local_space() {
+        assert!(Transform::scale(0.0).to_local(RECT).is_none());
+        let squash = Transform::from_affine(Affine2::from_scale(Vec2::new(1.0, 0.0)));
+        assert!(squash.to_local(RECT).is_none());
+        assert!(Transform::scale(f32::NAN).to_local(RECT).is_none());
+    }
+}
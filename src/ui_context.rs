@@ -1,11 +1,19 @@
-use crate::config::{components, Theme};
-use crate::core::{InputState, StateStore};
+use crate::animation::{Animatable, AnimationCurve, AnimationSpec, AnimationTrack, Easing, Timeline};
+use crate::commands::CommandScope;
+use crate::config::{components, font_size, render, spacing, Theme};
+use crate::core::{InputState, PointerButton, StateStore};
+use crate::drag::{DragResponse, DropResponse};
+use crate::image::{ImageHandle, ImageSource, ImageStyle, NineSlice, draw_image, draw_nine_slice};
 use crate::layout::Rect;
-use crate::renderer::FontAtlas;
+use crate::renderer::{Color, CornerRadii, DrawCommand, FontAtlas};
+use crate::shadow::{BoxShadow, draw_box_shadow};
+use crate::svg::{SvgHandle, SvgSource, draw_svg};
 use ab_glyph::FontArc;
 use std::cell::RefCell;
 use std::rc::Rc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
+use winit::event::VirtualKeyCode;
+use winit::window::CursorIcon;
 
 // ============================================================================
 // ANIMATION SYSTEM
@@ -20,28 +28,32 @@ pub struct AnimationState {
 }
 
 impl AnimationState {
-    pub fn new(start: f32, target: f32, duration_ms: f64) -> Self {
+    pub fn new(start: f32, target: f32, duration_ms: f64, now: Instant) -> Self {
         Self {
             start_value: start,
             target_value: target,
-            start_time: Instant::now(),
+            start_time: now,
             duration_ms,
         }
     }
 
-    pub fn value(&self) -> f32 {
-        let elapsed = self.start_time.elapsed().as_secs_f64() * 1000.0;
+    fn elapsed_ms(&self, now: Instant) -> f64 {
+        now.saturating_duration_since(self.start_time).as_secs_f64() * 1000.0
+    }
+
+    /// Valor no instante `now` (normalmente `Ui::now()`, o relógio do frame)
+    pub fn value(&self, now: Instant) -> f32 {
+        let elapsed = self.elapsed_ms(now);
         if elapsed >= self.duration_ms {
             return self.target_value;
         }
         let t = (elapsed / self.duration_ms) as f32;
-        // Ease-out cubic
-        let eased = 1.0 - (1.0 - t).powi(3);
+        let eased = Easing::CubicOut.apply(t);
         self.start_value + (self.target_value - self.start_value) * eased
     }
 
-    pub fn is_complete(&self) -> bool {
-        self.start_time.elapsed().as_secs_f64() * 1000.0 >= self.duration_ms
+    pub fn is_complete(&self, now: Instant) -> bool {
+        self.elapsed_ms(now) >= self.duration_ms
     }
 }
 
@@ -106,37 +118,92 @@ impl ScrollState {
 
 #[derive(Clone)]
 pub struct AnimatedValue {
-    state: Option<AnimationState>,
+    track: Option<AnimationTrack<f32>>,
     current: f32,
+    target: f32,
 }
 
 impl AnimatedValue {
     pub fn new(initial: f32) -> Self {
         Self {
-            state: None,
+            track: None,
             current: initial,
+            target: initial,
         }
     }
 
-    pub fn set(&mut self, target: f32, duration_ms: f64) {
-        self.state = Some(AnimationState::new(self.current, target, duration_ms));
+    /// Anima até `target`; não faz nada se o alvo não mudou e, ao reverter no meio,
+    /// parte do valor e da velocidade atuais
+    pub fn animate_to(&mut self, target: f32, duration_ms: f64, now: Instant) {
+        if target == self.target {
+            return;
+        }
+        let spec = AnimationSpec {
+            curve: AnimationCurve::Tween {
+                duration: Duration::from_secs_f64(duration_ms.max(0.0) / 1000.0),
+                easing: Easing::CubicOut,
+            },
+            delay: Duration::ZERO,
+        };
+        let current = self.current;
+        self.track
+            .get_or_insert_with(|| AnimationTrack::new(current, spec, now))
+            .set_target(target, spec, now);
+        self.target = target;
+    }
+
+    /// Mesmo que `animate_to`
+    pub fn set(&mut self, target: f32, duration_ms: f64, now: Instant) {
+        self.animate_to(target, duration_ms, now);
+    }
+
+    pub fn target(&self) -> f32 {
+        self.target
     }
 
-    pub fn update(&mut self) -> f32 {
-        if let Some(anim) = &self.state {
-            self.current = anim.value();
-            if anim.is_complete() {
-                self.state = None;
+    pub fn update(&mut self, now: Instant) -> f32 {
+        if let Some(track) = &mut self.track {
+            self.current = track.update(now);
+            if !track.is_animating() {
+                self.track = None;
             }
         }
         self.current
     }
 
     pub fn is_animating(&self) -> bool {
-        self.state.is_some()
+        self.track.is_some()
     }
 }
 
+/// Progresso animado (0..1) de hover e press de um widget
+#[derive(Clone, Copy, Debug, Default)]
+pub struct InteractionAnimation {
+    pub hover: f32,
+    pub press: f32,
+}
+
+// ============================================================================
+// RESPONSE DE INTERAÇÃO
+// ============================================================================
+
+/// Resultado de `Ui::interact` para um widget neste frame
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Response {
+    pub rect: Rect,
+    pub hovered: bool,
+    /// Botão primário pressionado sobre o widget (que recebeu a pressão)
+    pub pressed: bool,
+    /// Soltou o botão primário sobre o mesmo widget que recebeu a pressão
+    pub clicked: bool,
+    pub double_clicked: bool,
+    pub triple_clicked: bool,
+    pub secondary_clicked: bool,
+    pub middle_clicked: bool,
+    /// Contagem de cliques consecutivos do último clique primário
+    pub click_count: u32,
+}
+
 // ============================================================================
 // UI CONTEXT
 // ============================================================================
@@ -287,6 +354,412 @@ impl<'a> Ui<'a> {
         }
     }
 
+    // ------------------------------------------------------------------------
+    // FOCO
+    // ------------------------------------------------------------------------
+
+    /// Registra um widget interativo na ordem de Tab e retorna se ele está focado
+    pub fn register_focusable(&mut self, id: u64) -> bool {
+        let mut store = self.state.borrow_mut();
+        store.focus.register(id);
+        store.focus.is_focused(id)
+    }
+
+    /// Igual a `register_focusable`, para widgets que recebem texto
+    pub fn register_text_focusable(&mut self, id: u64) -> bool {
+        let mut store = self.state.borrow_mut();
+        store.focus.register_text_entry(id);
+        store.focus.is_focused(id)
+    }
+
+    /// Região de foco: atalhos com `CommandScope::Region(id)` só disparam
+    /// quando o widget focado foi registrado dentro dela
+    pub fn focus_region(&mut self, id: u64, content: impl FnOnce(&mut Ui)) {
+        self.state.borrow_mut().focus.push_region(id);
+        content(self);
+        self.state.borrow_mut().focus.pop_region();
+    }
+
+    pub fn is_focused(&self, id: u64) -> bool {
+        self.state.borrow().focus.is_focused(id)
+    }
+
+    pub fn request_focus(&mut self, id: u64) {
+        self.state.borrow_mut().focus.request_focus(id);
+    }
+
+    pub fn clear_focus(&mut self) {
+        self.state.borrow_mut().focus.clear_focus();
+    }
+
+    /// Enter/Espaço ativam o widget focado
+    pub fn activated_by_keyboard(&self, id: u64) -> bool {
+        self.is_focused(id)
+            && (self.input.key_just_pressed(VirtualKeyCode::Return)
+                || self.input.key_just_pressed(VirtualKeyCode::NumpadEnter)
+                || self.input.key_just_pressed(VirtualKeyCode::Space))
+    }
+
+    /// Desenha o anel de foco do tema ao redor de `rect` se o foco veio do teclado
+    pub fn draw_focus_ring(&mut self, id: u64, rect: Rect, radius: f32) {
+        let visible = {
+            let store = self.state.borrow();
+            store.focus.is_focused(id) && store.focus.focus_visible()
+        };
+        if !visible {
+            return;
+        }
+        let offset = components::FOCUS_RING_OFFSET + components::FOCUS_RING_WIDTH;
+        let color = self.theme().colors.focus_ring;
+        let ring = Rect {
+            x: rect.x - offset,
+            y: rect.y - offset,
+            w: rect.w + offset * 2.0,
+            h: rect.h + offset * 2.0,
+        };
+        crate::renderer::draw_rounded_ring(
+            self.frame,
+            ring,
+            radius + offset,
+            components::FOCUS_RING_WIDTH,
+            color,
+            self.width,
+            self.height,
+        );
+    }
+
+    /// Desenha as sombras de uma caixa, na ordem dada, usando o cache de máscaras
+    pub fn draw_shadows(&mut self, rect: Rect, radius: impl Into<CornerRadii>, shadows: &[BoxShadow]) {
+        let radius = radius.into();
+        let mut store = self.state.borrow_mut();
+        for shadow in shadows {
+            draw_box_shadow(self.frame, &mut store.shadows, rect, radius, shadow, self.width, self.height);
+        }
+    }
+
+    /// Carrega uma imagem no cache (decodificada só na primeira vez); `None` se falhar
+    pub fn load_image<'s>(&mut self, source: impl Into<ImageSource<'s>>) -> Option<ImageHandle> {
+        self.state.borrow_mut().images.load(source.into())
+    }
+
+    /// Tamanho natural de uma imagem já carregada
+    pub fn image_size(&self, handle: ImageHandle) -> Option<(f32, f32)> {
+        let store = self.state.borrow();
+        store
+            .images
+            .get(handle)
+            .map(|image| (image.width() as f32, image.height() as f32))
+    }
+
+    /// Desenha uma imagem do cache em `rect`; retorna `false` se ela não pôde ser carregada
+    pub fn draw_image<'s>(
+        &mut self,
+        source: impl Into<ImageSource<'s>>,
+        rect: Rect,
+        radius: impl Into<CornerRadii>,
+        style: &ImageStyle,
+    ) -> bool {
+        let Some(handle) = self.load_image(source) else {
+            return false;
+        };
+        let store = self.state.borrow();
+        let Some(image) = store.images.get(handle) else {
+            return false;
+        };
+        draw_image(self.frame, image, rect, radius, style, self.width, self.height);
+        drop(store);
+        self.mark_drawn(rect);
+        true
+    }
+
+    /// Desenha uma skin nine-slice cobrindo `rect`
+    pub fn draw_nine_slice(&mut self, rect: Rect, slice: &NineSlice) {
+        let store = self.state.borrow();
+        if let Some(image) = store.images.get(slice.image) {
+            draw_nine_slice(self.frame, image, slice, rect, self.width, self.height);
+        }
+    }
+
+    /// Carrega um SVG no atlas (lido só na primeira vez); `None` se falhar
+    pub fn load_svg<'s>(&mut self, source: impl Into<SvgSource<'s>>) -> Option<SvgHandle> {
+        self.state.borrow_mut().svgs.load(source.into())
+    }
+
+    /// Tamanho natural de um SVG já carregado
+    pub fn svg_size(&self, handle: SvgHandle) -> Option<(f32, f32)> {
+        self.state.borrow().svgs.get(handle).map(|svg| svg.size())
+    }
+
+    /// Desenha um SVG em `rect` (bitmap em cache por tamanho); `color` recolore o ícone.
+    /// Retorna `false` se ele não pôde ser carregado
+    pub fn draw_svg<'s>(&mut self, source: impl Into<SvgSource<'s>>, rect: Rect, color: Option<Color>) -> bool {
+        let Some(handle) = self.load_svg(source) else {
+            return false;
+        };
+        let mut store = self.state.borrow_mut();
+        draw_svg(self.frame, &mut store.svgs, handle, rect, color, self.width, self.height);
+        store.layers.mark_drawn(rect);
+        true
+    }
+
+    // ------------------------------------------------------------------------
+    // COMANDOS
+    // ------------------------------------------------------------------------
+
+    /// Registra um comando global com atalho e retorna se ele disparou neste frame
+    pub fn command(&mut self, name: &str, description: &str, binding: &str) -> bool {
+        self.scoped_command(name, description, binding, CommandScope::Global)
+    }
+
+    /// Igual a `command`, com escopo explícito (ex: `CommandScope::Region(id)`)
+    pub fn scoped_command(
+        &mut self,
+        name: &str,
+        description: &str,
+        binding: &str,
+        scope: CommandScope,
+    ) -> bool {
+        let mut store = self.state.borrow_mut();
+        store.commands.register_or_warn(name, description, binding, scope);
+        store.commands.triggered(name)
+    }
+
+    pub fn command_triggered(&self, name: &str) -> bool {
+        self.state.borrow().commands.triggered(name)
+    }
+
+    // ------------------------------------------------------------------------
+    // INTERAÇÃO COM O PONTEIRO
+    // ------------------------------------------------------------------------
+
+    /// Hover, pressão e cliques de um widget. Um clique só dispara ao soltar o botão
+    /// sobre o mesmo widget que recebeu a pressão (arrastar para fora cancela).
+    pub fn interact(&mut self, id: u64, rect: Rect) -> Response {
+        let hovered = self.is_hovered(rect);
+        let mut response = Response {
+            rect,
+            hovered,
+            ..Default::default()
+        };
+
+        let mut store = self.state.borrow_mut();
+        for button in PointerButton::ALL {
+            let i = button.index();
+            if hovered && self.input.button_pressed(button) {
+                store.pointer_capture[i] = Some(id);
+            }
+            let captured = store.pointer_capture[i] == Some(id);
+            let released_here = captured && hovered && self.input.button_released(button);
+            match button {
+                PointerButton::Primary => {
+                    response.pressed = captured && hovered && self.input.button_down(button);
+                    if released_here {
+                        let count = self.input.click_count(button);
+                        response.clicked = true;
+                        response.click_count = count;
+                        response.double_clicked = count == 2;
+                        response.triple_clicked = count == 3;
+                    }
+                }
+                PointerButton::Secondary => response.secondary_clicked = released_here,
+                PointerButton::Middle => response.middle_clicked = released_here,
+            }
+        }
+        response
+    }
+
+    // ------------------------------------------------------------------------
+    // TEMPO
+    // ------------------------------------------------------------------------
+
+    /// Instante do frame atual; todas as animações devem usar este relógio
+    pub fn now(&self) -> Instant {
+        self.state.borrow().frame_time
+    }
+
+    /// Pede outro frame logo após este (ex.: animação em andamento)
+    pub fn request_repaint(&mut self) {
+        let now = self.now();
+        self.state.borrow_mut().request_repaint_at(now);
+    }
+
+    /// Pede um frame daqui a `delay` (ex.: próxima troca do caret piscando)
+    pub fn request_repaint_after(&mut self, delay: Duration) {
+        let at = self.now() + delay;
+        self.state.borrow_mut().request_repaint_at(at);
+    }
+
+    // ------------------------------------------------------------------------
+    // ANIMAÇÕES
+    // ------------------------------------------------------------------------
+
+    /// Anima o valor do `id` até `target`; quando o alvo muda, parte do valor atual
+    /// (springs mantêm a velocidade). Pede frames enquanto não chegar.
+    pub fn animate<T: Animatable>(&mut self, id: u64, target: T, spec: AnimationSpec) -> T {
+        let now = self.now();
+        let key = self.make_id(id);
+        let (value, animating) = {
+            let mut store = self.state.borrow_mut();
+            let track = store.animations.track(key, target, spec, now);
+            track.set_target(target, spec, now);
+            (track.update(now), track.is_animating())
+        };
+        if animating {
+            self.request_repaint();
+        }
+        value
+    }
+
+    /// Transições de hover/press compartilhadas pelos widgets; pede frames enquanto anima
+    pub fn interaction_animation(&mut self, widget_id: u64, hovered: bool, pressed: bool) -> InteractionAnimation {
+        let hover_spec = AnimationSpec::tween(render::ANIMATION_DURATION_MS as u64, Easing::CubicOut);
+        let press_spec = AnimationSpec::tween(render::PRESS_ANIMATION_DURATION_MS as u64, Easing::CubicOut);
+
+        self.push_id(widget_id);
+        let hover = self.animate(0, if hovered { 1.0 } else { 0.0 }, hover_spec);
+        let press = self.animate(1, if pressed { 1.0 } else { 0.0 }, press_spec);
+        self.pop_id();
+
+        InteractionAnimation { hover, press }
+    }
+
+    /// Toca a timeline a partir do primeiro frame em que o `id` aparece
+    pub fn play<L: Timeline>(&mut self, id: u64, timeline: &L) -> L::Value {
+        let now = self.now();
+        let key = self.make_id(id);
+        let start = self.state.borrow_mut().animations.timeline_start(key, now);
+        let elapsed = now.saturating_duration_since(start);
+        if timeline.duration().is_none_or(|d| elapsed < d) {
+            self.request_repaint();
+        }
+        timeline.sample(elapsed)
+    }
+
+    /// Esquece o estado da animação do `id` (a timeline recomeça do zero)
+    pub fn restart_animation(&mut self, id: u64) {
+        let key = self.make_id(id);
+        self.state.borrow_mut().animations.restart(key);
+    }
+
+    // ------------------------------------------------------------------------
+    // ÁREA PINTADA
+    // ------------------------------------------------------------------------
+
+    /// Registra a área que um widget pintou. Camadas e `transform` compõem só o que foi
+    /// registrado (mais o layout); widgets próprios que pintam direto no frame devem chamar
+    pub fn mark_drawn(&self, rect: Rect) {
+        self.state.borrow_mut().layers.mark_drawn(rect);
+    }
+
+    // ------------------------------------------------------------------------
+    // CURSOR
+    // ------------------------------------------------------------------------
+
+    /// Pede um ícone de cursor para este frame por hover (o último pedido vence, mas
+    /// perde para `set_captured_cursor`)
+    pub fn set_cursor(&mut self, icon: CursorIcon) {
+        self.state.borrow_mut().cursor_icon = Some(icon);
+    }
+
+    /// Cursor de quem está com o ponteiro (arrasto, widget pressionado): widgets em hover
+    /// desenhados depois no frame não o substituem
+    pub fn set_captured_cursor(&mut self, icon: CursorIcon) {
+        self.state.borrow_mut().captured_cursor = Some(icon);
+    }
+
+    // ------------------------------------------------------------------------
+    // ARRASTO E DRAG-AND-DROP
+    // ------------------------------------------------------------------------
+
+    /// Gesto de arrasto sobre `rect`: começa após `input::DRAG_THRESHOLD` px com o botão primário
+    pub fn drag(&mut self, id: u64, rect: Rect) -> DragResponse {
+        let hovered = self.is_hovered(rect);
+        let mut store = self.state.borrow_mut();
+        if hovered && self.input.button_pressed(PointerButton::Primary) {
+            let (mx, my) = self.input.mouse_pos;
+            store.drag.begin(id, (mx, my), (mx - rect.x, my - rect.y));
+        }
+        let response = store.drag.update(id, self.input);
+        if response.dragging {
+            store.captured_cursor = Some(CursorIcon::Grabbing);
+        }
+        response
+    }
+
+    /// Torna `rect` origem de um arrasto carregando `payload`; desenha um preview
+    /// com `label` seguindo o cursor na camada de overlay enquanto arrasta
+    pub fn drag_source<T: 'static>(&mut self, id: u64, rect: Rect, label: &str, payload: T) -> DragResponse {
+        let response = self.drag(id, rect);
+        if response.started {
+            self.state.borrow_mut().drag.payload = Some(Rc::new(payload));
+        }
+        if response.dragging {
+            let (mx, my) = self.input.mouse_pos;
+            let theme = self.theme();
+            let (surface, text_col) = (theme.colors.surface_hover, theme.colors.text_primary);
+            drop(theme);
+            let mut store = self.state.borrow_mut();
+            let (gx, gy) = store.drag.grab_offset;
+            let (x, y) = (mx - gx, my - gy);
+            store.overlay.push(DrawCommand::RoundedRect {
+                x,
+                y,
+                w: rect.w,
+                h: rect.h,
+                r: components::BUTTON_BORDER_RADIUS,
+                color: surface.alpha(components::DRAG_GHOST_ALPHA),
+            });
+            store.overlay.push(DrawCommand::Text {
+                x: x + spacing::MD,
+                y: y + (rect.h - font_size::MD) / 2.0,
+                size: font_size::MD,
+                text: label.to_string(),
+                color: text_col,
+            });
+        }
+        response
+    }
+
+    /// Alvo de drop para payloads do tipo `T`: destaca `rect` quando um payload
+    /// compatível está por cima e devolve o payload no frame em que é solto
+    pub fn drop_target<T: 'static + Clone>(&mut self, rect: Rect) -> DropResponse<T> {
+        if !self.state.borrow().drag.has_payload::<T>() || !self.is_hovered(rect) {
+            return DropResponse::default();
+        }
+
+        let color = self.theme().colors.focus_ring;
+        crate::renderer::draw_rounded_rect(
+            self.frame,
+            rect.x,
+            rect.y,
+            rect.w,
+            rect.h,
+            components::BUTTON_BORDER_RADIUS,
+            color.alpha(40),
+            self.width,
+            self.height,
+        );
+        crate::renderer::draw_rounded_ring(
+            self.frame,
+            rect,
+            components::BUTTON_BORDER_RADIUS,
+            components::FOCUS_RING_WIDTH,
+            color,
+            self.width,
+            self.height,
+        );
+
+        let dropped = if self.input.button_released(PointerButton::Primary) {
+            self.state.borrow().drag.payload::<T>()
+        } else {
+            None
+        };
+        DropResponse {
+            hovered: true,
+            dropped,
+        }
+    }
+
     pub fn is_hovered(&self, rect: Rect) -> bool {
         let (mx, my) = self.input.mouse_pos;
         // Respeita clip rect se existir
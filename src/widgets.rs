@@ -1,10 +1,21 @@
+use crate::animation::{AnimationSpec, Easing, Edge, Transition};
 use crate::config::{components, font_size, render, spacing, text_alpha};
+use crate::core::InputState;
+use crate::icon::{Icon, draw_icon};
+use crate::image::{ImageSource, ImageStyle};
+use crate::layer::{CachedLayer, paint_bounds};
 use crate::layout::Rect;
 use crate::modifier::Modifier;
 use crate::renderer::{
-    Color, draw_icon_chart, draw_icon_plus, draw_rounded_rect, draw_shadow, draw_text_smooth,
+    BlendMode, Border, Color, CornerRadii, composite_layer, composite_layer_transformed,
+    draw_border, draw_rounded_rect, draw_text_smooth, measure_text,
 };
-use crate::ui_context::{Ui, AnimatedValue};
+use crate::shadow::BoxShadow;
+use crate::svg::SvgSource;
+use crate::transform::{Transform, to_local_input, transformed_bounds};
+use crate::ui_context::Ui;
+use std::time::{Duration, Instant};
+use winit::window::CursorIcon;
 
 
 
@@ -28,19 +39,25 @@ pub fn scroll_view(ui: &mut Ui, modifier: Modifier, content: impl FnOnce(&mut Ui
     };
 
     // Background
+    let radius = modifier
+        .radius
+        .unwrap_or(CornerRadii::all(components::CARD_BORDER_RADIUS));
     if let Some(bg) = modifier.background {
-        crate::renderer::draw_rounded_rect(
+        draw_rounded_rect(
             ui.frame,
-            ui.cursor.x,
-            ui.cursor.y,
-            container_w,
-            container_h,
-            components::CARD_BORDER_RADIUS,
+            rect.x,
+            rect.y,
+            rect.w,
+            rect.h,
+            radius,
             bg,
             ui.width,
             ui.height,
         );
     }
+    if let Some(slice) = &modifier.nine_slice {
+        ui.draw_nine_slice(rect, slice);
+    }
 
     // Check hover antes de modificar scroll
     let is_hovered = ui.is_hovered(Rect {
@@ -98,6 +115,10 @@ pub fn scroll_view(ui: &mut Ui, modifier: Modifier, content: impl FnOnce(&mut Ui
     // Restore clip
     ui.clip_rect = old_clip;
 
+    if let Some(border) = &modifier.border {
+        draw_border(ui.frame, rect, radius, border, ui.width, ui.height);
+    }
+
     // Draw scrollbar
     ui.draw_scrollbar(
         ui.cursor.x + container_w - components::SCROLLBAR_WIDTH,
@@ -105,6 +126,7 @@ pub fn scroll_view(ui: &mut Ui, modifier: Modifier, content: impl FnOnce(&mut Ui
     );
 
     ui.pop_id();
+    ui.mark_drawn(rect);
     rect
 }
 
@@ -167,19 +189,27 @@ pub fn column(ui: &mut Ui, modifier: Modifier, content: impl FnOnce(&mut Ui)) ->
     };
     
     // Draw background if present, now that we have final_h_for_column
+    let column_rect = Rect { h: final_h_for_column, ..rect_for_parent };
+    let radius = modifier.radius.unwrap_or(CornerRadii::ZERO); // Column sem cantos arredondados por padrão
     if let Some(bg) = modifier.background {
-        crate::renderer::draw_rounded_rect(
+        draw_rounded_rect(
             ui.frame,
-            rect_for_parent.x,
-            rect_for_parent.y,
-            rect_for_parent.w,
-            final_h_for_column,
-            0.0, // Assuming no rounded corners for column background
+            column_rect.x,
+            column_rect.y,
+            column_rect.w,
+            column_rect.h,
+            radius,
             bg,
             ui.width,
             ui.height,
         );
     }
+    if let Some(slice) = &modifier.nine_slice {
+        ui.draw_nine_slice(column_rect, slice);
+    }
+    if let Some(border) = &modifier.border {
+        draw_border(ui.frame, column_rect, radius, border, ui.width, ui.height);
+    }
 
 
     // Update parent ui's cursor
@@ -190,7 +220,9 @@ pub fn column(ui: &mut Ui, modifier: Modifier, content: impl FnOnce(&mut Ui)) ->
 
 
     ui.pop_id();
-    Rect { x: initial_x, y: initial_y, w: rect_for_parent.w, h: final_h_for_column } // Return actual rect consumed
+    let rect = Rect { x: initial_x, y: initial_y, w: rect_for_parent.w, h: final_h_for_column }; // Actual rect consumed
+    ui.mark_drawn(rect);
+    rect
 }
 
 // ============================================================================
@@ -255,19 +287,29 @@ pub fn row(ui: &mut Ui, modifier: Modifier, content: impl FnOnce(&mut Ui)) -> Re
     };
 
     // Draw background if present, now that we have final_w_for_row and final_h_for_row
+    let row_rect = Rect { w: final_w_for_row, h: final_h_for_row, ..rect_for_parent };
+    let radius = modifier
+        .radius
+        .unwrap_or(CornerRadii::all(components::CARD_BORDER_RADIUS));
     if let Some(bg) = modifier.background {
-        crate::renderer::draw_rounded_rect(
+        draw_rounded_rect(
             ui.frame,
-            rect_for_parent.x,
-            rect_for_parent.y,
-            final_w_for_row,
-            final_h_for_row,
-            components::CARD_BORDER_RADIUS, // Assuming rows can have rounded corners too
+            row_rect.x,
+            row_rect.y,
+            row_rect.w,
+            row_rect.h,
+            radius,
             bg,
             ui.width,
             ui.height,
         );
     }
+    if let Some(slice) = &modifier.nine_slice {
+        ui.draw_nine_slice(row_rect, slice);
+    }
+    if let Some(border) = &modifier.border {
+        draw_border(ui.frame, row_rect, radius, border, ui.width, ui.height);
+    }
 
     // Update parent ui's cursor
     ui.cursor.y = initial_y + final_h_for_row; // Row consumes vertical space in parent
@@ -276,7 +318,337 @@ pub fn row(ui: &mut Ui, modifier: Modifier, content: impl FnOnce(&mut Ui)) -> Re
     ui.max_x_seen = ui.max_x_seen.max(initial_x + final_w_for_row);
 
     ui.pop_id();
-    Rect { x: initial_x, y: initial_y, w: final_w_for_row, h: final_h_for_row } // Return actual rect consumed
+    let rect = Rect { x: initial_x, y: initial_y, w: final_w_for_row, h: final_h_for_row }; // Actual rect consumed
+    ui.mark_drawn(rect);
+    rect
+}
+
+// ============================================================================
+// VISIBILIDADE ANIMADA (ENTRADA/SAÍDA)
+// ============================================================================
+
+/// Mostra/esconde o conteúdo com uma transição. Enquanto sai, o conteúdo continua
+/// montado (mas sem receber input) e só deixa de ser desenhado quando a saída termina.
+pub fn animated_visibility(
+    ui: &mut Ui,
+    visible: bool,
+    transition: Transition,
+    content: impl FnOnce(&mut Ui),
+) -> Rect {
+    let widget_id = ui.next_widget_id();
+    ui.push_id(widget_id);
+
+    // Progresso (0 = oculto, 1 = visível) e altura do conteúdo medida no último frame
+    let spec = AnimationSpec::tween(transition.duration_ms as u64, Easing::CubicOut);
+    let t = ui.animate(widget_id, if visible { 1.0f32 } else { 0.0 }, spec);
+    let state = ui.use_state_with_id(widget_id, || 0.0f32);
+
+    let initial_x = ui.cursor.x;
+    let initial_y = ui.cursor.y;
+    let w = ui.cursor.w;
+
+    // Saída concluída: desmonta
+    if t <= 0.0 && !visible {
+        ui.pop_id();
+        return Rect { x: initial_x, y: initial_y, w, h: 0.0 };
+    }
+
+    let (dx, dy) = match transition.slide {
+        Some((edge, distance)) => {
+            let offset = distance * (1.0 - t);
+            match edge {
+                Edge::Left => (-offset, 0.0),
+                Edge::Right => (offset, 0.0),
+                Edge::Top => (0.0, -offset),
+                Edge::Bottom => (0.0, offset),
+            }
+        }
+        None => (0.0, 0.0),
+    };
+    // Faixa (largura total) que o conteúdo de altura `h` ocupa enquanto expande
+    let band = |h: f32| Rect {
+        x: 0.0,
+        y: initial_y,
+        w: ui.width as f32,
+        h: h * t,
+    };
+
+    // Só compõe (fade/recorte) durante a transição, numa camada; visível de vez desenha direto
+    let animating = t < 1.0;
+    let (mut layer, outer) = if animating && (transition.fade || transition.expand) {
+        let mut store = ui.state.borrow_mut();
+        (Some(store.layers.take(ui.frame.len())), store.layers.begin_measure())
+    } else {
+        (None, None)
+    };
+
+    let idle_input = InputState {
+        mouse_pos: (f32::NEG_INFINITY, f32::NEG_INFINITY),
+        ..InputState::default()
+    };
+    // O hit-testing precisa do recorte antes de desenhar: usa a altura do frame anterior
+    let clip_rect = if animating && transition.expand {
+        let band = band(state.get());
+        Some(ui.clip_rect.map_or(band, |clip| clip.intersect(&band)))
+    } else {
+        ui.clip_rect
+    };
+
+    let mut sub_ui = Ui {
+        frame: match layer.as_mut() {
+            Some(layer) => layer,
+            None => &mut *ui.frame,
+        },
+        width: ui.width,
+        height: ui.height,
+        font: ui.font,
+        atlas: ui.atlas,
+        state: ui.state.clone(),
+        input: if visible { ui.input } else { &idle_input },
+        cursor: Rect {
+            x: initial_x + dx,
+            y: initial_y + dy,
+            w,
+            h: 0.0,
+        },
+        clip_rect,
+        scroll: ui.scroll.clone(),
+        depth: ui.depth + 1,
+        widget_id_counter: ui.widget_id_counter,
+        max_y_seen: initial_y + dy,
+        max_x_seen: initial_x + dx,
+    };
+
+    content(&mut sub_ui);
+    ui.widget_id_counter = sub_ui.widget_id_counter;
+    let content_rect = Rect {
+        x: initial_x + dx,
+        y: initial_y + dy,
+        w: sub_ui.max_x_seen - (initial_x + dx),
+        h: sub_ui.max_y_seen - (initial_y + dy),
+    };
+    let content_h = content_rect.h;
+
+    if let Some(layer) = layer {
+        let drawn = ui.state.borrow_mut().layers.end_measure(outer);
+        // Recorta pela altura medida neste frame (a entrada não começa com um frame vazio)
+        let mut bounds = paint_bounds(content_rect, drawn, ui.width, ui.height);
+        if transition.expand {
+            bounds = bounds.intersect(&band(content_h));
+        }
+        let alpha = if transition.fade { t } else { 1.0 };
+        composite_layer(ui.frame, &layer, bounds, alpha, BlendMode::Normal, ui.width, ui.height);
+        ui.state.borrow_mut().layers.give_back(layer);
+        ui.mark_drawn(bounds);
+    }
+    state.set(content_h);
+
+    let consumed_h = if transition.expand { content_h * t } else { content_h };
+    ui.cursor.y = initial_y + consumed_h;
+    ui.cursor.x = initial_x;
+    ui.max_y_seen = ui.max_y_seen.max(ui.cursor.y);
+    ui.max_x_seen = ui.max_x_seen.max(content_rect.x + content_rect.w);
+
+    ui.pop_id();
+    Rect { x: initial_x, y: initial_y, w, h: consumed_h }
+}
+
+// ============================================================================
+// CAMADAS (OPACIDADE DE GRUPO, BLEND E CACHE)
+// ============================================================================
+
+/// Desenha `content` numa camada offscreen e a compõe de uma vez com `opacity`,
+/// para que filhos sobrepostos não apareçam uns através dos outros
+pub fn with_opacity(ui: &mut Ui, opacity: f32, content: impl FnOnce(&mut Ui)) -> Rect {
+    with_layer(ui, opacity, BlendMode::Normal, content)
+}
+
+/// Como `with_opacity`, misturando a camada com o fundo pelo `blend` dado
+pub fn with_layer(ui: &mut Ui, opacity: f32, blend: BlendMode, content: impl FnOnce(&mut Ui)) -> Rect {
+    let widget_id = ui.next_widget_id();
+    ui.push_id(widget_id);
+
+    let input = ui.input;
+    let (layer, rect, drawn) = draw_into_layer(ui, input, content);
+    let bounds = paint_bounds(rect, drawn, ui.width, ui.height);
+    composite_layer(ui.frame, &layer, bounds, opacity, blend, ui.width, ui.height);
+    ui.state.borrow_mut().layers.give_back(layer);
+    ui.mark_drawn(bounds);
+
+    ui.pop_id();
+    rect
+}
+
+/// Subárvore estática desenhada uma vez e reaproveitada até `version` mudar (ou a posição
+/// e o tamanho da janela). Nos frames em cache o conteúdo não roda: não use para widgets
+/// que dependem de hover ou input.
+pub fn cached_layer(ui: &mut Ui, version: u64, content: impl FnOnce(&mut Ui)) -> Rect {
+    let widget_id = ui.next_widget_id();
+    ui.push_id(widget_id);
+
+    let key = ui.make_id(widget_id);
+    let origin = (ui.cursor.x, ui.cursor.y);
+    let size = (ui.width, ui.height);
+
+    let hit = {
+        let mut store = ui.state.borrow_mut();
+        store.layers.cached(key, version, origin, size).map(|cached| {
+            let (sw, sh) = (ui.width, ui.height);
+            composite_layer(ui.frame, &cached.pixels, cached.bounds, 1.0, BlendMode::Normal, sw, sh);
+            (cached.rect, cached.bounds, cached.widget_ids)
+        })
+    };
+
+    let rect = match hit {
+        Some((rect, bounds, widget_ids)) => {
+            ui.widget_id_counter += widget_ids;
+            advance_past(ui, rect);
+            ui.mark_drawn(bounds);
+            rect
+        }
+        None => {
+            let ids_before = ui.widget_id_counter;
+            let input = ui.input;
+            let (pixels, rect, drawn) = draw_into_layer(ui, input, content);
+            let bounds = paint_bounds(rect, drawn, ui.width, ui.height);
+            composite_layer(ui.frame, &pixels, bounds, 1.0, BlendMode::Normal, ui.width, ui.height);
+            ui.mark_drawn(bounds);
+            let widget_ids = ui.widget_id_counter - ids_before;
+            ui.state.borrow_mut().layers.insert(
+                key,
+                CachedLayer {
+                    version,
+                    origin,
+                    size,
+                    pixels,
+                    rect,
+                    bounds,
+                    widget_ids,
+                },
+            );
+            rect
+        }
+    };
+
+    ui.pop_id();
+    rect
+}
+
+/// Roda `content` com um buffer transparente como frame, no mesmo cursor do pai.
+/// Retorna a camada, a área de layout e a área registrada como pintada.
+fn draw_into_layer(
+    ui: &mut Ui,
+    input: &InputState,
+    content: impl FnOnce(&mut Ui),
+) -> (Vec<u8>, Rect, Option<Rect>) {
+    let (mut layer, outer) = {
+        let mut store = ui.state.borrow_mut();
+        (store.layers.take(ui.frame.len()), store.layers.begin_measure())
+    };
+    let initial_x = ui.cursor.x;
+    let initial_y = ui.cursor.y;
+
+    let mut sub_ui = Ui {
+        frame: &mut layer,
+        width: ui.width,
+        height: ui.height,
+        font: ui.font,
+        atlas: ui.atlas,
+        state: ui.state.clone(),
+        input,
+        cursor: Rect {
+            x: initial_x,
+            y: initial_y,
+            w: ui.cursor.w,
+            h: 0.0,
+        },
+        clip_rect: ui.clip_rect,
+        scroll: ui.scroll.clone(),
+        depth: ui.depth + 1,
+        widget_id_counter: ui.widget_id_counter,
+        max_y_seen: initial_y,
+        max_x_seen: initial_x,
+    };
+
+    content(&mut sub_ui);
+    ui.widget_id_counter = sub_ui.widget_id_counter;
+    let drawn = ui.state.borrow_mut().layers.end_measure(outer);
+    let rect = Rect {
+        x: initial_x,
+        y: initial_y,
+        w: sub_ui.max_x_seen - initial_x,
+        h: sub_ui.max_y_seen - initial_y,
+    };
+
+    advance_past(ui, rect);
+    (layer, rect, drawn)
+}
+
+// ============================================================================
+// TRANSFORMAÇÕES AFINS
+// ============================================================================
+
+/// Aplica `t` à pintura e ao hit-testing de `content`; o layout continua usando o
+/// retângulo sem transformação (zoom de canvas, scale ao pressionar, badges rotacionados)
+pub fn transform(ui: &mut Ui, t: Transform, content: impl FnOnce(&mut Ui)) -> Rect {
+    let widget_id = ui.next_widget_id();
+    ui.push_id(widget_id);
+
+    // O ponto fixo depende do tamanho do conteúdo, que só existe depois de desenhá-lo:
+    // o hit-testing usa o retângulo (relativo ao cursor) medido no frame anterior
+    let pivot_state = ui.use_state_with_id(widget_id, Rect::default);
+    let previous = pivot_state.get();
+    let estimate = Rect {
+        x: ui.cursor.x + previous.x,
+        y: ui.cursor.y + previous.y,
+        ..previous
+    };
+    // Afim singular (escala zero): o conteúdo não aparece, então o ponteiro fica de fora
+    let to_local = t.to_local(estimate);
+    let mut local_input = match &to_local {
+        Some(to_local) => to_local_input(ui.input, to_local),
+        None => ui.input.clone(),
+    };
+    if to_local.is_none() || ui.clip_rect.is_some_and(|clip| !clip.contains(ui.input.mouse_pos)) {
+        local_input.mouse_pos = (f32::NEG_INFINITY, f32::NEG_INFINITY);
+    }
+
+    // O clip do pai está em coordenadas de tela; dentro da subárvore ele já foi aplicado acima
+    let clip_rect = ui.clip_rect.take();
+    let (layer, rect, drawn) = draw_into_layer(ui, &local_input, content);
+    ui.clip_rect = clip_rect;
+
+    // Widgets que não avançam o cursor não informam tamanho: usa a área que registraram
+    let pivot_rect = if rect.w > 0.0 && rect.h > 0.0 {
+        rect
+    } else {
+        drawn.unwrap_or(rect)
+    };
+    pivot_state.set(Rect {
+        x: pivot_rect.x - rect.x,
+        y: pivot_rect.y - rect.y,
+        ..pivot_rect
+    });
+
+    if let Some(to_layer) = t.to_local(pivot_rect) {
+        let to_screen = t.to_screen(pivot_rect);
+        let painted = paint_bounds(rect, drawn, ui.width, ui.height);
+        let dest = transformed_bounds(&to_screen, painted);
+        composite_layer_transformed(ui.frame, &layer, &to_layer, dest, ui.width, ui.height);
+        ui.mark_drawn(dest);
+    }
+    ui.state.borrow_mut().layers.give_back(layer);
+
+    ui.pop_id();
+    rect
+}
+
+/// Avança o cursor do pai como uma coluna que ocupou `rect`
+fn advance_past(ui: &mut Ui, rect: Rect) {
+    ui.cursor.y = rect.y + rect.h;
+    ui.cursor.x = rect.x;
+    ui.max_y_seen = ui.max_y_seen.max(ui.cursor.y);
+    ui.max_x_seen = ui.max_x_seen.max(rect.x + rect.w);
 }
 
 // ============================================================================
@@ -296,70 +668,90 @@ pub fn button(ui: &mut Ui, modifier: Modifier, label: &str) -> (bool, Rect) {
         h,
     };
 
-    // Hover state com animação
-    let hover_state = ui.use_state_with_id(widget_id, || AnimatedValue::new(0.0));
-    let hovered = ui.is_hovered(rect);
-
-    // Atualiza animação hover
-    {
-        let mut anim = hover_state.get();
-        if hovered {
-            anim.set(1.0, render::ANIMATION_DURATION_MS);
-        } else {
-            anim.set(0.0, render::ANIMATION_DURATION_MS);
-        }
-        hover_state.set(anim);
-    }
+    let focus_id = ui.make_id(widget_id);
+    let response = ui.interact(focus_id, rect);
+    let hovered = response.hovered;
 
-    let hover_t = hover_state.get().update();
+    // Transições de hover/press
+    let anim = ui.interaction_animation(widget_id, hovered, response.pressed);
+    let hover_t = anim.hover;
 
-    // Click state
-    let clicked = hovered && ui.input.mouse_just_clicked;
-    let is_pressed = hovered && ui.input.mouse_clicked;
+    // Click state (soltar sobre o botão ou Enter/Espaço com foco)
+    ui.register_focusable(focus_id);
+    if hovered && ui.input.mouse_just_clicked {
+        ui.request_focus(focus_id);
+    }
+    let clicked = response.clicked || ui.activated_by_keyboard(focus_id);
+    if response.pressed {
+        ui.set_captured_cursor(CursorIcon::Hand);
+    } else if hovered {
+        ui.set_cursor(CursorIcon::Hand);
+    }
 
     // Interpola cor baseada no hover
-    let base_col = modifier.background.unwrap_or(ui.theme().colors.primary);
+    let base = modifier.background.unwrap_or(ui.theme().colors.primary.into());
     let hover_col = ui.theme().colors.primary_hover;
 
-    let col = Color {
+    // Num gradiente, cada parada interpola em direção à cor de hover
+    let col = base.map(|base_col| Color {
         r: (base_col.r as f32 + (hover_col.r as f32 - base_col.r as f32) * hover_t) as u8,
         g: (base_col.g as f32 + (hover_col.g as f32 - base_col.g as f32) * hover_t) as u8,
         b: (base_col.b as f32 + (hover_col.b as f32 - base_col.b as f32) * hover_t) as u8,
-        a: if is_pressed { 200 } else { base_col.a },
-    };
-
-    draw_rounded_rect(
-        ui.frame,
-        ui.cursor.x,
-        ui.cursor.y,
-        w,
-        h,
-        components::BUTTON_BORDER_RADIUS,
-        col,
-        ui.width,
-        ui.height,
-    );
+        a: (base_col.a as f32 + (200.0 - base_col.a as f32) * anim.press) as u8,
+    });
 
-    // Render label ou ícone
-    if label == "+" {
-        draw_icon_plus(
+    let radius = modifier
+        .radius
+        .unwrap_or(CornerRadii::all(components::BUTTON_BORDER_RADIUS));
+    // Com skin nine-slice o fundo padrão só aparece se pedido explicitamente
+    if modifier.nine_slice.is_none() || modifier.background.is_some() {
+        draw_rounded_rect(
             ui.frame,
-            (ui.cursor.x + w / 2.0 - 7.0) as i32,
-            (ui.cursor.y + h / 2.0 - 7.0) as i32,
-            14,
-            Color::WHITE,
+            rect.x,
+            rect.y,
+            rect.w,
+            rect.h,
+            radius,
+            col,
             ui.width,
             ui.height,
         );
-    } else {
-        let tx = ui.cursor.x + (w - label.len() as f32 * 9.0) / 2.0;
+    }
+    if let Some(slice) = &modifier.nine_slice {
+        ui.draw_nine_slice(rect, slice);
+    }
+    if let Some(border) = &modifier.border {
+        draw_border(ui.frame, rect, radius, border, ui.width, ui.height);
+    }
+    ui.draw_focus_ring(focus_id, rect, radius.clamped(w, h).top_left);
+
+    // Ícone inicial e texto, centralizados juntos
+    let icon_size = components::BUTTON_ICON_SIZE;
+    let label_w = measure_text(ui.font, font_size::LG, label);
+    let icon_w = match modifier.icon {
+        Some(_) if label.is_empty() => icon_size,
+        Some(_) => icon_size + components::ICON_GAP,
+        None => 0.0,
+    };
+    let mut content_x = ui.cursor.x + (w - icon_w - label_w) / 2.0;
+    if let Some(icon) = modifier.icon {
+        let icon_rect = Rect {
+            x: content_x,
+            y: ui.cursor.y + (h - icon_size) / 2.0,
+            w: icon_size,
+            h: icon_size,
+        };
+        draw_icon(ui.frame, icon, icon_rect, Color::WHITE, ui.width, ui.height);
+        content_x += icon_w;
+    }
+    if !label.is_empty() {
         let ty = ui.cursor.y + (h - 18.0) / 2.0;
         draw_text_smooth(
             ui.frame,
             ui.atlas,
             ui.font,
             font_size::LG,
-            tx,
+            content_x,
             ty,
             label,
             Color::WHITE,
@@ -370,6 +762,7 @@ pub fn button(ui: &mut Ui, modifier: Modifier, label: &str) -> (bool, Rect) {
 
     ui.pop_id();
 
+    ui.mark_drawn(rect);
     (clicked, rect)
 }
 
@@ -383,7 +776,6 @@ pub fn text_input(ui: &mut Ui, modifier: Modifier, placeholder: &str) -> (String
 
     // Estado do input
     let text_state = ui.use_state_with_id(widget_id, || String::new());
-    let focus_state = ui.use_state_with_id(widget_id + 1000, || false);
 
     let w = modifier.width.unwrap_or(200.0);
     let h = modifier.height.unwrap_or(components::BUTTON_HEIGHT);
@@ -395,29 +787,44 @@ pub fn text_input(ui: &mut Ui, modifier: Modifier, placeholder: &str) -> (String
     };
 
     let hovered = ui.is_hovered(rect);
-    let focused = focus_state.get();
-    let text = text_state.get();
+    let focus_id = ui.make_id(widget_id);
 
-    // Click para focar
+    // Click para focar (clique fora já limpa o foco no início do frame)
     if hovered && ui.input.mouse_just_clicked {
-        focus_state.set(!focused);
+        ui.request_focus(focus_id);
+    }
+    if hovered {
+        ui.set_cursor(CursorIcon::Text);
     }
+    let mut focused = ui.register_text_focusable(focus_id);
+    let text_before = text_state.get();
 
-    // Perde foco ao clicar fora
-    if focused && ui.input.mouse_just_clicked && !hovered {
-        focus_state.set(false);
+    // Esc tira o foco
+    if focused && ui.input.key_just_pressed(winit::event::VirtualKeyCode::Escape) {
+        ui.clear_focus();
+        focused = false;
     }
 
     // Input de texto
     if focused {
-        if let Some(ch) = ui.input.char_input {
+        // Texto confirmado pelo IME (CJK, acentos com teclas mortas)
+        if let Some(commit) = &ui.input.ime_commit {
+            let mut new_text = text_state.get();
+            new_text.extend(commit.chars().filter(|c| !c.is_control()));
+            text_state.set(new_text);
+        }
+
+        // Durante a composição as teclas pertencem ao IME
+        if !ui.input.is_composing()
+            && let Some(ch) = ui.input.char_input
+        {
             if ch == '\x08' {
                 // Backspace
-                let mut new_text = text.clone();
+                let mut new_text = text_state.get();
                 new_text.pop();
                 text_state.set(new_text);
             } else if ch >= ' ' && ch != '\x7f' {
-                let mut new_text = text.clone();
+                let mut new_text = text_state.get();
                 new_text.push(ch);
                 text_state.set(new_text);
             }
@@ -429,6 +836,8 @@ pub fn text_input(ui: &mut Ui, modifier: Modifier, placeholder: &str) -> (String
         }
     }
 
+    let text = text_state.get();
+
     // Cores baseadas no estado
     let bg_col = if focused {
         ui.theme().colors.surface
@@ -447,28 +856,33 @@ pub fn text_input(ui: &mut Ui, modifier: Modifier, placeholder: &str) -> (String
     };
 
     // Background e border
+    let radius = modifier
+        .radius
+        .unwrap_or(CornerRadii::all(components::BUTTON_BORDER_RADIUS));
+    let border = modifier.border.unwrap_or_else(|| {
+        let width = if focused {
+            components::INPUT_BORDER_WIDTH_FOCUSED
+        } else {
+            components::INPUT_BORDER_WIDTH
+        };
+        Border::new(width, border_col)
+    });
     draw_rounded_rect(
         ui.frame,
-        ui.cursor.x,
-        ui.cursor.y,
-        w,
-        h,
-        components::BUTTON_BORDER_RADIUS,
+        rect.x,
+        rect.y,
+        rect.w,
+        rect.h,
+        radius,
         bg_col,
         ui.width,
         ui.height,
     );
-    draw_rounded_rect(
-        ui.frame,
-        ui.cursor.x,
-        ui.cursor.y,
-        w,
-        h,
-        components::BUTTON_BORDER_RADIUS,
-        border_col.alpha(80),
-        ui.width,
-        ui.height,
-    );
+    draw_border(ui.frame, rect, radius, &border, ui.width, ui.height);
+    ui.draw_focus_ring(focus_id, rect, radius.clamped(w, h).top_left);
+
+    let text_x = ui.cursor.x + spacing::MD;
+    let text_y = ui.cursor.y + 12.0;
 
     // Texto ou placeholder
     let display_text = if text.is_empty() && !focused {
@@ -482,24 +896,105 @@ pub fn text_input(ui: &mut Ui, modifier: Modifier, placeholder: &str) -> (String
         ui.atlas,
         ui.font,
         font_size::MD,
-        ui.cursor.x + spacing::MD,
-        ui.cursor.y + 12.0,
+        text_x,
+        text_y,
         display_text.0,
         display_text.1,
         ui.width,
         ui.height,
     );
 
+    let mut caret_x = text_x + measure_text(ui.font, font_size::MD, &text);
+
+    // Composição IME inline, sublinhada, logo após o texto confirmado
+    if focused && let Some(preedit) = ui.input.ime_preedit.as_ref().filter(|p| !p.text.is_empty()) {
+        let preedit_x = caret_x;
+        let preedit_w = measure_text(ui.font, font_size::MD, &preedit.text);
+        let theme = ui.theme();
+        let (text_col, accent_col) = (theme.colors.text_primary, theme.colors.primary);
+        drop(theme);
+
+        draw_text_smooth(
+            ui.frame,
+            ui.atlas,
+            ui.font,
+            font_size::MD,
+            preedit_x,
+            text_y,
+            &preedit.text,
+            text_col,
+            ui.width,
+            ui.height,
+        );
+        crate::renderer::draw_rect(
+            ui.frame,
+            preedit_x as i32,
+            (text_y + 20.0) as i32,
+            preedit_w as i32,
+            1,
+            text_col,
+            ui.width,
+            ui.height,
+        );
+
+        // Segmento ativo da composição (range do cursor) com sublinhado grosso; um range
+        // fora do texto ou no meio de um caractere é ignorado
+        let segment = preedit.cursor.and_then(|(start, end)| {
+            Some((preedit.text.get(..start)?, preedit.text.get(..end)?))
+        });
+        match segment {
+            Some((before_start, before_end)) => {
+                let start_x = preedit_x + measure_text(ui.font, font_size::MD, before_start);
+                let end_x = preedit_x + measure_text(ui.font, font_size::MD, before_end);
+                if before_end.len() > before_start.len() {
+                    crate::renderer::draw_rect(
+                        ui.frame,
+                        start_x as i32,
+                        (text_y + 19.0) as i32,
+                        (end_x - start_x) as i32,
+                        2,
+                        accent_col,
+                        ui.width,
+                        ui.height,
+                    );
+                }
+                caret_x = end_x;
+            }
+            None => caret_x = preedit_x + preedit_w,
+        }
+    }
+
     // Cursor de texto piscando
     if focused {
-        let blink_t = (ui.cursor.y as f64 * 0.005).sin();
-        if blink_t > 0.0 {
-            let caret_x = ui.cursor.x + spacing::MD + text.len() as f32 * 9.0;
+        // Posição do caret reportada ao runtime para a janela de candidatos do IME
+        ui.state.borrow_mut().ime_caret = Some(Rect {
+            x: caret_x,
+            y: text_y,
+            w: 2.0,
+            h: 20.0,
+        });
+
+        // Pisca no relógio do frame; reinicia (visível) ao focar ou editar
+        let now = ui.now();
+        let blink_state = ui.use_state_with_id(focus_id, || None::<Instant>);
+        let origin = match blink_state.get() {
+            Some(origin) if text_state.get() == text_before => origin,
+            _ => {
+                blink_state.set(Some(now));
+                now
+            }
+        };
+        let elapsed = now.saturating_duration_since(origin).as_millis() as u64;
+        ui.request_repaint_after(Duration::from_millis(
+            render::CARET_BLINK_MS - elapsed % render::CARET_BLINK_MS,
+        ));
+
+        if (elapsed / render::CARET_BLINK_MS).is_multiple_of(2) {
             let caret_color = ui.theme().colors.text_primary;
             crate::renderer::draw_rect(
                 ui.frame,
                 caret_x as i32,
-                (ui.cursor.y + 12.0) as i32,
+                text_y as i32,
                 2,
                 20,
                 caret_color,
@@ -507,10 +1002,13 @@ pub fn text_input(ui: &mut Ui, modifier: Modifier, placeholder: &str) -> (String
                 ui.height,
             );
         }
+    } else {
+        ui.use_state_with_id(focus_id, || None::<Instant>).set(None);
     }
 
     ui.pop_id();
 
+    ui.mark_drawn(rect);
     (text, rect)
 }
 
@@ -519,186 +1017,115 @@ pub fn text_input(ui: &mut Ui, modifier: Modifier, placeholder: &str) -> (String
 // ============================================================================
 
 pub fn sidebar_item(ui: &mut Ui, label: &str, active: bool) -> (bool, Rect) {
+    sidebar_item_with(ui, Modifier::new(), label, active)
+}
 
+/// Como `sidebar_item`, com ícone inicial opcional (`Modifier::icon`, como no `button`)
+pub fn sidebar_item_with(ui: &mut Ui, modifier: Modifier, label: &str, active: bool) -> (bool, Rect) {
     let widget_id = ui.next_widget_id();
-
     ui.push_id(widget_id);
 
-
-
     let h = components::SIDEBAR_ITEM_HEIGHT;
-
     let rect = Rect {
-
         x: ui.cursor.x,
-
         y: ui.cursor.y,
-
         w: ui.cursor.w,
-
         h,
-
     };
 
-
+    let focus_id = ui.make_id(widget_id);
+    let response = ui.interact(focus_id, rect);
+    let hovered = response.hovered;
 
     // Hover com animação
-
-    let hover_state = ui.use_state_with_id(widget_id, || AnimatedValue::new(0.0));
-
-    let hovered = ui.is_hovered(rect);
-
-
-
-    // Atualiza animação hover
-
-    {
-
-        let mut anim = hover_state.get();
-
-        if hovered {
-
-            anim.set(1.0, render::ANIMATION_DURATION_MS);
-
-        } else {
-
-            anim.set(0.0, render::ANIMATION_DURATION_MS);
-
-        }
-
-        hover_state.set(anim);
-
+    let hover_t = ui.interaction_animation(widget_id, hovered, response.pressed).hover;
+    ui.register_focusable(focus_id);
+    if hovered && ui.input.mouse_just_clicked {
+        ui.request_focus(focus_id);
+    }
+    let clicked = response.clicked || ui.activated_by_keyboard(focus_id);
+    if response.pressed {
+        ui.set_captured_cursor(CursorIcon::Hand);
+    } else if hovered {
+        ui.set_cursor(CursorIcon::Hand);
     }
-
-
-
-    let hover_t = hover_state.get().update();
-
-    let clicked = hovered && ui.input.mouse_just_clicked;
-
-
 
     // Interpola background
-
     let base_alpha = if active { 60 } else { 0 };
-
     let hover_alpha = 20;
-
     let alpha = (base_alpha as f32 + (hover_alpha as f32 - base_alpha as f32) * hover_t) as u8;
 
-
-
     let bg_color = if active {
-
         ui.theme().colors.primary.alpha(60)
-
     } else if hovered {
-
         ui.theme().colors.surface_hover
-
     } else {
-
         ui.theme().colors.background
-
     };
 
-
-
     let text_color = if active {
-
         ui.theme().colors.text_primary
-
     } else if hovered {
-
         ui.theme().colors.text_secondary
-
     } else {
-
         ui.theme().colors.text_secondary.alpha(text_alpha::SECONDARY)
-
     };
 
+    draw_rounded_rect(
+        ui.frame,
+        ui.cursor.x,
+        ui.cursor.y,
+        ui.cursor.w,
+        h,
+        components::SIDEBAR_ITEM_BORDER_RADIUS,
+        bg_color.alpha(alpha.max(base_alpha)),
+        ui.width,
+        ui.height,
+    );
+    ui.draw_focus_ring(focus_id, rect, components::SIDEBAR_ITEM_BORDER_RADIUS);
+
+    let mut text_x = ui.cursor.x + spacing::MD;
+    if let Some(icon) = modifier.icon {
+        let size = components::SIDEBAR_ICON_SIZE;
+        let icon_rect = Rect {
+            x: text_x,
+            y: ui.cursor.y + (h - size) / 2.0,
+            w: size,
+            h: size,
+        };
+        draw_icon(ui.frame, icon, icon_rect, text_color, ui.width, ui.height);
+        text_x += size + components::ICON_GAP;
+    }
 
-
-            draw_rounded_rect(
-
-
-
-                ui.frame,
-
-
-
-                ui.cursor.x,
-
-
-
-                ui.cursor.y,
-
-
-
-                ui.cursor.w,
-
-
-
-                h,
-
-
-
-                components::SIDEBAR_ITEM_BORDER_RADIUS,
-
-
-
-                bg_color.alpha(alpha.max(base_alpha)),
-
-
-
-                ui.width,
-
-
-
-                ui.height,
-
-
-
-            );
-
-            draw_text_smooth(
-
-                ui.frame,
-
-                ui.atlas,
-
-                ui.font,
-
-                font_size::MD,
-
-                ui.cursor.x + spacing::MD,
-
-                ui.cursor.y + 12.0,
-
-                label,
-
-                text_color,
-
-                ui.width,
-
-                ui.height,
-
-            );
-
-
+    draw_text_smooth(
+        ui.frame,
+        ui.atlas,
+        ui.font,
+        font_size::MD,
+        text_x,
+        ui.cursor.y + 12.0,
+        label,
+        text_color,
+        ui.width,
+        ui.height,
+    );
 
     ui.pop_id();
-
+    ui.mark_drawn(rect);
     (clicked, rect)
-
 }
 
 // ============================================================================
 // STAT CARD
 // ============================================================================
 
+/// Card de métrica com o ícone de gráfico na cor de destaque
 pub fn stat_card(ui: &mut Ui, label: &str, value: &str, color: Color) -> Rect {
+    stat_card_with(ui, Modifier::new().icon(Icon::Chart), label, value, color)
+}
+
+/// Como `stat_card`, com o ícone inicial vindo de `Modifier::icon` (nenhum se `None`)
+pub fn stat_card_with(ui: &mut Ui, modifier: Modifier, label: &str, value: &str, color: Color) -> Rect {
     let widget_id = ui.next_widget_id();
     ui.push_id(widget_id);
 
@@ -712,33 +1139,21 @@ pub fn stat_card(ui: &mut Ui, label: &str, value: &str, color: Color) -> Rect {
     };
 
     // Hover com animação de scale (simulado com shadow)
-    let hover_state = ui.use_state_with_id(widget_id, || AnimatedValue::new(0.0));
     let hovered = ui.is_hovered(rect);
+    let hover_t = ui.interaction_animation(widget_id, hovered, false).hover;
+    let shadow_offset = components::CARD_SHADOW_OFFSET_Y + hover_t * 4.0;
 
-    // Atualiza animação hover
-    {
-        let mut anim = hover_state.get();
-        if hovered {
-            anim.set(1.0, render::ANIMATION_DURATION_MS);
-        } else {
-            anim.set(0.0, render::ANIMATION_DURATION_MS);
-        }
-        hover_state.set(anim);
-    }
-
-    let hover_t = hover_state.get().update();
-    let shadow_offset = 4.0 + hover_t * 4.0;
-
-    draw_shadow(
-        ui.frame,
-        ui.cursor.x + 2.0,
-        ui.cursor.y + shadow_offset,
-        w,
-        h,
+    // Sombra de contato + sombra difusa que cresce com o hover
+    let shadow_color = ui.theme().colors.shadow;
+    ui.draw_shadows(
+        rect,
         components::CARD_BORDER_RADIUS,
-        4.0,
-        ui.width,
-        ui.height,
+        &[
+            BoxShadow::new(shadow_color).offset(0.0, 1.0).blur(2.0),
+            BoxShadow::new(shadow_color)
+                .offset(0.0, shadow_offset)
+                .blur(components::CARD_SHADOW_BLUR + hover_t * 8.0),
+        ],
     );
 
     let theme = ui.theme();
@@ -758,12 +1173,25 @@ pub fn stat_card(ui: &mut Ui, label: &str, value: &str, color: Color) -> Rect {
         ui.width,
         ui.height,
     );
+    // Ícone inicial centralizado na altura, com o texto à direita dele
+    let mut text_x = ui.cursor.x + spacing::MD;
+    if let Some(icon) = modifier.icon {
+        let size = components::STAT_CARD_ICON_SIZE;
+        let icon_rect = Rect {
+            x: text_x,
+            y: ui.cursor.y + (h - size) / 2.0,
+            w: size,
+            h: size,
+        };
+        draw_icon(ui.frame, icon, icon_rect, color, ui.width, ui.height);
+        text_x += size + components::ICON_GAP;
+    }
     draw_text_smooth(
         ui.frame,
         ui.atlas,
         ui.font,
         font_size::SM,
-        ui.cursor.x + spacing::MD,
+        text_x,
         ui.cursor.y + spacing::LG,
         label,
         text_secondary,
@@ -775,24 +1203,16 @@ pub fn stat_card(ui: &mut Ui, label: &str, value: &str, color: Color) -> Rect {
         ui.atlas,
         ui.font,
         font_size::XXL,
-        ui.cursor.x + spacing::MD,
+        text_x,
         ui.cursor.y + 45.0,
         value,
         text_primary,
         ui.width,
         ui.height,
     );
-    draw_icon_chart(
-        ui.frame,
-        (ui.cursor.x + 195.0) as i32,
-        (ui.cursor.y + 55.0) as i32,
-        30,
-        color,
-        ui.width,
-        ui.height,
-    );
 
     ui.pop_id();
+    ui.mark_drawn(rect);
     rect
 }
 
@@ -806,7 +1226,7 @@ pub fn card(ui: &mut Ui, modifier: Modifier, content: impl FnOnce(&mut Ui)) -> R
 
     let bg_color = modifier
         .background
-        .unwrap_or(ui.theme().colors.surface.alpha(240));
+        .unwrap_or(ui.theme().colors.surface.alpha(240).into());
     let w = modifier.width.unwrap_or(ui.cursor.w);
     let h = modifier.height.unwrap_or(80.0);
 
@@ -817,28 +1237,37 @@ pub fn card(ui: &mut Ui, modifier: Modifier, content: impl FnOnce(&mut Ui)) -> R
         h,
     };
 
-    draw_shadow(
-        ui.frame,
-        ui.cursor.x,
-        ui.cursor.y,
-        w,
-        h,
-        components::CARD_BORDER_RADIUS,
-        components::CARD_SHADOW_BLUR,
-        ui.width,
-        ui.height,
-    );
-    draw_rounded_rect(
-        ui.frame,
-        ui.cursor.x,
-        ui.cursor.y,
-        w,
-        h,
-        components::CARD_BORDER_RADIUS,
-        bg_color,
-        ui.width,
-        ui.height,
-    );
+    let radius = modifier
+        .radius
+        .unwrap_or(CornerRadii::all(components::CARD_BORDER_RADIUS));
+    // Uma skin nine-slice traz a própria moldura: sem sombra nem fundo padrão
+    if modifier.nine_slice.is_none() || modifier.background.is_some() {
+        let shadow_color = ui.theme().colors.shadow;
+        ui.draw_shadows(
+            rect,
+            radius,
+            &[BoxShadow::new(shadow_color)
+                .offset(0.0, components::CARD_SHADOW_OFFSET_Y)
+                .blur(components::CARD_SHADOW_BLUR)],
+        );
+        draw_rounded_rect(
+            ui.frame,
+            rect.x,
+            rect.y,
+            rect.w,
+            rect.h,
+            radius,
+            bg_color,
+            ui.width,
+            ui.height,
+        );
+    }
+    if let Some(slice) = &modifier.nine_slice {
+        ui.draw_nine_slice(rect, slice);
+    }
+    if let Some(border) = &modifier.border {
+        draw_border(ui.frame, rect, radius, border, ui.width, ui.height);
+    }
 
     let mut sub_ui = Ui {
         frame: &mut *ui.frame,
@@ -867,9 +1296,99 @@ pub fn card(ui: &mut Ui, modifier: Modifier, content: impl FnOnce(&mut Ui)) -> R
     ui.widget_id_counter = sub_ui.widget_id_counter; // This line was missing before!
     ui.pop_id();
 
+    ui.mark_drawn(rect);
     rect // Return rect
 }
 
+// ============================================================================
+// IMAGE / SVG
+// ============================================================================
+
+/// Rect no cursor com o tamanho do modifier ou o natural; com só um lado, o outro
+/// segue a proporção de `natural`
+fn natural_rect(ui: &Ui, modifier: &Modifier, natural: (f32, f32)) -> Rect {
+    let aspect = if natural.0 > 0.0 { natural.1 / natural.0 } else { 1.0 };
+    let (w, h) = match (modifier.width, modifier.height) {
+        (Some(w), Some(h)) => (w, h),
+        (Some(w), None) => (w, w * aspect),
+        (None, Some(h)) => (h / aspect, h),
+        (None, None) => natural,
+    };
+    Rect {
+        x: ui.cursor.x,
+        y: ui.cursor.y,
+        w,
+        h,
+    }
+}
+
+/// Fundo do modifier atrás de imagens e SVGs
+fn draw_media_background(ui: &mut Ui, modifier: &Modifier, rect: Rect, radius: CornerRadii) {
+    if let Some(background) = modifier.background {
+        draw_rounded_rect(
+            ui.frame,
+            rect.x,
+            rect.y,
+            rect.w,
+            rect.h,
+            radius,
+            background,
+            ui.width,
+            ui.height,
+        );
+    }
+}
+
+/// Imagem de um arquivo, bytes ou handle. Sem tamanho no modifier usa o tamanho natural
+/// (com só a largura, a altura segue a proporção); `radius` recorta (avatares)
+pub fn image<'s>(
+    ui: &mut Ui,
+    modifier: Modifier,
+    source: impl Into<ImageSource<'s>>,
+    style: ImageStyle,
+) -> Rect {
+    let handle = ui.load_image(source);
+    let natural = handle.and_then(|h| ui.image_size(h)).unwrap_or((0.0, 0.0));
+    let rect = natural_rect(ui, &modifier, natural);
+
+    let radius = modifier.radius.unwrap_or(CornerRadii::ZERO);
+    draw_media_background(ui, &modifier, rect, radius);
+    if let Some(handle) = handle {
+        ui.draw_image(handle, rect, radius, &style);
+    }
+    if let Some(border) = &modifier.border {
+        draw_border(ui.frame, rect, radius, border, ui.width, ui.height);
+    }
+
+    ui.mark_drawn(rect);
+    rect
+}
+
+/// SVG de um arquivo, bytes ou handle, rasterizado no tamanho final (nítido em qualquer
+/// escala). Dimensionado como `image`; `color` recolore o desenho inteiro (ícones)
+pub fn svg<'s>(
+    ui: &mut Ui,
+    modifier: Modifier,
+    source: impl Into<SvgSource<'s>>,
+    color: Option<Color>,
+) -> Rect {
+    let handle = ui.load_svg(source);
+    let natural = handle.and_then(|h| ui.svg_size(h)).unwrap_or((0.0, 0.0));
+    let rect = natural_rect(ui, &modifier, natural);
+
+    let radius = modifier.radius.unwrap_or(CornerRadii::ZERO);
+    draw_media_background(ui, &modifier, rect, radius);
+    if let Some(handle) = handle {
+        ui.draw_svg(handle, rect, color);
+    }
+    if let Some(border) = &modifier.border {
+        draw_border(ui.frame, rect, radius, border, ui.width, ui.height);
+    }
+
+    ui.mark_drawn(rect);
+    rect
+}
+
 // ============================================================================
 // TEXT
 // ============================================================================
@@ -896,6 +1415,7 @@ pub fn text(ui: &mut Ui, content: &str) -> Rect {
         ui.width,
         ui.height,
     );
+    ui.mark_drawn(rect);
     rect
 }
 
@@ -921,6 +1441,7 @@ pub fn text_muted(ui: &mut Ui, content: &str) -> Rect {
         ui.width,
         ui.height,
     );
+    ui.mark_drawn(rect);
     rect
 }
 
@@ -946,6 +1467,7 @@ pub fn text_heading(ui: &mut Ui, content: &str) -> Rect {
         ui.width,
         ui.height,
     );
+    ui.mark_drawn(rect);
     rect
 }
 
@@ -973,6 +1495,7 @@ pub fn divider(ui: &mut Ui) -> Rect {
         ui.width,
         ui.height,
     );
+    ui.mark_drawn(rect);
     rect
 }
 